@@ -0,0 +1,204 @@
+//! One bot's connection: handshake, then a loop that reads server frames,
+//! sends a `ClientMsg::Input` every sim tick, pings for latency, and
+//! respawns after dying — until the run's deadline.
+
+use anyhow::{anyhow, bail, Result};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, ServerMsg};
+use sim::{EntityKind, GameEvent, PlayerId, ScoreReason, Tick, TICK_DT};
+use tokio::net::TcpStream;
+use tokio::time::{self, Duration, Instant, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::policy::Pilot;
+use crate::stats::BotStats;
+use crate::BotConfig;
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How often each bot sends a WebSocket ping to sample round-trip time.
+const PING_INTERVAL: Duration = Duration::from_millis(500);
+/// Seconds a dead bot waits before asking to respawn, roughly what a
+/// human spends on the game-over screen.
+const RESPAWN_DELAY: Duration = Duration::from_secs(1);
+
+/// Fly bot `index` until `deadline`. Never fails: connection errors end
+/// up in `BotStats::error` so one refused socket doesn't sink the report.
+pub(crate) async fn fly(index: usize, config: &BotConfig, deadline: Instant) -> BotStats {
+    let mut stats = BotStats::default();
+    if let Err(e) = fly_inner(index, config, deadline, &mut stats).await {
+        stats.error = Some(format!("{e:#}"));
+    }
+    stats
+}
+
+async fn fly_inner(
+    index: usize,
+    config: &BotConfig,
+    deadline: Instant,
+    stats: &mut BotStats,
+) -> Result<()> {
+    let (ws, _) = tokio_tungstenite::connect_async(&config.url).await?;
+    let (mut ws_tx, mut ws_rx) = ws.split();
+
+    let hello = ClientMsg::Hello {
+        name: format!("{}{index}", config.name_prefix),
+        ship_class: config.ship_class,
+    };
+    let join_started = Instant::now();
+    send(&mut ws_tx, &hello, stats).await?;
+
+    let (player_id, world_width, mut snapshot) = loop {
+        let bytes = match next_frame(&mut ws_rx).await? {
+            Message::Binary(b) => b,
+            Message::Close(_) => bail!("server closed before Welcome"),
+            _ => continue,
+        };
+        stats.msgs_received += 1;
+        stats.bytes_received += bytes.len() as u64;
+        match protocol::decode::<ServerMsg>(&bytes) {
+            Ok(ServerMsg::Welcome {
+                player_id,
+                world_size,
+                snapshot,
+                ..
+            }) => break (player_id, world_size.x, snapshot),
+            Ok(_) => {}
+            Err(_) => stats.decode_errors += 1,
+        }
+    };
+    stats.join_ms = Some(millis(join_started.elapsed()));
+    stats.connected = true;
+
+    let mut pilot = Pilot::new(config.policy, config.seed.wrapping_add(index as u64));
+    let mut input_tick = time::interval(Duration::from_secs_f32(TICK_DT));
+    input_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut ping_tick = time::interval(PING_INTERVAL);
+    ping_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut ping_seq = 0u64;
+    let mut ping_in_flight: Option<(u64, Instant)> = None;
+    let mut tick = Tick(0);
+    let mut dead_since: Option<Instant> = None;
+    let end = time::sleep_until(deadline);
+    tokio::pin!(end);
+
+    loop {
+        tokio::select! {
+            _ = &mut end => break,
+            frame = next_frame(&mut ws_rx) => match frame? {
+                Message::Binary(bytes) => {
+                    stats.msgs_received += 1;
+                    stats.bytes_received += bytes.len() as u64;
+                    match protocol::decode::<ServerMsg>(&bytes) {
+                        Ok(ServerMsg::Snapshot(s)) => {
+                            stats.snapshots += 1;
+                            snapshot = s;
+                        }
+                        Ok(ServerMsg::Events { events, .. }) => {
+                            stats.event_batches += 1;
+                            for event in &events {
+                                observe(player_id, event, stats, &mut dead_since);
+                            }
+                        }
+                        Ok(
+                            ServerMsg::Welcome { .. }
+                            | ServerMsg::Leaderboard(_)
+                            | ServerMsg::AdminReply { .. }
+                            | ServerMsg::Announcement { .. }
+                            | ServerMsg::Chat { .. }
+                            | ServerMsg::Shutdown { .. },
+                        ) => {}
+                        Ok(ServerMsg::Kicked { reason }) => bail!("kicked: {reason}"),
+                        Err(_) => stats.decode_errors += 1,
+                    }
+                }
+                Message::Pong(payload) => {
+                    if let Some((seq, sent)) = ping_in_flight {
+                        if payload == seq.to_le_bytes() {
+                            stats.rtt_ms.push(millis(sent.elapsed()));
+                            ping_in_flight = None;
+                        }
+                    }
+                }
+                Message::Close(_) => bail!("server closed the connection"),
+                _ => {}
+            },
+            _ = input_tick.tick() => {
+                if let Some(since) = dead_since {
+                    if since.elapsed() >= RESPAWN_DELAY {
+                        dead_since = None;
+                        send(&mut ws_tx, &ClientMsg::Respawn, stats).await?;
+                    }
+                } else {
+                    let me = snapshot.entities.iter().find(|e| {
+                        matches!(e.kind, EntityKind::Player { player_id: p } if p == player_id)
+                    });
+                    let input = match me {
+                        Some(me) => pilot.steer(me, &snapshot, world_width, TICK_DT),
+                        None => Default::default(),
+                    };
+                    tick = tick.next();
+                    send(&mut ws_tx, &ClientMsg::Input { tick, input }, stats).await?;
+                }
+            }
+            _ = ping_tick.tick() => {
+                // One ping in flight at a time keeps the pairing trivial.
+                if ping_in_flight.is_none() {
+                    ping_seq += 1;
+                    ws_tx.send(Message::Ping(ping_seq.to_le_bytes().to_vec())).await?;
+                    ping_in_flight = Some((ping_seq, Instant::now()));
+                }
+            }
+        }
+    }
+
+    send(&mut ws_tx, &ClientMsg::Bye, stats).await?;
+    let _ = ws_tx.close().await;
+    Ok(())
+}
+
+/// Death and kill bookkeeping for this bot's own `player_id`.
+fn observe(
+    player_id: PlayerId,
+    event: &GameEvent,
+    stats: &mut BotStats,
+    dead_since: &mut Option<Instant>,
+) {
+    match *event {
+        GameEvent::PlayerKilled { player_id: p, .. } if p == player_id => {
+            stats.deaths += 1;
+            *dead_since = Some(Instant::now());
+        }
+        GameEvent::ScoreAwarded {
+            player_id: p,
+            reason: ScoreReason::EnemyKill | ScoreReason::TankKill | ScoreReason::PlayerKill,
+            ..
+        } if p == player_id => stats.kills += 1,
+        _ => {}
+    }
+}
+
+async fn next_frame(ws_rx: &mut SplitStream<WsStream>) -> Result<Message> {
+    Ok(ws_rx
+        .next()
+        .await
+        .ok_or_else(|| anyhow!("server closed the connection"))??)
+}
+
+async fn send(
+    ws_tx: &mut SplitSink<WsStream, Message>,
+    msg: &ClientMsg,
+    stats: &mut BotStats,
+) -> Result<()> {
+    let bytes = protocol::encode(msg);
+    stats.msgs_sent += 1;
+    stats.bytes_sent += bytes.len() as u64;
+    ws_tx.send(Message::Binary(bytes)).await?;
+    Ok(())
+}
+
+fn millis(d: Duration) -> f32 {
+    d.as_secs_f32() * 1000.0
+}
@@ -0,0 +1,79 @@
+//! Headless Icarust pilots for load testing. Opens N concurrent WebSocket
+//! connections, speaks the same `protocol` messages as the real client,
+//! flies each ship with a [`Policy`], and rolls what every bot saw into a
+//! [`Report`]: join and round-trip latency, message and byte rates, decode
+//! errors, deaths and kills.
+
+pub mod policy;
+pub mod stats;
+
+mod bot;
+
+use std::sync::Arc;
+
+use sim::ShipClass;
+use tokio::time::{self, Duration, Instant};
+
+pub use policy::Policy;
+pub use stats::{BotStats, Report};
+
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    /// Server WebSocket URL.
+    pub url: String,
+    pub bots: usize,
+    pub policy: Policy,
+    pub ship_class: ShipClass,
+    /// How long each bot flies once the whole swarm is connected.
+    pub duration: Duration,
+    /// Gap between connects so a big swarm doesn't handshake in one burst.
+    pub ramp: Duration,
+    /// Bots are named `<prefix><index>`.
+    pub name_prefix: String,
+    /// Base seed for the random policy; bot `i` uses `seed + i`.
+    pub seed: u64,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        BotConfig {
+            url: format!("ws://{}", protocol::DEFAULT_ADDR),
+            bots: 10,
+            policy: Policy::Seek,
+            ship_class: ShipClass::default(),
+            duration: Duration::from_secs(30),
+            ramp: Duration::from_millis(20),
+            name_prefix: "bot".to_string(),
+            seed: 0,
+        }
+    }
+}
+
+/// Connect the swarm, fly until every bot has had `duration` with the
+/// full swarm up, and report.
+pub async fn run(config: BotConfig) -> Report {
+    let started = Instant::now();
+    let deadline = started + config.ramp * config.bots as u32 + config.duration;
+    let config = Arc::new(config);
+
+    let mut handles = Vec::with_capacity(config.bots);
+    for index in 0..config.bots {
+        let bot_config = config.clone();
+        handles.push(tokio::spawn(async move {
+            bot::fly(index, &bot_config, deadline).await
+        }));
+        time::sleep(config.ramp).await;
+    }
+
+    let mut bots = Vec::with_capacity(handles.len());
+    for handle in handles {
+        bots.push(handle.await.unwrap_or_else(|e| BotStats {
+            error: Some(format!("bot task panicked: {e}")),
+            ..BotStats::default()
+        }));
+    }
+    Report {
+        bots,
+        elapsed: started.elapsed(),
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bots::{BotConfig, Policy};
+use sim::ShipClass;
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let config = parse_args()?;
+    info!(
+        url = %config.url,
+        bots = config.bots,
+        policy = %config.policy,
+        secs = config.duration.as_secs_f32(),
+        "launching bots"
+    );
+    let report = bots::run(config).await;
+    println!("{report}");
+    if report.connected() == 0 {
+        bail!("no bot reached the server");
+    }
+    Ok(())
+}
+
+/// `--connect <url> --bots <n> --policy <random|circle|seek> --ship <class>
+/// --duration <secs> --ramp-ms <ms> --seed <n>`. The URL falls back to
+/// `ICARUST_SERVER` like the real client.
+fn parse_args() -> Result<BotConfig> {
+    let mut config = BotConfig::default();
+    if let Ok(url) = std::env::var("ICARUST_SERVER") {
+        config.url = url;
+    }
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("{flag} needs a value"));
+        match flag.as_str() {
+            "--connect" => config.url = value()?,
+            "--bots" => config.bots = value()?.parse().context("--bots")?,
+            "--policy" => config.policy = value()?.parse::<Policy>()?,
+            "--ship" => {
+                let name = value()?;
+                config.ship_class = ShipClass::ALL
+                    .into_iter()
+                    .find(|c| c.name().eq_ignore_ascii_case(&name))
+                    .ok_or_else(|| anyhow!("unknown ship class {name:?}"))?;
+            }
+            "--duration" => {
+                config.duration =
+                    Duration::from_secs_f32(value()?.parse().context("--duration")?)
+            }
+            "--ramp-ms" => {
+                config.ramp = Duration::from_millis(value()?.parse().context("--ramp-ms")?)
+            }
+            "--seed" => config.seed = value()?.parse().context("--seed")?,
+            other => bail!("unknown flag {other}"),
+        }
+    }
+    Ok(config)
+}
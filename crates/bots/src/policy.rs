@@ -0,0 +1,246 @@
+//! Flight policies. Each bot owns a [`Pilot`] that turns the latest
+//! snapshot into a `PlayerInput` every input tick. No I/O here, so the
+//! steering is testable without a server.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use protocol::{EntityState, Snapshot};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use sim::terrain::ground_surface_at;
+use sim::util::{signed_angular_delta, toroidal_offset};
+use sim::{EntityKind, PlayerInput, Vec2};
+
+/// Height above the local ground below which every policy drops what it's
+/// doing and climbs. Keeps a swarm from spending the run cratering.
+pub const PULL_UP_ALTITUDE: f32 = 80.0;
+/// Seconds the random policy holds one input before rolling another.
+const RANDOM_HOLD: f32 = 0.5;
+/// Turn stick per radian of heading error. High enough to snap onto a
+/// target, low enough not to oscillate once lined up.
+const TURN_GAIN: f32 = 4.0;
+/// Seek holds the trigger when the target is within this many radians of
+/// the nose.
+const FIRE_CONE: f32 = 0.25;
+/// Seek ignores hostiles farther away than this and circles instead.
+const SEEK_RANGE: f32 = 900.0;
+/// Turn stick the circle policy holds.
+const CIRCLE_TURN: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Mash random inputs. Cheap noise for raw connection load.
+    Random,
+    /// Full thrust in a steady turn, trigger held.
+    Circle,
+    /// Chase the nearest hostile in the snapshot and fire when lined up.
+    Seek,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 3] = [Policy::Random, Policy::Circle, Policy::Seek];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Policy::Random => "random",
+            Policy::Circle => "circle",
+            Policy::Seek => "seek",
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Policy::ALL
+            .into_iter()
+            .find(|p| p.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| anyhow!("unknown policy {s:?} (expected random, circle or seek)"))
+    }
+}
+
+/// One bot's brain.
+pub struct Pilot {
+    policy: Policy,
+    rng: ChaCha8Rng,
+    /// Random policy: input currently held and for how much longer.
+    held: PlayerInput,
+    hold_left: f32,
+}
+
+impl Pilot {
+    pub fn new(policy: Policy, seed: u64) -> Self {
+        Pilot {
+            policy,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            held: PlayerInput::default(),
+            hold_left: 0.0,
+        }
+    }
+
+    /// Input for the next tick. `me` is this bot's ship in `snapshot`.
+    pub fn steer(
+        &mut self,
+        me: &EntityState,
+        snapshot: &Snapshot,
+        world_width: f32,
+        dt: f32,
+    ) -> PlayerInput {
+        let pos = Vec2::from(me.pos);
+        if pos.y - ground_surface_at(pos.x, &snapshot.terrain) < PULL_UP_ALTITUDE {
+            return PlayerInput {
+                xaxis: turn_toward(me.facing, 0.0),
+                yaxis: 1.0,
+                fire: false,
+            };
+        }
+        match self.policy {
+            Policy::Random => {
+                self.hold_left -= dt;
+                if self.hold_left <= 0.0 {
+                    self.hold_left = RANDOM_HOLD;
+                    self.held = PlayerInput {
+                        xaxis: self.rng.gen_range(-1.0..=1.0),
+                        yaxis: if self.rng.gen_bool(0.7) { 1.0 } else { 0.0 },
+                        fire: self.rng.gen_bool(0.5),
+                    };
+                }
+                self.held
+            }
+            Policy::Circle => circle(true),
+            Policy::Seek => match nearest_hostile(pos, snapshot, world_width) {
+                Some(offset) => {
+                    let aim = offset.x.atan2(offset.y);
+                    PlayerInput {
+                        xaxis: turn_toward(me.facing, aim),
+                        yaxis: 1.0,
+                        fire: signed_angular_delta(me.facing, aim).abs() < FIRE_CONE,
+                    }
+                }
+                None => circle(false),
+            },
+        }
+    }
+}
+
+fn circle(fire: bool) -> PlayerInput {
+    PlayerInput {
+        xaxis: CIRCLE_TURN,
+        yaxis: 1.0,
+        fire,
+    }
+}
+
+/// Turn stick that swings `facing` toward `target` by the short way.
+fn turn_toward(facing: f32, target: f32) -> f32 {
+    (signed_angular_delta(facing, target) * TURN_GAIN).clamp(-1.0, 1.0)
+}
+
+/// Wrap-aware offset to the closest live enemy ship or tank within
+/// `SEEK_RANGE`.
+fn nearest_hostile(from: Vec2, snapshot: &Snapshot, world_width: f32) -> Option<Vec2> {
+    snapshot
+        .entities
+        .iter()
+        .filter(|e| e.alive && matches!(e.kind, EntityKind::Enemy | EntityKind::Tank))
+        .map(|e| toroidal_offset(from, e.pos.into(), world_width))
+        .filter(|off| off.length() <= SEEK_RANGE)
+        .min_by(|a, b| a.length().total_cmp(&b.length()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim::terrain::{GroundProfile, TerrainBand, TerrainKind};
+    use sim::{Entity, EntityId, PlayerId, ShipClass, Tick};
+
+    const WIDTH: f32 = 3200.0;
+
+    fn snapshot(entities: Vec<EntityState>) -> Snapshot {
+        Snapshot {
+            tick: Tick(0),
+            entities,
+            score_by_player: Vec::new(),
+            player_names: Vec::new(),
+            pilot_stats: Vec::new(),
+            bots: Vec::new(),
+            teams: Vec::new(),
+            team_scores: Vec::new(),
+            objective: None,
+            spectators: 0,
+            level: 1,
+            paused: false,
+            time_scale: 1.0,
+            terrain: vec![TerrainBand {
+                kind: TerrainKind::Ground,
+                profile: GroundProfile::flat(WIDTH, 40.0),
+            }],
+        }
+    }
+
+    fn ship_at(pos: Vec2, facing: f32) -> EntityState {
+        let mut e = Entity::player(EntityId(1), PlayerId(1), pos, ShipClass::Fighter);
+        e.facing = facing;
+        EntityState::from_entity(&e)
+    }
+
+    fn enemy_at(pos: Vec2) -> EntityState {
+        let mut e = Entity::player(EntityId(2), PlayerId(2), pos, ShipClass::Fighter);
+        e.kind = EntityKind::Enemy;
+        EntityState::from_entity(&e)
+    }
+
+    #[test]
+    fn policy_names_round_trip() {
+        for p in Policy::ALL {
+            assert_eq!(p.name().parse::<Policy>().unwrap(), p);
+        }
+        assert!("loop".parse::<Policy>().is_err());
+    }
+
+    #[test]
+    fn seek_turns_toward_a_target_and_fires_once_lined_up() {
+        let mut pilot = Pilot::new(Policy::Seek, 1);
+        // Target due right (+X), ship nose up: turn clockwise, hold fire.
+        let me = ship_at(Vec2::new(1000.0, 500.0), 0.0);
+        let snap = snapshot(vec![me, enemy_at(Vec2::new(1300.0, 500.0))]);
+        let input = pilot.steer(&me, &snap, WIDTH, 1.0 / 60.0);
+        assert!(input.xaxis > 0.0);
+        assert!(!input.fire);
+
+        let me = ship_at(Vec2::new(1000.0, 500.0), std::f32::consts::FRAC_PI_2);
+        let input = pilot.steer(&me, &snap, WIDTH, 1.0 / 60.0);
+        assert!(input.fire);
+    }
+
+    #[test]
+    fn seek_aims_across_the_wrap_seam() {
+        let mut pilot = Pilot::new(Policy::Seek, 1);
+        // Target just across the seam to the left is closer than going right.
+        let me = ship_at(Vec2::new(50.0, 500.0), 0.0);
+        let snap = snapshot(vec![me, enemy_at(Vec2::new(WIDTH - 100.0, 500.0))]);
+        let input = pilot.steer(&me, &snap, WIDTH, 1.0 / 60.0);
+        assert!(input.xaxis < 0.0, "should turn left toward the seam");
+    }
+
+    #[test]
+    fn every_policy_pulls_up_near_the_ground() {
+        for policy in Policy::ALL {
+            let mut pilot = Pilot::new(policy, 7);
+            // Nose pointing right, skimming the 40-unit-high floor.
+            let me = ship_at(Vec2::new(500.0, 60.0), std::f32::consts::FRAC_PI_2);
+            let input = pilot.steer(&me, &snapshot(vec![me]), WIDTH, 1.0 / 60.0);
+            assert!(input.xaxis < 0.0, "{policy} should turn nose-up");
+            assert_eq!(input.yaxis, 1.0);
+        }
+    }
+}
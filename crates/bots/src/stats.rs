@@ -0,0 +1,195 @@
+//! Per-bot counters and the end-of-run report.
+
+use std::fmt;
+use std::time::Duration;
+
+/// What one bot saw over its run.
+#[derive(Debug, Default, Clone)]
+pub struct BotStats {
+    /// Reached `Welcome`.
+    pub connected: bool,
+    /// Hello → Welcome, in milliseconds.
+    pub join_ms: Option<f32>,
+    /// WebSocket ping → pong round trips, in milliseconds.
+    pub rtt_ms: Vec<f32>,
+    pub msgs_received: u64,
+    pub snapshots: u64,
+    pub event_batches: u64,
+    pub bytes_received: u64,
+    /// Binary frames that didn't decode as a `ServerMsg`.
+    pub decode_errors: u64,
+    pub msgs_sent: u64,
+    pub bytes_sent: u64,
+    pub deaths: u32,
+    pub kills: u32,
+    /// Why the connection ended early, if it did.
+    pub error: Option<String>,
+}
+
+/// Aggregate over every bot in a run.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub bots: Vec<BotStats>,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn connected(&self) -> usize {
+        self.bots.iter().filter(|b| b.connected).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.bots.iter().filter(|b| b.error.is_some()).count()
+    }
+
+    pub fn msgs_received(&self) -> u64 {
+        self.bots.iter().map(|b| b.msgs_received).sum()
+    }
+
+    pub fn bytes_received(&self) -> u64 {
+        self.bots.iter().map(|b| b.bytes_received).sum()
+    }
+
+    pub fn decode_errors(&self) -> u64 {
+        self.bots.iter().map(|b| b.decode_errors).sum()
+    }
+
+    /// Every RTT sample across the swarm, sorted ascending.
+    pub fn rtt_ms(&self) -> Vec<f32> {
+        sorted(self.bots.iter().flat_map(|b| b.rtt_ms.iter().copied()))
+    }
+
+    fn join_ms(&self) -> Vec<f32> {
+        sorted(self.bots.iter().filter_map(|b| b.join_ms))
+    }
+
+    fn per_sec(&self, n: u64) -> f64 {
+        n as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sum = |g: fn(&BotStats) -> u64| self.bots.iter().map(g).sum::<u64>();
+        let received = self.msgs_received();
+        let bytes = self.bytes_received();
+        let sent = sum(|b| b.msgs_sent);
+        writeln!(
+            f,
+            "bots       {} requested, {} connected, {} failed",
+            self.bots.len(),
+            self.connected(),
+            self.failed()
+        )?;
+        writeln!(f, "duration   {:.1} s", self.elapsed.as_secs_f32())?;
+        writeln!(f, "join       {}", Latency(&self.join_ms()))?;
+        writeln!(f, "rtt        {}", Latency(&self.rtt_ms()))?;
+        writeln!(
+            f,
+            "received   {received} msgs ({:.0}/s), {} snapshots, {} event batches",
+            self.per_sec(received),
+            sum(|b| b.snapshots),
+            sum(|b| b.event_batches)
+        )?;
+        writeln!(
+            f,
+            "bytes in   {:.2} MiB ({:.1} KiB/s)",
+            bytes as f64 / (1024.0 * 1024.0),
+            self.per_sec(bytes) / 1024.0
+        )?;
+        writeln!(
+            f,
+            "sent       {sent} msgs ({:.0}/s), {:.1} KiB",
+            self.per_sec(sent),
+            sum(|b| b.bytes_sent) as f64 / 1024.0
+        )?;
+        writeln!(f, "decode err {}", self.decode_errors())?;
+        write!(
+            f,
+            "combat     {} deaths, {} kills",
+            sum(|b| b.deaths as u64),
+            sum(|b| b.kills as u64)
+        )?;
+        for (i, b) in self.bots.iter().enumerate() {
+            if let Some(e) = &b.error {
+                write!(f, "\n  bot {i}: {e}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `p50 / p95 / p99 / max` line for a sorted sample set.
+struct Latency<'a>(&'a [f32]);
+
+impl fmt::Display for Latency<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.0;
+        if s.is_empty() {
+            return f.write_str("no samples");
+        }
+        write!(
+            f,
+            "p50 {:.1} ms  p95 {:.1} ms  p99 {:.1} ms  max {:.1} ms  ({} samples)",
+            percentile(s, 0.50),
+            percentile(s, 0.95),
+            percentile(s, 0.99),
+            s[s.len() - 1],
+            s.len()
+        )
+    }
+}
+
+fn sorted(samples: impl Iterator<Item = f32>) -> Vec<f32> {
+    let mut v: Vec<f32> = samples.collect();
+    v.sort_by(f32::total_cmp);
+    v
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+pub fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let rank = (p * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let s: Vec<f32> = (1..=100).map(|i| i as f32).collect();
+        assert_eq!(percentile(&s, 0.50), 50.0);
+        assert_eq!(percentile(&s, 0.95), 95.0);
+        assert_eq!(percentile(&s, 1.0), 100.0);
+        assert_eq!(percentile(&[7.0], 0.99), 7.0);
+    }
+
+    #[test]
+    fn report_sums_across_bots() {
+        let bot = |rtt: Vec<f32>, bytes: u64| BotStats {
+            connected: true,
+            rtt_ms: rtt,
+            msgs_received: 10,
+            bytes_received: bytes,
+            ..BotStats::default()
+        };
+        let report = Report {
+            bots: vec![
+                bot(vec![3.0, 1.0], 100),
+                bot(vec![2.0], 50),
+                BotStats {
+                    error: Some("refused".into()),
+                    ..BotStats::default()
+                },
+            ],
+            elapsed: Duration::from_secs(2),
+        };
+        assert_eq!(report.connected(), 2);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.msgs_received(), 20);
+        assert_eq!(report.bytes_received(), 150);
+        assert_eq!(report.rtt_ms(), vec![1.0, 2.0, 3.0]);
+        assert!(report.to_string().contains("bot 2: refused"));
+    }
+}
@@ -0,0 +1,38 @@
+//! Fly a small swarm against an in-process server and check every bot
+//! joined, flew, and measured something.
+
+use std::time::Duration;
+
+use bots::{BotConfig, Policy};
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn swarm_connects_flies_and_reports() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let _ = server::run_with_listener(listener).await;
+    });
+
+    let report = bots::run(BotConfig {
+        url: format!("ws://{addr}"),
+        bots: 3,
+        policy: Policy::Seek,
+        duration: Duration::from_millis(1200),
+        ramp: Duration::from_millis(5),
+        ..BotConfig::default()
+    })
+    .await;
+
+    assert_eq!(report.connected(), 3, "{report}");
+    assert_eq!(report.failed(), 0, "{report}");
+    assert_eq!(report.decode_errors(), 0);
+    for bot in &report.bots {
+        assert!(bot.join_ms.is_some());
+        assert!(bot.snapshots > 0, "every bot should see snapshots");
+        assert!(bot.msgs_sent > 10, "every bot should stream inputs");
+    }
+    assert!(!report.rtt_ms().is_empty(), "pings should come back");
+
+    server.abort();
+}
@@ -0,0 +1,479 @@
+//! Keyboard bindings: which keys drive which `Action`.
+//!
+//! Every action can have up to `MAX_KEYS` keys, and a key drives at most
+//! one action. Bindings start from a `Preset` (arrow keys or WASD) and are
+//! rebound on the menu's controls screen. They're saved as a small text
+//! file in the user's config directory natively and in `localStorage` on
+//! the web:
+//!
+//! ```text
+//! rotate_left = ArrowLeft, KeyA
+//! fire = Space
+//! ```
+//!
+//! Escape isn't bindable: it quits, and cancels on the controls screen.
+//! Menu navigation and the chat prompt's editing keys are fixed too, and
+//! Chat only takes keys that don't type a character, or the key that
+//! opens the prompt would land in it.
+
+use ggez::input::keyboard::KeyCode;
+
+/// Most keys one action can have. Binding another drops the oldest.
+pub const MAX_KEYS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    RotateLeft,
+    RotateRight,
+    Thrust,
+    Fire,
+    Pause,
+    Chat,
+    /// Held to show the scoreboard.
+    Scoreboard,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Thrust,
+        Action::Fire,
+        Action::Pause,
+        Action::Chat,
+        Action::Scoreboard,
+    ];
+
+    /// Name on the controls screen.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "ROTATE LEFT",
+            Action::RotateRight => "ROTATE RIGHT",
+            Action::Thrust => "THRUST",
+            Action::Fire => "FIRE",
+            Action::Pause => "VOTE PAUSE",
+            Action::Chat => "CHAT",
+            Action::Scoreboard => "SCOREBOARD",
+        }
+    }
+
+    /// Name in the settings file. Don't rename: saved files use it.
+    fn id(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "rotate_left",
+            Action::RotateRight => "rotate_right",
+            Action::Thrust => "thrust",
+            Action::Fire => "fire",
+            Action::Pause => "pause",
+            Action::Chat => "chat",
+            Action::Scoreboard => "scoreboard",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.id() == id)
+    }
+}
+
+/// Starting layouts. Either can be picked on the controls screen, which
+/// replaces every binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preset {
+    #[default]
+    Arrows,
+    Wasd,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 2] = [Preset::Arrows, Preset::Wasd];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Arrows => "ARROWS",
+            Preset::Wasd => "WASD",
+        }
+    }
+
+    /// The preset after this one, wrapping.
+    pub fn next(self) -> Preset {
+        let i = Preset::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Preset::ALL[(i + 1) % Preset::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    /// Indexed by `Action as usize`, oldest binding first.
+    keys: [Vec<KeyCode>; Action::ALL.len()],
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::preset(Preset::default())
+    }
+}
+
+impl Bindings {
+    pub fn preset(preset: Preset) -> Bindings {
+        let (left, right, thrust) = match preset {
+            Preset::Arrows => (KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::ArrowUp),
+            Preset::Wasd => (KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyW),
+        };
+        Bindings {
+            keys: [
+                vec![left],
+                vec![right],
+                vec![thrust],
+                vec![KeyCode::Space],
+                vec![KeyCode::KeyP],
+                vec![KeyCode::Enter],
+                vec![KeyCode::Tab],
+            ],
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        &self.keys[action as usize]
+    }
+
+    /// The action `code` drives, if any.
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|&a| self.keys(a).contains(&code))
+    }
+
+    /// Add `code` to `action`, taking it off whatever action had it.
+    /// Returns false for keys that can't be bound: Escape, anything the
+    /// settings file has no name for, and typing keys for Chat.
+    pub fn bind(&mut self, action: Action, code: KeyCode) -> bool {
+        if code == KeyCode::Escape || key_id(code).is_none() {
+            return false;
+        }
+        if action == Action::Chat && types_text(code) {
+            return false;
+        }
+        for keys in &mut self.keys {
+            keys.retain(|&k| k != code);
+        }
+        let keys = &mut self.keys[action as usize];
+        keys.push(code);
+        if keys.len() > MAX_KEYS {
+            keys.remove(0);
+        }
+        true
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.keys[action as usize].clear();
+    }
+
+    /// "LEFT / A", or "-" when nothing is bound.
+    pub fn describe(&self, action: Action) -> String {
+        let labels: Vec<&str> = self
+            .keys(action)
+            .iter()
+            .filter_map(|&k| key_label(k))
+            .collect();
+        if labels.is_empty() {
+            "-".to_string()
+        } else {
+            labels.join(" / ")
+        }
+    }
+
+    /// The settings file's contents.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for action in Action::ALL {
+            let ids: Vec<&str> = self
+                .keys(action)
+                .iter()
+                .filter_map(|&k| key_id(k))
+                .collect();
+            text.push_str(&format!("{} = {}\n", action.id(), ids.join(", ")));
+        }
+        text
+    }
+
+    /// Read a settings file. Actions it doesn't mention keep their default
+    /// keys; lines or keys it can't make sense of are skipped with a
+    /// warning rather than throwing the whole file away.
+    pub fn from_text(text: &str) -> Bindings {
+        let mut bindings = Bindings::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((action, keys)) = line.split_once('=') else {
+                tracing::warn!(line, "ignoring malformed key binding");
+                continue;
+            };
+            let Some(action) = Action::from_id(action.trim()) else {
+                tracing::warn!(line, "ignoring binding for an unknown action");
+                continue;
+            };
+            bindings.clear(action);
+            for id in keys.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                match key_from_id(id) {
+                    Some(code) if bindings.bind(action, code) => {}
+                    Some(_) => tracing::warn!(key = id, line, "ignoring a key that can't go there"),
+                    None => tracing::warn!(key = id, "ignoring unknown key"),
+                }
+            }
+        }
+        bindings
+    }
+
+    /// Saved bindings, or the default preset when there are none.
+    pub fn load() -> Bindings {
+        match storage::read() {
+            Some(text) => Bindings::from_text(&text),
+            None => Bindings::default(),
+        }
+    }
+
+    /// Write the bindings out. Failing to is logged, not fatal: the game
+    /// keeps the new bindings for this session either way.
+    pub fn save(&self) {
+        if let Err(e) = storage::write(&self.to_text()) {
+            tracing::warn!("couldn't save key bindings: {e}");
+        }
+    }
+}
+
+/// Bindable keys: the code, its name in the settings file (winit's name
+/// for it) and its label on screen.
+const KEYS: &[(KeyCode, &str, &str)] = &[
+    (KeyCode::KeyA, "KeyA", "A"),
+    (KeyCode::KeyB, "KeyB", "B"),
+    (KeyCode::KeyC, "KeyC", "C"),
+    (KeyCode::KeyD, "KeyD", "D"),
+    (KeyCode::KeyE, "KeyE", "E"),
+    (KeyCode::KeyF, "KeyF", "F"),
+    (KeyCode::KeyG, "KeyG", "G"),
+    (KeyCode::KeyH, "KeyH", "H"),
+    (KeyCode::KeyI, "KeyI", "I"),
+    (KeyCode::KeyJ, "KeyJ", "J"),
+    (KeyCode::KeyK, "KeyK", "K"),
+    (KeyCode::KeyL, "KeyL", "L"),
+    (KeyCode::KeyM, "KeyM", "M"),
+    (KeyCode::KeyN, "KeyN", "N"),
+    (KeyCode::KeyO, "KeyO", "O"),
+    (KeyCode::KeyP, "KeyP", "P"),
+    (KeyCode::KeyQ, "KeyQ", "Q"),
+    (KeyCode::KeyR, "KeyR", "R"),
+    (KeyCode::KeyS, "KeyS", "S"),
+    (KeyCode::KeyT, "KeyT", "T"),
+    (KeyCode::KeyU, "KeyU", "U"),
+    (KeyCode::KeyV, "KeyV", "V"),
+    (KeyCode::KeyW, "KeyW", "W"),
+    (KeyCode::KeyX, "KeyX", "X"),
+    (KeyCode::KeyY, "KeyY", "Y"),
+    (KeyCode::KeyZ, "KeyZ", "Z"),
+    (KeyCode::Digit0, "Digit0", "0"),
+    (KeyCode::Digit1, "Digit1", "1"),
+    (KeyCode::Digit2, "Digit2", "2"),
+    (KeyCode::Digit3, "Digit3", "3"),
+    (KeyCode::Digit4, "Digit4", "4"),
+    (KeyCode::Digit5, "Digit5", "5"),
+    (KeyCode::Digit6, "Digit6", "6"),
+    (KeyCode::Digit7, "Digit7", "7"),
+    (KeyCode::Digit8, "Digit8", "8"),
+    (KeyCode::Digit9, "Digit9", "9"),
+    (KeyCode::ArrowUp, "ArrowUp", "UP"),
+    (KeyCode::ArrowDown, "ArrowDown", "DOWN"),
+    (KeyCode::ArrowLeft, "ArrowLeft", "LEFT"),
+    (KeyCode::ArrowRight, "ArrowRight", "RIGHT"),
+    (KeyCode::Space, "Space", "SPACE"),
+    (KeyCode::Enter, "Enter", "ENTER"),
+    (KeyCode::Tab, "Tab", "TAB"),
+    (KeyCode::Backspace, "Backspace", "BACKSPACE"),
+    (KeyCode::ShiftLeft, "ShiftLeft", "LSHIFT"),
+    (KeyCode::ShiftRight, "ShiftRight", "RSHIFT"),
+    (KeyCode::ControlLeft, "ControlLeft", "LCTRL"),
+    (KeyCode::ControlRight, "ControlRight", "RCTRL"),
+    (KeyCode::AltLeft, "AltLeft", "LALT"),
+    (KeyCode::AltRight, "AltRight", "RALT"),
+    (KeyCode::Comma, "Comma", ","),
+    (KeyCode::Period, "Period", "."),
+    (KeyCode::Slash, "Slash", "/"),
+    (KeyCode::Semicolon, "Semicolon", ";"),
+    (KeyCode::Quote, "Quote", "'"),
+    (KeyCode::BracketLeft, "BracketLeft", "["),
+    (KeyCode::BracketRight, "BracketRight", "]"),
+    (KeyCode::Minus, "Minus", "-"),
+    (KeyCode::Equal, "Equal", "="),
+    (KeyCode::Backquote, "Backquote", "`"),
+    (KeyCode::Backslash, "Backslash", "\\"),
+    (KeyCode::Insert, "Insert", "INSERT"),
+    (KeyCode::Delete, "Delete", "DELETE"),
+    (KeyCode::Home, "Home", "HOME"),
+    (KeyCode::End, "End", "END"),
+    (KeyCode::PageUp, "PageUp", "PGUP"),
+    (KeyCode::PageDown, "PageDown", "PGDN"),
+    (KeyCode::Numpad0, "Numpad0", "NUM 0"),
+    (KeyCode::Numpad1, "Numpad1", "NUM 1"),
+    (KeyCode::Numpad2, "Numpad2", "NUM 2"),
+    (KeyCode::Numpad3, "Numpad3", "NUM 3"),
+    (KeyCode::Numpad4, "Numpad4", "NUM 4"),
+    (KeyCode::Numpad5, "Numpad5", "NUM 5"),
+    (KeyCode::Numpad6, "Numpad6", "NUM 6"),
+    (KeyCode::Numpad7, "Numpad7", "NUM 7"),
+    (KeyCode::Numpad8, "Numpad8", "NUM 8"),
+    (KeyCode::Numpad9, "Numpad9", "NUM 9"),
+    (KeyCode::NumpadEnter, "NumpadEnter", "NUM ENTER"),
+];
+
+fn key_id(code: KeyCode) -> Option<&'static str> {
+    KEYS.iter().find(|k| k.0 == code).map(|k| k.1)
+}
+
+fn key_from_id(id: &str) -> Option<KeyCode> {
+    KEYS.iter().find(|k| k.1 == id).map(|k| k.0)
+}
+
+pub fn key_label(code: KeyCode) -> Option<&'static str> {
+    KEYS.iter().find(|k| k.0 == code).map(|k| k.2)
+}
+
+/// Whether `code` types a character too: the keys labelled with their
+/// own character, Space, and the numpad digits.
+fn types_text(code: KeyCode) -> bool {
+    key_label(code).is_some_and(|label| label.chars().count() == 1)
+        || matches!(
+            code,
+            KeyCode::Space
+                | KeyCode::Numpad0
+                | KeyCode::Numpad1
+                | KeyCode::Numpad2
+                | KeyCode::Numpad3
+                | KeyCode::Numpad4
+                | KeyCode::Numpad5
+                | KeyCode::Numpad6
+                | KeyCode::Numpad7
+                | KeyCode::Numpad8
+                | KeyCode::Numpad9
+        )
+}
+
+/// `controls.cfg` under `$XDG_CONFIG_HOME/icarust`, `%APPDATA%\icarust`
+/// or `~/.config/icarust`, whichever is set first.
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::path::PathBuf;
+    use std::{env, fs, io};
+
+    const FILE_NAME: &str = "controls.cfg";
+
+    fn path() -> Option<PathBuf> {
+        let dir = |var: &str| {
+            env::var_os(var)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+        let base = dir("XDG_CONFIG_HOME")
+            .or_else(|| dir("APPDATA"))
+            .or_else(|| dir("HOME").map(|home| home.join(".config")))?;
+        Some(base.join("icarust").join(FILE_NAME))
+    }
+
+    pub fn read() -> Option<String> {
+        fs::read_to_string(path()?).ok()
+    }
+
+    pub fn write(text: &str) -> io::Result<()> {
+        let path = path().ok_or_else(|| io::Error::other("no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    const KEY: &str = "icarust.controls";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read() -> Option<String> {
+        local_storage()?.get_item(KEY).ok()?
+    }
+
+    pub fn write(text: &str) -> Result<(), String> {
+        let storage = local_storage().ok_or("localStorage unavailable")?;
+        storage.set_item(KEY, text).map_err(|e| format!("{e:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_cover_every_action() {
+        for preset in Preset::ALL {
+            let bindings = Bindings::preset(preset);
+            for action in Action::ALL {
+                let key = bindings.keys(action)[0];
+                assert_eq!(bindings.action(key), Some(action));
+            }
+        }
+        assert_eq!(
+            Bindings::preset(Preset::Wasd).action(KeyCode::KeyW),
+            Some(Action::Thrust)
+        );
+        assert_eq!(Bindings::default().action(KeyCode::KeyW), None);
+    }
+
+    #[test]
+    fn binding_a_key_moves_it_and_drops_the_oldest() {
+        let mut bindings = Bindings::default();
+        assert!(bindings.bind(Action::Thrust, KeyCode::Space));
+        assert_eq!(bindings.action(KeyCode::Space), Some(Action::Thrust));
+        assert!(bindings.keys(Action::Fire).is_empty());
+        assert_eq!(bindings.describe(Action::Fire), "-");
+
+        bindings.bind(Action::Thrust, KeyCode::KeyW);
+        bindings.bind(Action::Thrust, KeyCode::KeyK);
+        assert_eq!(
+            bindings.keys(Action::Thrust),
+            [KeyCode::Space, KeyCode::KeyW, KeyCode::KeyK]
+        );
+        assert_eq!(bindings.describe(Action::Thrust), "SPACE / W / K");
+
+        assert!(!bindings.bind(Action::Fire, KeyCode::Escape));
+        assert!(bindings.keys(Action::Fire).is_empty());
+    }
+
+    #[test]
+    fn chat_only_takes_keys_that_dont_type() {
+        let mut bindings = Bindings::default();
+        for code in [KeyCode::KeyT, KeyCode::Slash, KeyCode::Space, KeyCode::Numpad7] {
+            assert!(!bindings.bind(Action::Chat, code), "{code:?}");
+        }
+        assert!(bindings.bind(Action::Chat, KeyCode::Tab));
+        assert_eq!(bindings.keys(Action::Chat), [KeyCode::Enter, KeyCode::Tab]);
+        // Other actions still take them.
+        assert!(bindings.bind(Action::Pause, KeyCode::KeyT));
+
+        let parsed = Bindings::from_text("chat = KeyT, NumpadEnter\n");
+        assert_eq!(parsed.keys(Action::Chat), [KeyCode::NumpadEnter]);
+    }
+
+    #[test]
+    fn settings_text_round_trips_and_skips_junk() {
+        let mut bindings = Bindings::preset(Preset::Wasd);
+        bindings.bind(Action::RotateLeft, KeyCode::ArrowLeft);
+        bindings.clear(Action::Scoreboard);
+        assert_eq!(Bindings::from_text(&bindings.to_text()), bindings);
+
+        let parsed = Bindings::from_text("# mine\nfire = KeyJ, NotAKey\nwarp = KeyX\nnonsense\n");
+        assert_eq!(parsed.keys(Action::Fire), [KeyCode::KeyJ]);
+        assert_eq!(parsed.keys(Action::Thrust), [KeyCode::ArrowUp]);
+        assert_eq!(parsed.action(KeyCode::KeyX), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Listen-server hosting (`--host <port>`). The `server` crate's game loop
+//! runs on a background thread inside the client, with its own tokio
+//! runtime, and the client joins it over loopback like any other pilot.
+//! Friends connect to `public_url`, which the HUD shows.
+//!
+//! Quitting the client stops the server: the other pilots get the usual
+//! shutdown notice and a clean close instead of a dead socket.
+
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use server::shutdown::Shutdown;
+use server::ServerConfig;
+use tracing::{info, warn};
+
+/// Longest `stop` waits for the server to close its connections.
+const STOP_WAIT: Duration = Duration::from_secs(2);
+
+pub struct HostedServer {
+    /// Address for friends to `--connect` to.
+    pub public_url: String,
+    /// Address the hosting client itself connects to.
+    pub local_url: String,
+    shutdown: Shutdown,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl HostedServer {
+    /// Bind every interface on `port` (0 picks a free one) and start
+    /// serving. The socket is bound before this returns, so connecting to
+    /// `local_url` straight away is safe.
+    pub fn start(port: u16) -> Result<Self> {
+        let listener = std::net::TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let shutdown = Shutdown::default();
+        let config = ServerConfig {
+            shutdown: shutdown.clone(),
+            ..ServerConfig::default()
+        };
+
+        let thread = thread::Builder::new()
+            .name("icarust-host".into())
+            .spawn(move || {
+                let rt = match tokio::runtime::Builder::new_multi_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        warn!("failed to start the hosted server's runtime: {e}");
+                        return;
+                    }
+                };
+                let served = rt.block_on(async move {
+                    let listener = tokio::net::TcpListener::from_std(listener)?;
+                    server::run_with_config(listener, config).await
+                });
+                match served {
+                    Ok(()) => info!("hosted server stopped"),
+                    Err(e) => warn!("hosted server failed: {e:#}"),
+                }
+            })?;
+
+        let public_url = format!("ws://{}:{port}", lan_ip());
+        info!(%public_url, "hosting");
+        Ok(HostedServer {
+            public_url,
+            local_url: format!("ws://127.0.0.1:{port}"),
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Warn everyone the host is leaving and close their connections,
+    /// waiting up to `STOP_WAIT` for the server to finish.
+    pub fn stop(&mut self) {
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+        self.shutdown.trigger("The host left.", Duration::ZERO);
+        let deadline = Instant::now() + STOP_WAIT;
+        while !thread.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Best guess at this machine's LAN address: the local end of a UDP socket
+/// pointed at a public address. Connecting a UDP socket sends nothing.
+/// Falls back to loopback when there's no route out.
+fn lan_ip() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
@@ -1,50 +1,171 @@
-use ggez::input::keyboard::{KeyCode, KeyInput};
-use ggez::winit::keyboard::PhysicalKey;
+use ggez::input::gamepad::gilrs::{Axis, Button};
+use ggez::input::keyboard::KeyCode;
 use sim::PlayerInput;
 
+use crate::bindings::Action;
+
+/// Stick travel ignored around the centre, as a fraction of full
+/// deflection, unless `--dead-zone` says otherwise. Worn sticks rarely
+/// rest at exactly zero.
+pub const DEFAULT_DEAD_ZONE: f32 = 0.15;
+/// Trigger travel ignored before thrust starts.
+const TRIGGER_DEAD_ZONE: f32 = 0.05;
+
 #[derive(Debug, Default)]
 pub struct InputState {
     pub xaxis: f32,
     pub yaxis: f32,
     pub fire: bool,
     pub quit: bool,
+    /// Scoreboard key held.
+    pub scoreboard: bool,
     left_held: bool,
     right_held: bool,
+    /// Chat line being typed. While it's `Some`, keys edit the line and
+    /// the ship gets no input.
+    chat_draft: Option<String>,
+    pad: Pad,
+    /// Latest from the on-screen controls (`touch::TouchControls`).
+    touch: PlayerInput,
+}
+
+/// Gamepad half of the controls, kept apart from the keys so letting go
+/// of one never cancels the other.
+#[derive(Debug)]
+struct Pad {
+    dead_zone: f32,
+    /// Left stick X past the dead zone, -1..=1.
+    steer: f32,
+    dpad_left: bool,
+    dpad_right: bool,
+    /// Right trigger past its dead zone, 0..=1.
+    throttle: f32,
+    fire: bool,
+}
+
+impl Default for Pad {
+    fn default() -> Self {
+        Pad {
+            dead_zone: DEFAULT_DEAD_ZONE,
+            steer: 0.0,
+            dpad_left: false,
+            dpad_right: false,
+            throttle: 0.0,
+            fire: false,
+        }
+    }
 }
 
 impl InputState {
-    pub fn handle_key_down(&mut self, input: KeyInput) {
-        let Some(code) = key_code(&input) else { return };
-        match code {
-            KeyCode::ArrowUp => self.yaxis = 1.0,
-            KeyCode::ArrowLeft => {
-                self.left_held = true;
+    /// A bound key went down (`held`) or up. Pause and chat are one-shot
+    /// and handled by `MainState`; only held actions land here.
+    pub fn handle_action(&mut self, action: Action, held: bool) {
+        match action {
+            Action::Thrust => self.yaxis = if held { 1.0 } else { 0.0 },
+            Action::RotateLeft => {
+                self.left_held = held;
                 self.recompute_xaxis();
             }
-            KeyCode::ArrowRight => {
-                self.right_held = true;
+            Action::RotateRight => {
+                self.right_held = held;
                 self.recompute_xaxis();
             }
-            KeyCode::Space => self.fire = true,
-            KeyCode::Escape => self.quit = true,
+            Action::Fire => self.fire = held,
+            Action::Scoreboard => self.scoreboard = held,
+            Action::Pause | Action::Chat => {}
+        }
+    }
+
+    /// Open the chat prompt. Held keys are let go, since the prompt takes
+    /// their key-ups, so the ship doesn't fly off once it closes.
+    pub fn start_chat(&mut self) {
+        self.xaxis = 0.0;
+        self.yaxis = 0.0;
+        self.fire = false;
+        self.scoreboard = false;
+        self.left_held = false;
+        self.right_held = false;
+        self.chat_draft = Some(String::new());
+    }
+
+    /// Clamped to `0.0..=0.9` so the stick always keeps some travel.
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.pad.dead_zone = dead_zone.clamp(0.0, 0.9);
+    }
+
+    /// Left stick X steers in proportion to how far it's pushed.
+    pub fn handle_pad_axis(&mut self, axis: Axis, value: f32) {
+        if axis == Axis::LeftStickX {
+            self.pad.steer = past_dead_zone(value, self.pad.dead_zone);
+        }
+    }
+
+    /// How far the right trigger is pulled, 0 to 1. ggez passes trigger
+    /// presses on but not how far they travel, so `MainState` polls this.
+    pub fn handle_pad_trigger(&mut self, value: f32) {
+        self.pad.throttle = past_dead_zone(value.max(0.0), TRIGGER_DEAD_ZONE);
+    }
+
+    pub fn handle_pad_button(&mut self, button: Button, pressed: bool) {
+        match button {
+            Button::South | Button::RightTrigger => self.pad.fire = pressed,
+            Button::DPadLeft => self.pad.dpad_left = pressed,
+            Button::DPadRight => self.pad.dpad_right = pressed,
+            // Full thrust until `handle_pad_trigger` says how much.
+            Button::RightTrigger2 => self.pad.throttle = if pressed { 1.0 } else { 0.0 },
+            Button::Select => self.scoreboard = pressed,
             _ => {}
         }
     }
 
-    pub fn handle_key_up(&mut self, input: KeyInput) {
-        let Some(code) = key_code(&input) else { return };
+    pub fn set_touch(&mut self, touch: PlayerInput) {
+        self.touch = touch;
+    }
+
+    /// Let go of everything the gamepad was holding, e.g. when it's
+    /// unplugged mid-turn.
+    pub fn release_pad(&mut self) {
+        self.pad = Pad {
+            dead_zone: self.pad.dead_zone,
+            ..Pad::default()
+        };
+    }
+
+    pub fn chat_draft(&self) -> Option<&str> {
+        self.chat_draft.as_deref()
+    }
+
+    /// A typed character, from ggez's text input. Only lands while the
+    /// prompt is open.
+    pub fn handle_text(&mut self, c: char) {
+        let Some(draft) = &mut self.chat_draft else {
+            return;
+        };
+        if !c.is_control() && draft.chars().count() < protocol::MAX_CHAT_CHARS {
+            draft.push(c);
+        }
+    }
+
+    /// Editing keys while the prompt is open. Enter closes it and returns
+    /// the line to send, if there is one; Escape throws the line away.
+    pub fn handle_chat_key(&mut self, code: KeyCode) -> Option<String> {
         match code {
-            KeyCode::ArrowUp => self.yaxis = 0.0,
-            KeyCode::ArrowLeft => {
-                self.left_held = false;
-                self.recompute_xaxis();
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                let line = self.chat_draft.take()?;
+                let line = line.trim();
+                (!line.is_empty()).then(|| line.to_string())
             }
-            KeyCode::ArrowRight => {
-                self.right_held = false;
-                self.recompute_xaxis();
+            KeyCode::Escape => {
+                self.chat_draft = None;
+                None
             }
-            KeyCode::Space => self.fire = false,
-            _ => {}
+            KeyCode::Backspace => {
+                if let Some(draft) = &mut self.chat_draft {
+                    draft.pop();
+                }
+                None
+            }
+            _ => None,
         }
     }
 
@@ -56,18 +177,110 @@ impl InputState {
         };
     }
 
+    /// Keys, gamepad and touch together. Held keys win over the stick,
+    /// and the stick over the touch screen; the D-pad steers like the
+    /// arrow keys. Nothing at all while the chat prompt is open.
     pub fn to_player_input(&self) -> PlayerInput {
+        if self.chat_draft.is_some() {
+            return PlayerInput::default();
+        }
+        let dpad = match (self.pad.dpad_left, self.pad.dpad_right) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => self.pad.steer,
+        };
+        let xaxis = [self.xaxis, dpad, self.touch.xaxis]
+            .into_iter()
+            .find(|&x| x != 0.0)
+            .unwrap_or(0.0);
         PlayerInput {
-            xaxis: self.xaxis,
-            yaxis: self.yaxis,
-            fire: self.fire,
+            xaxis,
+            yaxis: self.yaxis.max(self.pad.throttle).max(self.touch.yaxis),
+            fire: self.fire || self.pad.fire || self.touch.fire,
         }
     }
 }
 
-fn key_code(input: &KeyInput) -> Option<KeyCode> {
-    match input.event.physical_key {
-        PhysicalKey::Code(c) => Some(c),
-        PhysicalKey::Unidentified(_) => None,
+/// Rescale `value` so travel inside `zone` reads as zero and the rest still
+/// spans the full range, keeping the sign.
+pub fn past_dead_zone(value: f32, zone: f32) -> f32 {
+    let magnitude = value.abs().min(1.0);
+    if magnitude <= zone {
+        return 0.0;
+    }
+    value.signum() * (magnitude - zone) / (1.0 - zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_actions_steer_until_released() {
+        let mut input = InputState::default();
+        input.handle_action(Action::RotateLeft, true);
+        input.handle_action(Action::Thrust, true);
+        assert_eq!(input.to_player_input().xaxis, -1.0);
+        input.handle_action(Action::RotateRight, true);
+        assert_eq!(input.to_player_input().xaxis, 0.0);
+        input.handle_action(Action::RotateLeft, false);
+        assert_eq!(input.to_player_input().xaxis, 1.0);
+        assert_eq!(input.to_player_input().yaxis, 1.0);
+    }
+
+    #[test]
+    fn dead_zone_swallows_drift_and_keeps_full_travel() {
+        assert_eq!(past_dead_zone(0.1, 0.15), 0.0);
+        assert_eq!(past_dead_zone(-0.15, 0.15), 0.0);
+        assert_eq!(past_dead_zone(1.0, 0.15), 1.0);
+        assert_eq!(past_dead_zone(-1.0, 0.15), -1.0);
+        assert!((past_dead_zone(0.575, 0.15) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn stick_steers_proportionally_and_keys_win() {
+        let mut input = InputState::default();
+        input.handle_pad_axis(Axis::LeftStickX, -0.575);
+        input.handle_pad_trigger(0.5);
+        let steered = input.to_player_input();
+        assert!((steered.xaxis + 0.5).abs() < 1e-5);
+        assert!(steered.yaxis > 0.0 && steered.yaxis < 0.5);
+
+        input.handle_pad_button(Button::DPadRight, true);
+        assert_eq!(input.to_player_input().xaxis, 1.0);
+
+        input.xaxis = -1.0;
+        assert_eq!(input.to_player_input().xaxis, -1.0);
+    }
+
+    #[test]
+    fn unplugging_releases_the_pad_but_keeps_the_dead_zone() {
+        let mut input = InputState::default();
+        input.set_dead_zone(0.3);
+        input.handle_pad_button(Button::South, true);
+        input.handle_pad_axis(Axis::LeftStickX, 1.0);
+        assert!(input.to_player_input().fire);
+
+        input.release_pad();
+        assert_eq!(input.to_player_input(), PlayerInput::default());
+        input.handle_pad_axis(Axis::LeftStickX, 0.25);
+        assert_eq!(input.to_player_input().xaxis, 0.0);
+    }
+
+    #[test]
+    fn the_chat_prompt_grounds_every_control() {
+        let mut input = InputState::default();
+        input.start_chat();
+        input.set_touch(PlayerInput {
+            xaxis: 1.0,
+            yaxis: 1.0,
+            fire: true,
+        });
+        input.handle_pad_axis(Axis::LeftStickX, -1.0);
+        assert_eq!(input.to_player_input(), PlayerInput::default());
+
+        // The stick is still over when the prompt closes.
+        input.handle_chat_key(KeyCode::Escape);
+        assert_eq!(input.to_player_input().xaxis, -1.0);
     }
 }
@@ -9,35 +9,57 @@ use ggez::conf;
 use ggez::event::EventHandler;
 use ggez::glam::Vec2;
 use ggez::graphics::{self, Canvas, Color, DrawParam, InstanceArray, Mesh};
+use ggez::input::gamepad::gilrs::{Axis, Button};
+use ggez::input::gamepad::GamepadId;
 use ggez::input::keyboard::KeyInput;
+use ggez::winit::event::TouchPhase;
 use ggez::{Context, ContextBuilder, GameResult};
 
 use protocol::{ClientMsg, EntityState, ServerMsg, Snapshot};
 use sim::entity::EntityKind;
-use sim::{GameEvent, PlayerId, Tick};
+use sim::{DeathCause, GameEvent, PlayerId, ShipClass, Tick};
 
 use crate::render::explosion::{Explosion, ExplosionStyle};
 
 pub mod assets;
+pub mod bindings;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod host;
 pub mod input;
 pub mod menu;
 pub mod net;
+pub mod objective_hud;
+pub mod radar;
 pub mod render;
+pub mod scoreboard;
+pub mod spectator;
+pub mod touch;
 pub mod widget;
 
 use crate::assets::{AssetManager, SoundId};
+use crate::bindings::{Action, Bindings};
 use crate::input::InputState;
 use crate::menu::Menu;
-use crate::net::Net;
+use crate::net::{Net, NetStatus};
+use crate::objective_hud::ObjectiveHud;
 use crate::render::camera::{Camera, Point2};
+use crate::render::chat::ChatLog;
 use crate::render::entities::{
-    ship_wing_factor, EntityMeshes, ShipMesh, TankMesh, ENEMY_COLOR, ENEMY_SHOT_COLOR,
-    PLAYER_COLOR, PLAYER_SHOT_COLOR, TANK_COLOR, TANK_SHOT_COLOR, TANK_TREAD_BAND_Y,
-    TANK_TREAD_HALF_WIDTH, TANK_TREAD_LINK_COLOR, TANK_TREAD_LINK_SPACING, TANK_TURRET_PIVOT_Y,
+    ship_wing_factor, EntityMeshes, ShipMesh, TankMesh, CONVOY_COLOR, ENEMY_COLOR,
+    ENEMY_SHOT_COLOR, ESCORT_BOMBER_COLOR, PLAYER_COLOR, PLAYER_SHOT_COLOR, TANK_COLOR,
+    TANK_SHOT_COLOR, TANK_TREAD_BAND_Y, TANK_TREAD_HALF_WIDTH, TANK_TREAD_LINK_COLOR,
+    TANK_TREAD_LINK_SPACING, TANK_TURRET_PIVOT_Y, TEAM_COLORS,
 };
 use crate::render::instance_batch::InstanceQuadBatch;
+use crate::render::killfeed::KillFeed;
 use crate::render::particles::{DamageSmoker, ThrustEmitter};
+use crate::render::popups::ScorePopups;
+use crate::render::radar::Radar;
 use crate::render::sky::{Sky, SKY_COLOR};
+use crate::render::touch::TouchOverlay;
+use crate::scoreboard::{RunSummary, Scoreboard};
+use crate::spectator::SpectatorCam;
+use crate::touch::TouchControls;
 use crate::widget::TextWidget;
 
 /// Top-level UI state. The simulation keeps running on the server in all
@@ -53,6 +75,9 @@ pub enum AppState {
     /// Player just died. World stays visible behind a "GAME OVER" overlay.
     /// Any key press returns to `Menu`; Esc still quits.
     GameOver,
+    /// Joined as a spectator: no ship, no menu. The world renders with the
+    /// camera on a pilot or free-roaming (see `spectator::SpectatorCam`).
+    Spectating,
 }
 
 /// Visible window onto the world, in world units. The world is larger on
@@ -63,10 +88,15 @@ const VIEW_HEIGHT: f32 = sim::world::VIEW_HEIGHT;
 /// How quickly the camera homes in on the player each second. 8.0 is a good
 /// middle ground — responsive without snapping.
 const CAMERA_FOLLOW_RATE: f32 = 8.0;
+/// How long an operator announcement stays on screen.
+const ANNOUNCEMENT_SECS: f32 = 6.0;
 
 fn print_instructions() {
     tracing::info!("Welcome to Icarust!");
-    tracing::info!("Controls: Left/Right rotate, Up thrust, Space fire, Esc quit");
+    tracing::info!("Controls: Left/Right rotate, Up thrust, Space fire, Enter chat, Esc quit");
+    tracing::info!("Rebind them with C on the menu (WASD preset available)");
+    tracing::info!("Gamepad: left stick steer, right trigger thrust, A fire, Start pause");
+    tracing::info!("Spectating (--spectate): Tab/[ ] cycle pilots, F free camera, arrows pan");
 }
 
 /// What to draw for an entity. Ships need their wings scaled separately from
@@ -79,16 +109,20 @@ enum EntityVisual<'a> {
     Single { mesh: &'a Mesh, tint: Color },
 }
 
-/// Pick the right `EntityVisual` for a given entity kind. Each kind has a
-/// dedicated mesh built procedurally at startup (see `render::entities`); the
-/// tint multiplies against the mesh's white vertices so we can retint at draw
-/// time without re-uploading geometry.
-fn visual_for_kind<'a>(meshes: &'a EntityMeshes, kind: &EntityKind) -> EntityVisual<'a> {
+/// Pick the right `EntityVisual` for a given entity. Each kind has a
+/// dedicated mesh built procedurally at startup (see `render::entities`),
+/// and player ships pick theirs by `ship_class`; the tint multiplies against
+/// the mesh's white vertices so we can retint at draw time without
+/// re-uploading geometry. Pilots on a team wear their team's color, and
+/// objective escorts reuse the tank and bomber meshes in their own tints.
+fn visual_for_entity<'a>(meshes: &'a EntityMeshes, entity: &EntityState) -> EntityVisual<'a> {
     use sim::entity::ShotOwner;
-    match kind {
+    match &entity.kind {
         EntityKind::Player { .. } => EntityVisual::Ship {
-            ship: &meshes.player,
-            tint: PLAYER_COLOR,
+            ship: meshes.ship_for(entity.ship_class),
+            tint: entity
+                .team
+                .map_or(PLAYER_COLOR, |t| TEAM_COLORS[t as usize % TEAM_COLORS.len()]),
         },
         EntityKind::Enemy => EntityVisual::Ship {
             ship: &meshes.enemy,
@@ -98,12 +132,26 @@ fn visual_for_kind<'a>(meshes: &'a EntityMeshes, kind: &EntityKind) -> EntityVis
             tank: &meshes.tank,
             tint: TANK_COLOR,
         },
+        EntityKind::Convoy => EntityVisual::Tank {
+            tank: &meshes.tank,
+            tint: CONVOY_COLOR,
+        },
+        EntityKind::Bomber => EntityVisual::Ship {
+            ship: meshes.ship_for(ShipClass::Bomber),
+            tint: ESCORT_BOMBER_COLOR,
+        },
         EntityKind::Shot {
             owner: ShotOwner::Player(_),
         } => EntityVisual::Single {
             mesh: &meshes.shot,
             tint: PLAYER_SHOT_COLOR,
         },
+        EntityKind::Shot {
+            owner: ShotOwner::Bomb(_),
+        } => EntityVisual::Single {
+            mesh: &meshes.bomb,
+            tint: PLAYER_SHOT_COLOR,
+        },
         EntityKind::Shot {
             owner: ShotOwner::Enemy,
         } => EntityVisual::Single {
@@ -146,9 +194,10 @@ fn sprite_half_extent(kind: &EntityKind) -> f32 {
         EntityKind::Player { .. } | EntityKind::Enemy => 18.0,
         // Tank silhouette is widest at the cannon when the turret is
         // horizontal — about 22 world units from chassis center.
-        EntityKind::Tank => 24.0,
+        EntityKind::Tank | EntityKind::Convoy => 24.0,
+        EntityKind::Bomber => 22.0,
         EntityKind::Shot {
-            owner: ShotOwner::Tank,
+            owner: ShotOwner::Tank | ShotOwner::Bomb(_),
         } => 9.0,
         EntityKind::Shot { .. } => 6.0,
     }
@@ -199,6 +248,9 @@ pub struct MainState {
     shot_sound_id: SoundId,
     hit_sound_id: SoundId,
     input: InputState,
+    /// Which keys drive which actions; rebound on the menu's controls
+    /// screen and saved on every change.
+    bindings: Bindings,
     local_player_id: Option<PlayerId>,
     latest_snapshot: Option<Snapshot>,
     /// True once we've snapped the camera onto the first snapshot — earlier
@@ -211,13 +263,51 @@ pub struct MainState {
     gui_dirty: bool,
     score_text: TextWidget,
     level_text: TextWidget,
-    game_over_text: TextWidget,
-    game_over_hint: TextWidget,
+    /// Held up over the world with the Scoreboard key.
+    scoreboard: Scoreboard,
+    /// Replaces the world's HUD once the local pilot is down.
+    run_summary: RunSummary,
     disconnected_text: TextWidget,
+    /// Latest `ServerMsg::Announcement`, shown near the top of the screen
+    /// for `announcement_secs` more seconds.
+    announcement_text: TextWidget,
+    announcement_secs: f32,
+    /// Blinks near the top-center of the HUD while the local
+    /// ship's wing is stalled (aerodynamic flight model only).
+    stall_text: TextWidget,
+    /// Spectator status line along the bottom edge.
+    spectate_text: TextWidget,
+    /// Centered while the latest snapshot says the world is paused.
+    paused_text: TextWidget,
+    /// "Hosting on ws://…" under the score while this client runs the
+    /// server itself; empty otherwise.
+    host_text: TextWidget,
+    /// The listen-server started with `--host`, stopped on quit.
+    #[cfg(not(target_arch = "wasm32"))]
+    host: Option<host::HostedServer>,
+    /// Our standing pause vote, and whether the window losing focus cast
+    /// it (so regaining focus takes it back).
+    pause_vote: bool,
+    paused_on_blur: bool,
+    /// On-screen joystick and fire button, for phones and tablets.
+    touch: TouchControls,
+    touch_overlay: TouchOverlay,
+    /// Whole-world radar strip and off-screen threat arrows.
+    radar: Radar,
+    /// Gamepad that last pressed anything; its trigger is polled for
+    /// throttle. `None` until one is used.
+    active_pad: Option<GamepadId>,
+    /// Gamepads connected as of last frame, to notice hot-plugging.
+    known_pads: Vec<GamepadId>,
+    /// Camera control while `app_state == Spectating`.
+    spectator: SpectatorCam,
     /// Top-level UI state. See `AppState` for transitions.
     app_state: AppState,
     /// Title screen owns its own animation; ticked while `app_state == Menu`.
     menu: Menu,
+    /// Loadout picked on the menu. Sent to the server as `SelectShip`
+    /// whenever it changes; the server applies it on the next spawn.
+    ship_class: ShipClass,
     /// Edge-triggered: Space pressed in `Menu`. Consumed at next update.
     request_start: bool,
     /// Edge-triggered: any key pressed in `GameOver`. Consumed at next update.
@@ -232,6 +322,15 @@ pub struct MainState {
     /// Active particle bursts. Client-side only; each `PlayerKilled` /
     /// `EnemyKilled` / `ShellExploded` event spawns one.
     explosions: Vec<Explosion>,
+    /// Floating "+250 x2" labels from the local pilot's `ScoreAwarded`
+    /// events.
+    score_popups: ScorePopups,
+    /// PvP "KILLER > VICTIM" lines, top right.
+    kill_feed: KillFeed,
+    /// Chat lines, server notices and the open chat prompt.
+    chat: ChatLog,
+    /// Co-op objective briefing line and round results card.
+    objective_hud: ObjectiveHud,
     /// Monotonic counter used as a per-explosion RNG seed so simultaneous
     /// bursts don't render identically.
     next_explosion_seed: u64,
@@ -248,13 +347,18 @@ pub struct MainState {
     /// Tank-tread links, batched into one draw call per frame so multi-tank
     /// scenes don't pay one draw per link.
     tread_batch: InstanceArray,
-    /// Set once `net.is_connected()` first returns false, to swap the overlay
-    /// text and skip the input-send loop.
-    disconnected: bool,
+    /// Last `net.status()` seen. Anything but `Connected` swaps in the
+    /// overlay text and skips the input-send loop.
+    net_status: NetStatus,
+    /// Set by `ServerMsg::Kicked` or `Shutdown`; shown instead of the
+    /// generic message once the connection closes.
+    closed_text: Option<String>,
 }
 
 impl MainState {
-    pub fn new(ctx: &mut Context, net: Box<dyn Net>) -> GameResult<MainState> {
+    /// `spectate` must match the first message `net` sent: `Spectate`
+    /// rather than `Hello` (see `hello_msg`).
+    pub fn new(ctx: &mut Context, net: Box<dyn Net>, spectate: bool) -> GameResult<MainState> {
         print_instructions();
 
         let mut am = AssetManager::new();
@@ -274,13 +378,24 @@ impl MainState {
 
         let score_text = TextWidget::new(ctx, &mut am, 18.0)?;
         let level_text = TextWidget::new(ctx, &mut am, 18.0)?;
-        let mut game_over_text = TextWidget::new(ctx, &mut am, 48.0)?;
-        game_over_text.set_text("GAME OVER", 48.0);
-        let mut game_over_hint = TextWidget::new(ctx, &mut am, 22.0)?;
-        game_over_hint.set_text("press any key for menu", 22.0);
+        let scoreboard = Scoreboard::new(ctx, &mut am)?;
+        let run_summary = RunSummary::new(ctx, &mut am)?;
         let mut disconnected_text = TextWidget::new(ctx, &mut am, 24.0)?;
         disconnected_text.set_text("Connecting…", 24.0);
-        let menu = Menu::new(ctx, &mut am)?;
+        let announcement_text = TextWidget::new(ctx, &mut am, 22.0)?;
+        let mut stall_text = TextWidget::new(ctx, &mut am, 26.0)?;
+        stall_text.set_text("STALL", 26.0);
+        let spectate_text = TextWidget::new(ctx, &mut am, 18.0)?;
+        let mut paused_text = TextWidget::new(ctx, &mut am, 48.0)?;
+        paused_text.set_text("PAUSED", 48.0);
+        let host_text = TextWidget::new(ctx, &mut am, 16.0)?;
+        let bindings = Bindings::load();
+        let mut menu = Menu::new(ctx, &mut am)?;
+        menu.set_bindings(&bindings);
+        let score_popups = ScorePopups::new(am.ensure_default_font(ctx)?);
+        let kill_feed = KillFeed::new(am.ensure_default_font(ctx)?);
+        let chat = ChatLog::new(am.ensure_default_font(ctx)?);
+        let objective_hud = ObjectiveHud::new(ctx, &mut am)?;
 
         // Use the deepest valley as the camera's floor reference so the pilot
         // can dive into low spots without the camera bottoming out on the
@@ -296,6 +411,14 @@ impl MainState {
             ground_y,
         );
 
+        let mut touch = TouchControls::default();
+        touch.set_screen_size(camera.screen_size());
+        if touch_screen_detected() {
+            touch.enable();
+        }
+        let touch_overlay = TouchOverlay::new(ctx)?;
+        let radar = Radar::new(ctx)?;
+
         Ok(MainState {
             asset_manager: am,
             camera,
@@ -306,6 +429,7 @@ impl MainState {
             shot_sound_id,
             hit_sound_id,
             input: InputState::default(),
+            bindings,
             local_player_id: None,
             latest_snapshot: None,
             camera_initialized: false,
@@ -313,23 +437,49 @@ impl MainState {
             gui_dirty: true,
             score_text,
             level_text,
-            game_over_text,
-            game_over_hint,
+            scoreboard,
+            run_summary,
             disconnected_text,
-            app_state: AppState::Menu,
+            announcement_text,
+            announcement_secs: 0.0,
+            stall_text,
+            spectate_text,
+            paused_text,
+            host_text,
+            #[cfg(not(target_arch = "wasm32"))]
+            host: None,
+            pause_vote: false,
+            paused_on_blur: false,
+            touch,
+            touch_overlay,
+            radar,
+            active_pad: None,
+            known_pads: Vec::new(),
+            spectator: SpectatorCam::default(),
+            app_state: if spectate {
+                AppState::Spectating
+            } else {
+                AppState::Menu
+            },
             menu,
+            ship_class: ShipClass::default(),
             request_start: false,
             request_back_to_menu: false,
             cached_score: 0,
             cached_level: 0,
             explosions: Vec::new(),
+            score_popups,
+            kill_feed,
+            chat,
+            objective_hud,
             next_explosion_seed: 1,
             thrust: ThrustEmitter::new(0xF1A4E_AB1u64),
             smoke: DamageSmoker::new(0x5_E0FFEEu64),
             behind_batch,
             overlay_batch,
             tread_batch,
-            disconnected: false,
+            net_status: NetStatus::Connected,
+            closed_text: None,
             time_since_snapshot: 0.0,
         })
     }
@@ -341,13 +491,26 @@ impl MainState {
                 snapshot,
                 ..
             } => {
-                self.local_player_id = Some(player_id);
+                // A spectator's id only names the connection; there's no
+                // ship behind it.
+                if self.app_state == AppState::Spectating {
+                    self.spectator.follow_first(&snapshot);
+                } else {
+                    self.local_player_id = Some(player_id);
+                }
                 self.camera
                     .set_ground_y(sim::terrain::min_surface_y(&snapshot.terrain));
                 self.terrain_renderer.sync(ctx, &snapshot.terrain);
+                self.objective_hud.sync(&snapshot, self.local_player_id);
+                self.scoreboard.sync(&snapshot, self.local_player_id);
+                self.run_summary.sync(&snapshot, self.local_player_id);
                 self.latest_snapshot = Some(snapshot);
                 self.time_since_snapshot = 0.0;
+                // Votes don't outlive the connection that cast them.
+                self.pause_vote = false;
+                self.paused_on_blur = false;
                 self.gui_dirty = true;
+                self.net.send(&ClientMsg::RequestLeaderboard);
             }
             ServerMsg::Snapshot(snap) => {
                 // Terrain doesn't change today, but the camera clamp and
@@ -357,8 +520,15 @@ impl MainState {
                 self.camera
                     .set_ground_y(sim::terrain::min_surface_y(&snap.terrain));
                 self.terrain_renderer.sync(ctx, &snap.terrain);
+                self.objective_hud.sync(&snap, self.local_player_id);
+                self.scoreboard.sync(&snap, self.local_player_id);
+                self.run_summary.sync(&snap, self.local_player_id);
                 self.latest_snapshot = Some(snap);
                 self.time_since_snapshot = 0.0;
+                if self.app_state == AppState::Spectating {
+                    // Names and the spectator count ride on snapshots.
+                    self.gui_dirty = true;
+                }
                 // If we requested a respawn while in GameOver / Menu and our
                 // entity is back in the world, drop the overlay so the next
                 // snapshot draws live gameplay.
@@ -376,6 +546,33 @@ impl MainState {
                     self.handle_game_events(ctx, &events);
                 }
             }
+            ServerMsg::Leaderboard(entries) => {
+                self.menu.set_leaderboard(&entries);
+            }
+            ServerMsg::Kicked { reason } => {
+                tracing::warn!(%reason, "kicked by the server");
+                self.closed_text = Some(format!("Kicked by the server — {reason}"));
+            }
+            ServerMsg::Announcement { text } => {
+                self.chat.push(None, &text);
+                self.announcement_text.set_text(&text, 22.0);
+                self.announcement_secs = ANNOUNCEMENT_SECS;
+                self.gui_dirty = true;
+            }
+            ServerMsg::Chat { from, text } => {
+                self.chat.push(from.as_deref(), &text);
+            }
+            ServerMsg::Shutdown { reason, seconds } => {
+                tracing::warn!(%reason, seconds, "server shutting down");
+                let text = format!("{reason} Closing in {seconds} s.");
+                self.chat.push(None, &text);
+                self.announcement_text.set_text(&text, 22.0);
+                self.announcement_secs = ANNOUNCEMENT_SECS.max(seconds as f32);
+                self.closed_text = Some(format!("Server shut down — {reason}"));
+                self.gui_dirty = true;
+            }
+            // We never send admin commands.
+            ServerMsg::AdminReply { .. } => {}
         }
     }
 
@@ -427,6 +624,15 @@ impl MainState {
                 } => {
                     self.spawn_explosion(Vec2::new(pos.x, pos.y), ExplosionStyle::for_cause(cause));
                     self.play_sound(ctx, self.hit_sound_id);
+                    if let DeathCause::Player(killer) = cause {
+                        let involves_local = self.local_player_id == Some(*killer)
+                            || self.local_player_id == Some(*player_id);
+                        self.kill_feed.push(
+                            &self.pilot_name(*killer),
+                            &self.pilot_name(*player_id),
+                            involves_local,
+                        );
+                    }
                     // Only flip into GameOver if we're currently playing —
                     // dying on the title screen leaves the menu intact; we'll
                     // Respawn whenever the player next hits Space.
@@ -445,7 +651,34 @@ impl MainState {
                     self.spawn_explosion(Vec2::new(pos.x, pos.y), ExplosionStyle::DustAndEmbers);
                     self.play_sound(ctx, self.hit_sound_id);
                 }
-                GameEvent::LevelUp(_) | GameEvent::PlayerJoined(_) | GameEvent::PlayerLeft(_) => {
+                GameEvent::ScoreAwarded {
+                    player_id,
+                    points,
+                    reason,
+                    multiplier,
+                    pos,
+                } => {
+                    if Some(*player_id) == self.local_player_id {
+                        self.score_popups
+                            .spawn(Vec2::new(pos.x, pos.y), *points, *reason, *multiplier);
+                    }
+                    self.gui_dirty = true;
+                }
+                GameEvent::EscortDamaged { pos, .. } => {
+                    let p = Vec2::new(pos.x, pos.y);
+                    self.smoke.spark_burst(p, 10);
+                    self.smoke.puff_burst(p, 4);
+                    self.play_sound(ctx, self.hit_sound_id);
+                }
+                GameEvent::EscortDestroyed { pos } => {
+                    self.spawn_explosion(Vec2::new(pos.x, pos.y), ExplosionStyle::FieryBurst);
+                    self.play_sound(ctx, self.hit_sound_id);
+                }
+                GameEvent::LevelUp(_)
+                | GameEvent::PlayerJoined(_)
+                | GameEvent::PlayerLeft(_)
+                | GameEvent::ObjectiveStarted(_)
+                | GameEvent::ObjectiveEnded { .. } => {
                     self.gui_dirty = true;
                 }
             }
@@ -458,6 +691,15 @@ impl MainState {
         self.explosions.push(Explosion::new(pos, style, seed));
     }
 
+    /// Display name from the latest snapshot, falling back to the id for a
+    /// pilot the snapshot hasn't caught up with yet.
+    fn pilot_name(&self, pid: PlayerId) -> String {
+        self.latest_snapshot
+            .as_ref()
+            .and_then(|s| s.player_names.iter().find(|(p, _)| *p == pid))
+            .map_or_else(|| format!("PILOT {}", pid.0), |(_, name)| name.clone())
+    }
+
     fn local_player(&self) -> Option<&EntityState> {
         let snap = self.latest_snapshot.as_ref()?;
         let pid = self.local_player_id?;
@@ -467,13 +709,23 @@ impl MainState {
         })
     }
 
+    /// Pilot whose score the HUD shows: our own, or the one a spectator is
+    /// watching.
+    fn hud_pilot(&self) -> Option<PlayerId> {
+        if self.app_state == AppState::Spectating {
+            self.spectator.target()
+        } else {
+            self.local_player_id
+        }
+    }
+
     fn refresh_hud(&mut self, ctx: &mut Context) {
         let snap = match &self.latest_snapshot {
             Some(s) => s,
             None => return,
         };
         let score = self
-            .local_player_id
+            .hud_pilot()
             .and_then(|pid| {
                 snap.score_by_player
                     .iter()
@@ -495,19 +747,26 @@ impl MainState {
         let level_w = self.level_text.width(ctx);
         self.score_text
             .set_position(Point2::new(level_w + 25.0, 10.0));
+        // Below the HP and heat bars.
+        self.host_text.set_position(Point2::new(10.0, 52.0));
 
-        let go_w = self.game_over_text.width(ctx);
-        let go_h = self.game_over_text.height(ctx);
-        self.game_over_text.set_position(Point2::new(
-            (screen.x - go_w) / 2.0,
-            (screen.y - go_h) / 2.0,
-        ));
+        self.spectate_text
+            .set_text(&self.spectator.status_line(snap), 18.0);
+        let spectate_w = self.spectate_text.width(ctx);
+        self.spectate_text
+            .set_position(Point2::new((screen.x - spectate_w) / 2.0, screen.y - 36.0));
 
-        let hint_w = self.game_over_hint.width(ctx);
-        self.game_over_hint.set_position(Point2::new(
-            (screen.x - hint_w) / 2.0,
-            (screen.y - go_h) / 2.0 + go_h + 8.0,
-        ));
+        let stall_w = self.stall_text.width(ctx);
+        self.stall_text
+            .set_position(Point2::new((screen.x - stall_w) / 2.0, screen.y * 0.2));
+
+        let paused_w = self.paused_text.width(ctx);
+        self.paused_text
+            .set_position(Point2::new((screen.x - paused_w) / 2.0, screen.y * 0.35));
+
+        let ann_w = self.announcement_text.width(ctx);
+        self.announcement_text
+            .set_position(Point2::new((screen.x - ann_w) / 2.0, screen.y * 0.12));
 
         let dc_w = self.disconnected_text.width(ctx);
         let dc_h = self.disconnected_text.height(ctx);
@@ -538,7 +797,7 @@ impl MainState {
         entity: &EntityState,
         time_since_snapshot: f32,
     ) {
-        let visual = visual_for_kind(meshes, &entity.kind);
+        let visual = visual_for_entity(meshes, entity);
         let half = sprite_half_extent(&entity.kind);
         let scale = camera.scale();
         let pos = extrapolated_pos(entity, time_since_snapshot);
@@ -606,7 +865,7 @@ impl MainState {
     /// and flushed at one point each, so a scene with hundreds of particles
     /// still issues only a couple of draw calls. Order: sky → terrain →
     /// thrust → entities → smoke / sparks / explosions → HUD.
-    fn draw_world(&mut self, _ctx: &mut Context, canvas: &mut Canvas) {
+    fn draw_world(&mut self, ctx: &mut Context, canvas: &mut Canvas) {
         if let Some(snap) = &self.latest_snapshot {
             // Background: cream sky + parallax clouds, behind everything.
             self.sky.draw(canvas, &self.camera);
@@ -654,19 +913,58 @@ impl MainState {
                 ex.fill(&mut self.overlay_batch, &self.camera);
             }
             self.overlay_batch.flush(canvas);
+            self.score_popups.draw(canvas, &self.camera);
+            self.kill_feed.draw(ctx, canvas, self.camera.screen_size().x);
+            self.chat
+                .draw(canvas, self.camera.screen_size(), self.input.chat_draft());
+            self.objective_hud
+                .draw(ctx, canvas, self.camera.screen_size());
+            self.radar
+                .draw(canvas, &self.camera, snap, self.local_player_id);
 
             self.level_text.draw(canvas);
             self.score_text.draw(canvas);
+            self.host_text.draw(canvas);
             if let Some(p) = self.local_player() {
                 if p.alive && p.max_hp > 0 {
                     self.draw_hp_bar(canvas, p.hp, p.max_hp);
+                    // Overheated gauge flashes at ~2 Hz until the lock clears.
+                    let flash = p.gun_overheated
+                        && (ctx.time.time_since_start().as_secs_f32() * 12.0).sin() > 0.0;
+                    self.draw_heat_bar(canvas, p.gun_heat, p.gun_overheated, flash);
                 }
+                if p.alive && p.stalled {
+                    // Blink at ~3 Hz so the warning reads as urgent without
+                    // hiding the world behind it.
+                    let blink = (ctx.time.time_since_start().as_secs_f32() * 18.0).sin() > -0.3;
+                    if blink {
+                        self.stall_text
+                            .draw_with(canvas, Color::new(0.92, 0.30, 0.28, 1.0));
+                    }
+                }
+            }
+            if self.app_state == AppState::Spectating {
+                self.spectate_text
+                    .draw_with(canvas, Color::new(0.28, 0.08, 0.12, 1.0));
+            }
+            if snap.paused {
+                self.paused_text
+                    .draw_with(canvas, Color::new(0.12, 0.10, 0.22, 0.85));
+            }
+            if self.app_state == AppState::Playing {
+                self.touch_overlay.draw(canvas, &self.touch);
             }
             if self.app_state == AppState::GameOver {
-                self.game_over_text.draw(canvas);
-                self.game_over_hint.draw(canvas);
+                self.run_summary
+                    .draw(ctx, canvas, self.camera.screen_size());
+            } else if self.input.scoreboard {
+                self.scoreboard.draw(ctx, canvas, self.camera.screen_size());
+            }
+            if self.announcement_secs > 0.0 {
+                self.announcement_text
+                    .draw_with(canvas, Color::new(0.98, 0.86, 0.36, 1.0));
             }
-            if self.disconnected {
+            if self.net_status != NetStatus::Connected {
                 self.disconnected_text.draw(canvas);
             }
         } else {
@@ -674,6 +972,62 @@ impl MainState {
         }
     }
 
+    /// Switch loadout from the menu picker and tell the server.
+    fn select_ship(&mut self, ship_class: ShipClass) {
+        self.ship_class = ship_class;
+        self.menu.set_ship_class(ship_class);
+        self.net.send(&ClientMsg::SelectShip { ship_class });
+    }
+
+    /// Keep the listen-server this client is hosting running for as long
+    /// as the game is, and show its address.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_host(mut self, host: host::HostedServer) -> Self {
+        self.host_text
+            .set_text(&format!("Hosting on {}", host.public_url), 16.0);
+        self.host = Some(host);
+        self.gui_dirty = true;
+        self
+    }
+
+    /// Post a notice when a gamepad comes or goes, and let go of whatever
+    /// the active one was holding if it's unplugged. ggez doesn't pass
+    /// gilrs's connect/disconnect events on, so compare against last
+    /// frame's list instead.
+    fn watch_gamepads(&mut self, ctx: &Context) {
+        let unchanged = ctx.gamepad.gamepads().count() == self.known_pads.len()
+            && ctx
+                .gamepad
+                .gamepads()
+                .all(|(id, _)| self.known_pads.contains(&id));
+        if unchanged {
+            return;
+        }
+        for (id, pad) in ctx.gamepad.gamepads() {
+            if !self.known_pads.contains(&id) {
+                self.chat
+                    .push(None, &format!("Gamepad connected: {}", pad.name()));
+            }
+        }
+        self.known_pads = ctx.gamepad.gamepads().map(|(id, _)| id).collect();
+        if let Some(id) = self.active_pad {
+            if !self.known_pads.contains(&id) {
+                self.active_pad = None;
+                self.input.release_pad();
+                self.chat.push(None, "Gamepad disconnected");
+            }
+        }
+    }
+
+    /// Cast or take back our pause vote. Spectators have no vote.
+    fn vote_pause(&mut self, pause: bool) {
+        if pause == self.pause_vote || self.app_state == AppState::Spectating {
+            return;
+        }
+        self.pause_vote = pause;
+        self.net.send(&ClientMsg::VotePause { pause });
+    }
+
     /// Apply pending UI transitions queued from key handlers. Runs once at
     /// the top of `update` so the rest of the frame sees the new state.
     fn apply_state_transitions(&mut self) {
@@ -681,7 +1035,7 @@ impl MainState {
             self.request_start = false;
             // If we're currently dead, ask the server to respawn us before
             // entering Playing. Otherwise just take the camera off the menu.
-            if self.local_player().is_none() && !self.disconnected {
+            if self.local_player().is_none() && self.net_status == NetStatus::Connected {
                 self.net.send(&ClientMsg::Respawn);
             }
             // Drop any keys that were already held while on the menu so we
@@ -693,6 +1047,8 @@ impl MainState {
         if self.request_back_to_menu && self.app_state == AppState::GameOver {
             self.request_back_to_menu = false;
             self.menu.set_last_score(Some(self.cached_score));
+            // The run that just ended may have made the table.
+            self.net.send(&ClientMsg::RequestLeaderboard);
             self.input = InputState::default();
             self.app_state = AppState::Menu;
             self.gui_dirty = true;
@@ -740,6 +1096,46 @@ impl MainState {
                 .color(fill),
         );
     }
+
+    /// Gun-heat gauge to the right of the HP bar. Fills warm orange as the
+    /// gun heats up and turns red while it's locked out after overheating;
+    /// `flash` brightens the locked bar so the lockout is hard to miss.
+    fn draw_heat_bar(&self, canvas: &mut Canvas, heat: f32, overheated: bool, flash: bool) {
+        let bar_w: f32 = 80.0;
+        let bar_h: f32 = 10.0;
+        let x: f32 = 10.0 + 200.0 + 12.0;
+        let y: f32 = 36.0;
+        canvas.draw(
+            &graphics::Quad,
+            DrawParam::new()
+                .dest(Vec2::new(x - 1.0, y - 1.0))
+                .scale([bar_w + 2.0, bar_h + 2.0])
+                .color(Color::new(0.20, 0.10, 0.12, 0.75)),
+        );
+        canvas.draw(
+            &graphics::Quad,
+            DrawParam::new()
+                .dest(Vec2::new(x, y))
+                .scale([bar_w, bar_h])
+                .color(Color::new(0.32, 0.22, 0.20, 1.0)),
+        );
+        let fill = if overheated {
+            if flash {
+                Color::new(1.0, 0.45, 0.40, 1.0)
+            } else {
+                Color::new(0.92, 0.30, 0.28, 1.0)
+            }
+        } else {
+            Color::new(0.95, 0.62, 0.25, 1.0)
+        };
+        canvas.draw(
+            &graphics::Quad,
+            DrawParam::new()
+                .dest(Vec2::new(x, y))
+                .scale([bar_w * heat.clamp(0.0, 1.0), bar_h])
+                .color(fill),
+        );
+    }
 }
 
 impl EventHandler for MainState {
@@ -761,18 +1157,45 @@ impl EventHandler for MainState {
             drained += 1;
         }
 
-        let connected = self.net.is_connected();
-        if !connected && !self.disconnected {
-            self.disconnected = true;
-            self.disconnected_text
-                .set_text("Disconnected — server unreachable", 24.0);
+        let status = self.net.status();
+        if status != self.net_status {
+            self.net_status = status;
+            match status {
+                NetStatus::Connected => {}
+                NetStatus::Reconnecting => self.disconnected_text.set_text("Reconnecting…", 24.0),
+                NetStatus::Closed => {
+                    let text = self
+                        .closed_text
+                        .as_deref()
+                        .unwrap_or("Disconnected — server unreachable");
+                    self.disconnected_text.set_text(text, 24.0);
+                }
+            }
             self.gui_dirty = true;
         }
+        let connected = status == NetStatus::Connected;
+
+        self.watch_gamepads(ctx);
+        if let Some(id) = self.active_pad {
+            if self.app_state == AppState::Playing {
+                let pad = ctx.gamepad.gamepad(id);
+                if let Some(trigger) = pad.button_data(Button::RightTrigger2) {
+                    self.input.handle_pad_trigger(trigger.value());
+                }
+            }
+        }
 
         // Resolve pending UI transitions before the fixed-step loop runs,
         // so the right state's input/animation runs this frame.
         self.apply_state_transitions();
 
+        // Fingers still down from another screen don't carry into flight.
+        if self.app_state == AppState::Playing {
+            self.input.set_touch(self.touch.to_player_input());
+        } else {
+            self.touch.release();
+        }
+
         while ctx.time.check_update_time(DESIRED_FPS) {
             if self.input.quit {
                 ctx.request_quit();
@@ -781,7 +1204,8 @@ impl EventHandler for MainState {
             }
 
             // Sends to a dead socket allocate and drop; skip the churn.
-            if !connected {
+            // Spectators have no ship to steer.
+            if !connected || self.app_state == AppState::Spectating {
                 continue;
             }
 
@@ -802,13 +1226,25 @@ impl EventHandler for MainState {
         }
 
         // Step active explosions on real elapsed time so they look the same
-        // regardless of the fixed-step input cadence.
+        // regardless of the fixed-step input cadence. Anything in the world
+        // runs on `world_dt` instead: stopped while the server is paused and
+        // slowed with it, so motion and effects freeze together. The HUD
+        // and camera keep real time.
         let dt = ctx.time.delta().as_secs_f32();
-        self.time_since_snapshot += dt;
+        let world_dt = match &self.latest_snapshot {
+            Some(snap) if snap.paused => 0.0,
+            Some(snap) => dt * snap.time_scale,
+            None => dt,
+        };
+        self.time_since_snapshot += world_dt;
         for ex in &mut self.explosions {
-            ex.update(dt);
+            ex.update(world_dt);
         }
         self.explosions.retain(|e| !e.done());
+        self.score_popups.update(dt);
+        self.kill_feed.update(dt);
+        self.chat.update(dt);
+        self.announcement_secs = (self.announcement_secs - dt).max(0.0);
 
         // Drive camera + particle systems from the latest snapshot. The camera
         // tracks the local player; particle emitters read `thrusting` / `hp`
@@ -819,13 +1255,18 @@ impl EventHandler for MainState {
         // Cheap — `Option::take/replace` just moves the snapshot, no clone.
         let time_since_snapshot = self.time_since_snapshot;
         if let Some(snap) = self.latest_snapshot.take() {
-            // Local player drives the camera. Snap on first frame, ease after.
-            let local = self.local_player_id.and_then(|pid| {
-                snap.entities.iter().find(|e| match e.kind {
-                    EntityKind::Player { player_id } => player_id == pid && e.alive,
-                    _ => false,
+            // Local player drives the camera — or, for a spectator, the
+            // watched pilot. Snap on first frame, ease after.
+            let local = if self.app_state == AppState::Spectating {
+                self.spectator.followed(&snap)
+            } else {
+                self.local_player_id.and_then(|pid| {
+                    snap.entities.iter().find(|e| match e.kind {
+                        EntityKind::Player { player_id } => player_id == pid && e.alive,
+                        _ => false,
+                    })
                 })
-            });
+            };
             if let Some(p) = local {
                 let target = extrapolated_pos(p, time_since_snapshot);
                 if !self.camera_initialized {
@@ -834,6 +1275,9 @@ impl EventHandler for MainState {
                 } else {
                     self.camera.follow(target, dt * CAMERA_FOLLOW_RATE);
                 }
+            } else if self.app_state == AppState::Spectating {
+                let pan = self.spectator.pan_velocity();
+                self.camera.snap_to(self.camera.center() + pan * dt);
             }
             // Pump every player's thrust/smoke emitters every frame so they
             // stop cleanly when the ship dies or the flag flips. Tanks also
@@ -848,19 +1292,25 @@ impl EventHandler for MainState {
                 match e.kind {
                     EntityKind::Player { .. } => {
                         self.thrust
-                            .note_thrust(e.id, pos, e.facing, dt, e.thrusting);
-                        self.smoke.note_health(e.id, pos, e.hp, e.max_hp, 1.0, dt);
+                            .note_thrust(e.id, pos, e.facing, world_dt, e.thrusting);
+                        self.smoke
+                            .note_health(e.id, pos, e.hp, e.max_hp, 1.0, world_dt);
                     }
-                    EntityKind::Tank => {
+                    EntityKind::Tank | EntityKind::Convoy => {
                         let smoke_pos = Vec2::new(pos.x, pos.y + TANK_TURRET_PIVOT_Y);
                         self.smoke
-                            .note_health(e.id, smoke_pos, e.hp, e.max_hp, 0.55, dt);
+                            .note_health(e.id, smoke_pos, e.hp, e.max_hp, 0.55, world_dt);
                     }
                     EntityKind::Enemy => {
                         // Enemies never heal, so a steady trail reads as a
                         // wounded plane heading for a crash. Less dense than
                         // the player's so the sky doesn't fill with brown.
-                        self.smoke.note_health(e.id, pos, e.hp, e.max_hp, 0.7, dt);
+                        self.smoke
+                            .note_health(e.id, pos, e.hp, e.max_hp, 0.7, world_dt);
+                    }
+                    EntityKind::Bomber => {
+                        self.smoke
+                            .note_health(e.id, pos, e.hp, e.max_hp, 0.7, world_dt);
                     }
                     _ => {}
                 }
@@ -875,10 +1325,10 @@ impl EventHandler for MainState {
                 .retain_ids(|id| entities.iter().any(|e| e.id == id));
             self.latest_snapshot = Some(snap);
         }
-        self.thrust.update(dt);
-        self.smoke.update(dt);
+        self.thrust.update(world_dt);
+        self.smoke.update(world_dt);
         let world_size = Vec2::new(sim::world::WORLD_WIDTH, sim::world::WORLD_HEIGHT);
-        self.sky.update(dt, world_size);
+        self.sky.update(world_dt, world_size);
         if self.app_state == AppState::Menu {
             self.menu.update(dt, self.camera.screen_size());
         }
@@ -887,7 +1337,7 @@ impl EventHandler for MainState {
             .latest_snapshot
             .as_ref()
             .and_then(|s| {
-                self.local_player_id.and_then(|pid| {
+                self.hud_pilot().and_then(|pid| {
                     s.score_by_player
                         .iter()
                         .find(|(p, _)| *p == pid)
@@ -913,11 +1363,11 @@ impl EventHandler for MainState {
             AppState::Menu => {
                 let screen = self.camera.screen_size();
                 self.menu.draw(ctx, &mut canvas, &self.meshes, screen);
-                if self.disconnected {
+                if self.net_status != NetStatus::Connected {
                     self.disconnected_text.draw(&mut canvas);
                 }
             }
-            AppState::Playing | AppState::GameOver => {
+            AppState::Playing | AppState::GameOver | AppState::Spectating => {
                 self.draw_world(ctx, &mut canvas);
             }
         }
@@ -928,24 +1378,72 @@ impl EventHandler for MainState {
     }
 
     fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, repeat: bool) -> GameResult {
-        // Esc always quits, regardless of which screen we're on.
         let code = match input.event.physical_key {
             ggez::winit::keyboard::PhysicalKey::Code(c) => Some(c),
             _ => None,
         };
+        // An open chat prompt takes every key; characters arrive through
+        // `text_input_event`.
+        if self.input.chat_draft().is_some() {
+            if let Some(text) = code.and_then(|c| self.input.handle_chat_key(c)) {
+                self.net.send(&ClientMsg::Chat { text });
+            }
+            return Ok(());
+        }
+        // So does the controls screen, where Escape backs out instead.
+        if self.app_state == AppState::Menu && self.menu.controls_open() {
+            if let Some(code) = code.filter(|_| !repeat) {
+                if self.menu.controls_key(code, &mut self.bindings) {
+                    self.bindings.save();
+                }
+            }
+            return Ok(());
+        }
+        // Otherwise Esc always quits, regardless of which screen we're on.
         if code == Some(ggez::input::keyboard::KeyCode::Escape) {
             self.input.quit = true;
             return Ok(());
         }
 
+        let action = code.and_then(|c| self.bindings.action(c));
         match self.app_state {
             AppState::Menu => {
-                if !repeat && code == Some(ggez::input::keyboard::KeyCode::Space) {
-                    self.request_start = true;
+                use ggez::input::keyboard::KeyCode;
+                match code {
+                    Some(KeyCode::Space) if !repeat => self.request_start = true,
+                    Some(KeyCode::ArrowLeft) if !repeat => {
+                        self.select_ship(self.ship_class.prev());
+                    }
+                    Some(KeyCode::ArrowRight) if !repeat => {
+                        self.select_ship(self.ship_class.next());
+                    }
+                    Some(KeyCode::KeyC) if !repeat => self.menu.open_controls(&self.bindings),
+                    _ => {}
+                }
+            }
+            AppState::Playing | AppState::Spectating if action == Some(Action::Chat) && !repeat => {
+                self.input.start_chat();
+            }
+            AppState::Playing if action == Some(Action::Pause) => {
+                if !repeat {
+                    self.vote_pause(!self.pause_vote);
+                    self.paused_on_blur = false;
                 }
             }
             AppState::Playing => {
-                self.input.handle_key_down(input);
+                if let Some(action) = action {
+                    self.input.handle_action(action, true);
+                }
+            }
+            AppState::Spectating => {
+                if action == Some(Action::Scoreboard) {
+                    self.input.handle_action(Action::Scoreboard, true);
+                }
+                if let Some(code) = code {
+                    self.spectator
+                        .handle_key_down(code, self.latest_snapshot.as_ref());
+                    self.gui_dirty = true;
+                }
             }
             AppState::GameOver => {
                 // Any key press returns to the menu. Ignore key repeat so
@@ -958,16 +1456,153 @@ impl EventHandler for MainState {
         Ok(())
     }
 
+    /// Losing focus mid-flight votes to pause, which in single player
+    /// pauses outright; coming back takes that vote back.
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) -> GameResult {
+        if gained {
+            if self.paused_on_blur {
+                self.paused_on_blur = false;
+                self.vote_pause(false);
+            }
+        } else if self.app_state == AppState::Playing && !self.pause_vote {
+            self.paused_on_blur = true;
+            self.vote_pause(true);
+        }
+        Ok(())
+    }
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(host) = &mut self.host {
+            host.stop();
+        }
+        Ok(false)
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> GameResult {
+        self.input.handle_text(character);
+        Ok(())
+    }
+
     fn key_up_event(&mut self, _ctx: &mut Context, input: KeyInput) -> GameResult {
-        // Only Playing tracks held-key state; other states ignore key-ups.
+        // Only Playing and Spectating track held-key state; other states
+        // ignore key-ups.
+        match self.app_state {
+            AppState::Playing => {
+                let code = match input.event.physical_key {
+                    ggez::winit::keyboard::PhysicalKey::Code(c) => Some(c),
+                    _ => None,
+                };
+                if let Some(action) = code.and_then(|c| self.bindings.action(c)) {
+                    self.input.handle_action(action, false);
+                }
+            }
+            AppState::Spectating => {
+                if let ggez::winit::keyboard::PhysicalKey::Code(code) = input.event.physical_key {
+                    if self.bindings.action(code) == Some(Action::Scoreboard) {
+                        self.input.handle_action(Action::Scoreboard, false);
+                    }
+                    self.spectator.handle_key_up(code);
+                }
+            }
+            AppState::Menu | AppState::GameOver => {}
+        }
+        Ok(())
+    }
+
+    /// South (A / Cross) launches from the menu and fires in flight, as
+    /// Space does; the D-pad picks a ship like the arrow keys; Start votes
+    /// to pause like P.
+    fn gamepad_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        id: GamepadId,
+    ) -> GameResult {
+        self.active_pad = Some(id);
+        if self.input.chat_draft().is_some() {
+            return Ok(());
+        }
+        match self.app_state {
+            AppState::Menu if self.menu.controls_open() => {}
+            AppState::Menu => match btn {
+                Button::South | Button::Start => self.request_start = true,
+                Button::DPadLeft => self.select_ship(self.ship_class.prev()),
+                Button::DPadRight => self.select_ship(self.ship_class.next()),
+                _ => {}
+            },
+            AppState::Playing if btn == Button::Start => {
+                self.vote_pause(!self.pause_vote);
+                self.paused_on_blur = false;
+            }
+            AppState::Playing => self.input.handle_pad_button(btn, true),
+            AppState::GameOver => self.request_back_to_menu = true,
+            AppState::Spectating => {}
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        _id: GamepadId,
+    ) -> GameResult {
         if self.app_state == AppState::Playing {
-            self.input.handle_key_up(input);
+            self.input.handle_pad_button(btn, false);
+        }
+        Ok(())
+    }
+
+    fn gamepad_axis_event(
+        &mut self,
+        _ctx: &mut Context,
+        axis: Axis,
+        value: f32,
+        id: GamepadId,
+    ) -> GameResult {
+        self.active_pad = Some(id);
+        if self.app_state == AppState::Playing {
+            self.input.handle_pad_axis(axis, value);
+        }
+        Ok(())
+    }
+
+    /// Replaces ggez's default, which turns touches into mouse clicks. In
+    /// flight touches drive the on-screen controls; elsewhere a tap stands
+    /// in for a key: the sides of the menu pick a ship and the middle
+    /// launches, and Game Over goes back to the menu.
+    fn touch_event(&mut self, _ctx: &mut Context, phase: TouchPhase, x: f64, y: f64) -> GameResult {
+        let pos = Vec2::new(x as f32, y as f32);
+        self.touch.enable();
+        if self.app_state == AppState::Playing {
+            self.touch.handle(phase, pos);
+            return Ok(());
+        }
+        if phase != TouchPhase::Started {
+            return Ok(());
+        }
+        match self.app_state {
+            AppState::Menu if self.menu.controls_open() => {}
+            AppState::Menu => {
+                let width = self.camera.screen_size().x;
+                if pos.x < width * 0.25 {
+                    self.select_ship(self.ship_class.prev());
+                } else if pos.x > width * 0.75 {
+                    self.select_ship(self.ship_class.next());
+                } else {
+                    self.request_start = true;
+                }
+            }
+            AppState::GameOver => self.request_back_to_menu = true,
+            AppState::Playing | AppState::Spectating => {}
         }
         Ok(())
     }
 
     fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult {
         self.camera.set_screen_size(width, height);
+        self.touch.set_screen_size(Vec2::new(width, height));
         self.gui_dirty = true;
         Ok(())
     }
@@ -1002,7 +1637,8 @@ pub fn build_ggez(resource_dir: Option<std::path::PathBuf>) -> ContextBuilder {
 /// the event loop. Returns once the window is closed.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn native_main() -> GameResult {
-    use crate::net::NativeNet;
+    use crate::host::HostedServer;
+    use crate::net::{LocalNet, NativeNet};
     use std::env;
     use std::path;
 
@@ -1013,14 +1649,37 @@ pub fn native_main() -> GameResult {
         )
         .init();
 
-    let (url, name) = parse_args();
-    let net: Box<dyn Net> = match NativeNet::connect(url, name) {
-        Ok(n) => Box::new(n),
+    let LaunchArgs {
+        mut url,
+        name,
+        spectate,
+        offline,
+        host,
+        dead_zone,
+    } = parse_args();
+    let host = match host.map(HostedServer::start).transpose() {
+        Ok(host) => host,
         Err(e) => {
-            eprintln!("could not start net layer: {e:#}");
+            eprintln!("could not start hosting: {e:#}");
             std::process::exit(1);
         }
     };
+    if let Some(host) = &host {
+        url = host.local_url.clone();
+    }
+    let hello = hello_msg(name, spectate);
+    let net: Box<dyn Net> = if offline {
+        tracing::info!("playing offline");
+        Box::new(LocalNet::start(hello))
+    } else {
+        match NativeNet::connect(url, hello) {
+            Ok(n) => Box::new(n),
+            Err(e) => {
+                eprintln!("could not start net layer: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    };
 
     let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
         let mut path = path::PathBuf::from(manifest_dir);
@@ -1035,15 +1694,60 @@ pub fn native_main() -> GameResult {
 
     // `run_with` lets us capture `net` in the state-builder closure — the
     // plain `run::<G>()` path only passes `&mut Context` to `Game::new`.
-    build_ggez(Some(resource_dir)).run_with(move |ctx| MainState::new(ctx, net))
+    build_ggez(Some(resource_dir)).run_with(move |ctx| {
+        let mut state = MainState::new(ctx, net, spectate)?;
+        if let Some(dead_zone) = dead_zone {
+            state.input.set_dead_zone(dead_zone);
+        }
+        Ok(match host {
+            Some(host) => state.with_host(host),
+            None => state,
+        })
+    })
+}
+
+/// First message on a new connection: `Hello` to fly, `Spectate` to watch.
+/// Pilots join in the default loadout; the menu picker sends `SelectShip`
+/// if they change it before launching.
+fn hello_msg(name: String, spectate: bool) -> ClientMsg {
+    if spectate {
+        ClientMsg::Spectate { name }
+    } else {
+        ClientMsg::Hello {
+            name,
+            ship_class: ShipClass::default(),
+        }
+    }
+}
+
+/// Launch options, from the command line natively and the page's query
+/// string on the web.
+struct LaunchArgs {
+    url: String,
+    name: String,
+    spectate: bool,
+    /// Run the world in-process instead of connecting (`LocalNet`).
+    offline: bool,
+    /// Port to host a listen-server on and join (`host`). Native only.
+    host: Option<u16>,
+    /// Gamepad stick dead zone, 0 to 0.9; `input::DEFAULT_DEAD_ZONE` if
+    /// not given.
+    dead_zone: Option<f32>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn parse_args() -> (String, String) {
-    // crude flag parser: --connect <url> --name <name>
+fn parse_args() -> LaunchArgs {
+    // crude flag parser: --connect <url> --name <name> --spectate --offline
+    // --host <port> --dead-zone <0..0.9>
     let mut url = std::env::var("ICARUST_SERVER")
         .unwrap_or_else(|_| format!("ws://{}", protocol::DEFAULT_ADDR));
     let mut name = std::env::var("ICARUST_NAME").unwrap_or_else(|_| "pilot".to_string());
+    let mut spectate = std::env::var("ICARUST_SPECTATE").is_ok_and(|v| v == "1");
+    let mut offline = std::env::var("ICARUST_OFFLINE").is_ok_and(|v| v == "1");
+    let mut host = None;
+    let mut dead_zone = std::env::var("ICARUST_DEAD_ZONE")
+        .ok()
+        .and_then(|v| v.parse().ok());
     let mut args = std::env::args().skip(1);
     while let Some(a) = args.next() {
         match a.as_str() {
@@ -1057,22 +1761,47 @@ fn parse_args() -> (String, String) {
                     name = v;
                 }
             }
+            "--spectate" => spectate = true,
+            "--offline" => offline = true,
+            "--host" => {
+                if let Some(v) = args.next() {
+                    match v.parse() {
+                        Ok(port) => host = Some(port),
+                        Err(_) => eprintln!("--host wants a port number, got {v:?}"),
+                    }
+                }
+            }
+            "--dead-zone" => {
+                if let Some(v) = args.next() {
+                    match v.parse() {
+                        Ok(zone) => dead_zone = Some(zone),
+                        Err(_) => eprintln!("--dead-zone wants a number, got {v:?}"),
+                    }
+                }
+            }
             _ => {}
         }
     }
-    (url, name)
+    LaunchArgs {
+        url,
+        name,
+        spectate,
+        offline,
+        host,
+        dead_zone,
+    }
 }
 
 /// Wasm entry point. wasm-bindgen calls this as the module's `start` hook
 /// when `init()` is awaited in JS. Reads connection params from
-/// `window.location.search` (`?server=ws://…&name=…`), wires up a
-/// `WebSocket`-backed `Net`, and delegates to `ContextBuilder::run_with`,
-/// which on wasm spawns the async build/state setup onto the JS event loop
-/// and returns immediately.
+/// `window.location.search` (`?server=ws://…&name=…`, or `?offline=1` to
+/// play without a server), wires up a `WebSocket`-backed or local `Net`,
+/// and delegates to `ContextBuilder::run_with`, which on wasm spawns the
+/// async build/state setup onto the JS event loop and returns immediately.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen::prelude::wasm_bindgen(start)]
 pub fn wasm_start() -> Result<(), wasm_bindgen::JsValue> {
-    use crate::net::WebNet;
+    use crate::net::{LocalNet, WebNet};
 
     console_error_panic_hook::set_once();
     // tracing-wasm defaults to `report_logs_in_timings: true`, which calls
@@ -1098,13 +1827,32 @@ pub fn wasm_start() -> Result<(), wasm_bindgen::JsValue> {
         return Ok(());
     }
 
-    let (url, name) = wasm_parse_args();
-    tracing::info!(%url, %name, "connecting");
-    let net = WebNet::connect(&url, name)
-        .map_err(|e| wasm_bindgen::JsValue::from_str(&format!("WebNet::connect: {e:#}")))?;
+    let LaunchArgs {
+        url,
+        name,
+        spectate,
+        offline,
+        dead_zone,
+        ..
+    } = wasm_parse_args();
+    let hello = hello_msg(name.clone(), spectate);
+    let net: Box<dyn Net> = if offline {
+        tracing::info!(%name, spectate, "playing offline");
+        Box::new(LocalNet::start(hello))
+    } else {
+        tracing::info!(%url, %name, spectate, "connecting");
+        let net = WebNet::connect(&url, hello)
+            .map_err(|e| wasm_bindgen::JsValue::from_str(&format!("WebNet::connect: {e:#}")))?;
+        Box::new(net)
+    };
 
-    let _ =
-        build_ggez(None).run_with(move |ctx| MainState::new(ctx, Box::new(net) as Box<dyn Net>));
+    let _ = build_ggez(None).run_with(move |ctx| {
+        let mut state = MainState::new(ctx, net, spectate)?;
+        if let Some(dead_zone) = dead_zone {
+            state.input.set_dead_zone(dead_zone);
+        }
+        Ok(state)
+    });
     Ok(())
 }
 
@@ -1120,6 +1868,24 @@ fn webgpu_available() -> bool {
         .unwrap_or(false)
 }
 
+/// Phones and tablets report touch points; the on-screen controls start
+/// enabled there instead of waiting for the first touch.
+#[cfg(target_arch = "wasm32")]
+fn touch_screen_detected() -> bool {
+    web_sys::window()
+        .and_then(|w| {
+            let key = wasm_bindgen::JsValue::from_str("maxTouchPoints");
+            js_sys::Reflect::get(&w.navigator(), &key).ok()
+        })
+        .and_then(|v| v.as_f64())
+        .is_some_and(|points| points > 0.0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn touch_screen_detected() -> bool {
+    false
+}
+
 /// Write a fallback message into `#status` (the boot banner in index.html) so
 /// init failures are visible without opening devtools.
 #[cfg(target_arch = "wasm32")]
@@ -1132,19 +1898,27 @@ fn show_init_error(msg: &str) {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn wasm_parse_args() -> (String, String) {
+fn wasm_parse_args() -> LaunchArgs {
     let default_url = "ws://127.0.0.1:4015".to_string();
     let default_name = "pilot".to_string();
+    let defaults = || LaunchArgs {
+        url: default_url.clone(),
+        name: default_name.clone(),
+        spectate: false,
+        offline: false,
+        host: None,
+        dead_zone: None,
+    };
 
     let Some(window) = web_sys::window() else {
-        return (default_url, default_name);
+        return defaults();
     };
     let Ok(search) = window.location().search() else {
-        return (default_url, default_name);
+        return defaults();
     };
     let params = match web_sys::UrlSearchParams::new_with_str(&search) {
         Ok(p) => p,
-        Err(_) => return (default_url, default_name),
+        Err(_) => return defaults(),
     };
 
     let url = params.get("server").unwrap_or_else(|| {
@@ -1161,5 +1935,15 @@ fn wasm_parse_args() -> (String, String) {
     });
     let url = if url.is_empty() { default_url } else { url };
     let name = params.get("name").unwrap_or(default_name);
-    (url, name)
+    let spectate = params.get("spectate").is_some_and(|v| v == "1");
+    let offline = params.get("offline").is_some_and(|v| v == "1");
+    let dead_zone = params.get("dead_zone").and_then(|v| v.parse().ok());
+    LaunchArgs {
+        url,
+        name,
+        spectate,
+        offline,
+        host: None,
+        dead_zone,
+    }
 }
@@ -6,18 +6,26 @@
 //! menu never touches the game camera or world snapshot.
 //!
 //! Transitions are driven from `MainState`: Space launches into the game,
+//! Left/Right cycle the ship picker (`set_ship_class`), and
 //! `set_last_score` shows the previous run's score under the title after
-//! the player comes back from a death.
+//! the player comes back from a death. `set_leaderboard` fills the
+//! high-score table whenever the server answers a `RequestLeaderboard`.
+//! C opens the controls screen, which takes over the keyboard
+//! (`controls_key`) until Escape closes it.
 
 use ggez::glam::Vec2;
 use ggez::graphics::{
     Canvas, Color, DrawMode, DrawParam, InstanceArray, Mesh, MeshBuilder, MeshData, Vertex,
 };
+use ggez::input::keyboard::KeyCode;
 use ggez::{Context, GameResult};
+use protocol::LeaderboardEntry;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+use sim::ShipClass;
 
 use crate::assets::AssetManager;
+use crate::bindings::{self, Action, Bindings, Preset};
 use crate::render::camera::Point2;
 use crate::render::entities::{ship_wing_factor, EntityMeshes, ENEMY_COLOR, PLAYER_COLOR};
 use crate::widget::TextWidget;
@@ -49,6 +57,9 @@ const HINT_TEXT_COLOR: Color = Color::new(0.18, 0.26, 0.44, 1.0);
 /// Vertical margin from the bottom of the screen to the controls hint
 /// baseline. Keeps the text well clear of any "ground" edge feeling.
 const HINT_BOTTOM_MARGIN: f32 = 32.0;
+/// Rows of the server's high-score table shown under the prompt. The
+/// server sends more; the menu only has room for the top few.
+const HIGH_SCORE_ROWS: usize = 5;
 
 struct BgShip {
     /// Screen-pixel position (top-left origin, Y-down).
@@ -67,6 +78,42 @@ impl BgShip {
     }
 }
 
+/// Rebinding screen. Rows are the actions in `Action::ALL` order, then a
+/// last row that resets everything to a preset.
+struct ControlsScreen {
+    selected: usize,
+    /// Waiting for the key to add to the selected action.
+    capturing: bool,
+    /// Preset the last row applies; Left/Right cycle it.
+    preset: Preset,
+}
+
+impl ControlsScreen {
+    const ROWS: usize = Action::ALL.len() + 1;
+
+    fn text(&self, bindings: &Bindings) -> String {
+        let mut text = String::from("CONTROLS\n");
+        for (row, action) in Action::ALL.into_iter().enumerate() {
+            let cursor = if row == self.selected { ">" } else { " " };
+            let keys = if row == self.selected && self.capturing {
+                "PRESS A KEY  (ESC CANCELS)".to_string()
+            } else {
+                bindings.describe(action)
+            };
+            text.push_str(&format!("\n{cursor} {:<14} {keys}", action.label()));
+        }
+        let cursor = if self.selected == Action::ALL.len() {
+            ">"
+        } else {
+            " "
+        };
+        let preset = self.preset.name();
+        text.push_str(&format!("\n\n{cursor} {:<14} <  {preset}  >", "PRESET"));
+        text.push_str("\n\nUP/DOWN SELECTS  /  ENTER ADDS A KEY  /  BACKSPACE CLEARS  /  ESC BACK");
+        text
+    }
+}
+
 struct BgCloud {
     pos: Vec2,
     drift: f32,
@@ -80,6 +127,16 @@ pub struct Menu {
     prompt: TextWidget,
     controls_hint: TextWidget,
     score_hint: TextWidget,
+    /// "< GUNSHIP >" line above the launch prompt.
+    ship_picker: TextWidget,
+    /// One-line description of the picked class under `ship_picker`.
+    ship_blurb: TextWidget,
+    /// "HIGH SCORES" block under the prompt. Empty until the server
+    /// replies to the first `RequestLeaderboard`.
+    high_scores: TextWidget,
+    /// Open while rebinding; drawn instead of the picker and prompt.
+    controls: Option<ControlsScreen>,
+    controls_list: TextWidget,
     ships: Vec<BgShip>,
     clouds: Vec<BgCloud>,
     cloud_meshes: Vec<Mesh>,
@@ -109,13 +166,18 @@ impl Menu {
 
         let mut controls_hint = TextWidget::new(ctx, am, 18.0)?;
         controls_hint.set_text(
-            "ARROWS TURN  /  UP THRUSTS  /  SPACE FIRES  /  ESC QUITS",
+            "LEFT/RIGHT PICKS SHIP  /  ARROWS TURN  /  UP THRUSTS  /  SPACE FIRES  /  ESC QUITS",
             18.0,
         );
 
         let mut score_hint = TextWidget::new(ctx, am, 22.0)?;
         score_hint.set_text("", 22.0);
 
+        let ship_picker = TextWidget::new(ctx, am, 26.0)?;
+        let ship_blurb = TextWidget::new(ctx, am, 18.0)?;
+        let high_scores = TextWidget::new(ctx, am, 18.0)?;
+        let controls_list = TextWidget::new(ctx, am, 20.0)?;
+
         // Deterministic seed — same layout every boot keeps the screen
         // recognizable without freezing the motion.
         let mut rng = ChaCha8Rng::seed_from_u64(0xAFEE_C0FF_E55E_1234);
@@ -181,12 +243,17 @@ impl Menu {
             .map(|_| InstanceArray::new(ctx, None))
             .collect();
 
-        Ok(Menu {
+        let mut menu = Menu {
             title,
             title_shadow,
             prompt,
             controls_hint,
             score_hint,
+            ship_picker,
+            ship_blurb,
+            high_scores,
+            controls: None,
+            controls_list,
             ships,
             clouds,
             cloud_meshes,
@@ -194,7 +261,10 @@ impl Menu {
             sky_gradient,
             elapsed: 0.0,
             last_screen: initial_screen,
-        })
+        };
+        menu.set_ship_class(ShipClass::default());
+        menu.set_bindings(&Bindings::default());
+        Ok(menu)
     }
 
     pub fn update(&mut self, dt: f32, screen: Vec2) {
@@ -242,6 +312,122 @@ impl Menu {
         }
     }
 
+    /// Replace the high-score table with the server's latest. An empty
+    /// table hides the block.
+    pub fn set_leaderboard(&mut self, entries: &[LeaderboardEntry]) {
+        if entries.is_empty() {
+            self.high_scores.set_text("", 18.0);
+            return;
+        }
+        let mut text = String::from("HIGH SCORES");
+        for (rank, e) in entries.iter().take(HIGH_SCORE_ROWS).enumerate() {
+            let name: String = e.name.chars().take(12).collect::<String>().to_uppercase();
+            text.push_str(&format!(
+                "\n{:>2}. {:<12} {:>7}   LV {:<2} {:>4.0}S",
+                rank + 1,
+                name,
+                e.score,
+                e.level,
+                e.duration_secs
+            ));
+        }
+        self.high_scores.set_text(&text, 18.0);
+    }
+
+    /// Refresh the controls hint, and the controls screen if it's open,
+    /// after the bindings change.
+    pub fn set_bindings(&mut self, bindings: &Bindings) {
+        let first = |action| {
+            bindings
+                .keys(action)
+                .first()
+                .and_then(|&k| bindings::key_label(k))
+                .unwrap_or("-")
+        };
+        self.controls_hint.set_text(
+            &format!(
+                "LEFT/RIGHT PICKS SHIP  /  {}/{} TURN  /  {} THRUSTS  /  {} FIRES  /  \
+                 C CONTROLS  /  ESC QUITS",
+                first(Action::RotateLeft),
+                first(Action::RotateRight),
+                first(Action::Thrust),
+                first(Action::Fire),
+            ),
+            18.0,
+        );
+        if let Some(screen) = &self.controls {
+            self.controls_list.set_text(&screen.text(bindings), 20.0);
+        }
+    }
+
+    pub fn controls_open(&self) -> bool {
+        self.controls.is_some()
+    }
+
+    pub fn open_controls(&mut self, bindings: &Bindings) {
+        self.controls = Some(ControlsScreen {
+            selected: 0,
+            capturing: false,
+            preset: Preset::default(),
+        });
+        self.set_bindings(bindings);
+    }
+
+    /// A key pressed while the controls screen is open. Returns whether
+    /// `bindings` changed, so the caller knows to save them.
+    pub fn controls_key(&mut self, code: KeyCode, bindings: &mut Bindings) -> bool {
+        let Some(screen) = &mut self.controls else {
+            return false;
+        };
+        let action = Action::ALL.get(screen.selected).copied();
+        let rows = ControlsScreen::ROWS;
+        let mut changed = false;
+        if screen.capturing {
+            if code == KeyCode::Escape {
+                screen.capturing = false;
+            } else if let Some(action) = action {
+                // Keys that can't be bound leave it waiting for another.
+                changed = bindings.bind(action, code);
+                screen.capturing = !changed;
+            }
+        } else {
+            match code {
+                KeyCode::Escape => {
+                    self.controls = None;
+                    return false;
+                }
+                KeyCode::ArrowUp => screen.selected = (screen.selected + rows - 1) % rows,
+                KeyCode::ArrowDown => screen.selected = (screen.selected + 1) % rows,
+                KeyCode::ArrowLeft | KeyCode::ArrowRight if action.is_none() => {
+                    screen.preset = screen.preset.next();
+                }
+                KeyCode::Enter | KeyCode::NumpadEnter => match action {
+                    Some(_) => screen.capturing = true,
+                    None => {
+                        *bindings = Bindings::preset(screen.preset);
+                        changed = true;
+                    }
+                },
+                KeyCode::Backspace | KeyCode::Delete => {
+                    if let Some(action) = action {
+                        bindings.clear(action);
+                        changed = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.set_bindings(bindings);
+        changed
+    }
+
+    /// Show `class` in the ship picker.
+    pub fn set_ship_class(&mut self, class: ShipClass) {
+        self.ship_picker
+            .set_text(&format!("<  {}  >", class.name()), 26.0);
+        self.ship_blurb.set_text(ship_blurb(class), 18.0);
+    }
+
     pub fn draw(
         &mut self,
         ctx: &mut Context,
@@ -313,6 +499,27 @@ impl Menu {
             .set_position(Point2::new((screen.x - sc_w) / 2.0, sc_y));
         self.score_hint.draw_with(canvas, PROMPT_COLOR);
 
+        if self.controls.is_some() {
+            let list_w = self.controls_list.width(ctx);
+            let list_y = screen.y * 0.34;
+            self.controls_list
+                .set_position(Point2::new((screen.x - list_w) / 2.0, list_y));
+            self.controls_list.draw_with(canvas, PROMPT_COLOR);
+            return;
+        }
+
+        // Ship picker sits between the title block and the prompt.
+        let picker_w = self.ship_picker.width(ctx);
+        let picker_y = screen.y * 0.46;
+        self.ship_picker
+            .set_position(Point2::new((screen.x - picker_w) / 2.0, picker_y));
+        self.ship_picker.draw_with(canvas, PROMPT_COLOR);
+        let blurb_w = self.ship_blurb.width(ctx);
+        let blurb_y = picker_y + self.ship_picker.height(ctx) + 6.0;
+        self.ship_blurb
+            .set_position(Point2::new((screen.x - blurb_w) / 2.0, blurb_y));
+        self.ship_blurb.draw_with(canvas, HINT_TEXT_COLOR);
+
         // Pulsing "press space" prompt.
         let prompt_w = self.prompt.width(ctx);
         let prompt_y = screen.y * 0.62;
@@ -324,6 +531,13 @@ impl Menu {
             Color::new(PROMPT_COLOR.r, PROMPT_COLOR.g, PROMPT_COLOR.b, pulse),
         );
 
+        // High scores centered under the prompt.
+        let hs_w = self.high_scores.width(ctx);
+        let hs_y = prompt_y + self.prompt.height(ctx) + 18.0;
+        self.high_scores
+            .set_position(Point2::new((screen.x - hs_w) / 2.0, hs_y));
+        self.high_scores.draw_with(canvas, HINT_TEXT_COLOR);
+
         // Controls hint floats over the sky near the bottom — no band.
         // Margin keeps it well clear of the bottom edge so it doesn't read
         // like ground text.
@@ -337,6 +551,16 @@ impl Menu {
     }
 }
 
+/// Menu description for each ship class.
+fn ship_blurb(class: ShipClass) -> &'static str {
+    match class {
+        ShipClass::Fighter => "ALL-ROUNDER  -  ONE GUN",
+        ShipClass::Interceptor => "FAST AND NIMBLE  -  FRAGILE",
+        ShipClass::Gunship => "SLOW AND TANKY  -  TWIN GUNS",
+        ShipClass::Bomber => "HEAVY  -  BOMBS CRACK TANKS",
+    }
+}
+
 /// Unit-rect mesh whose top edge is `top` and bottom edge is `bottom`.
 /// ggez interpolates the per-vertex colors across the quad, giving a
 /// smooth vertical gradient that scales cleanly to any screen size.
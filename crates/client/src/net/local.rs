@@ -0,0 +1,317 @@
+//! Offline `Net` impl: no socket, no server. The client owns a
+//! `sim::World` and plays the server's part in-process, answering `Hello`
+//! with `Welcome` and feeding back `Snapshot`s and `Events` as the world
+//! ticks.
+//!
+//! There's no thread either, so it runs the same on wasm. The world steps
+//! from `try_recv`, at `sim::TICK_DT`, for however much wall-clock time has
+//! passed since the last call. Catch-up is capped at `MAX_CATCH_UP`: when
+//! ggez stops calling `update` (a hidden tab, a dragged window) the world
+//! simply waits instead of fast-forwarding through the gap afterwards.
+//!
+//! Single player only: there's nobody to vote with, so `VotePause` pauses
+//! outright, operator commands are refused and nothing goes on a
+//! high-score table.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use protocol::{ClientMsg, ServerMsg};
+use sim::{GameEvent, PlayerId, PlayerInputs, World, WorldConfig};
+
+use super::session::NetStatus;
+use super::Net;
+
+/// Most wall-clock time made up in one `try_recv`, in seconds.
+const MAX_CATCH_UP: f64 = 0.25;
+/// The offline pilot. Any id would do with nobody else around.
+const LOCAL_PILOT: PlayerId = PlayerId(1);
+/// Wingmen flying about for an offline spectator to watch.
+const SPECTATE_BOTS: u32 = 2;
+
+pub struct LocalNet {
+    world: World,
+    inputs: PlayerInputs,
+    /// Messages the game sent since the last step. `send` takes `&self`.
+    inbox: RefCell<Vec<ClientMsg>>,
+    outbox: VecDeque<ServerMsg>,
+    /// Our name, from `Hello`, for chat echoes.
+    name: String,
+    /// Wall-clock time not yet stepped through, in seconds.
+    unsimulated: f64,
+    last_pump: Option<f64>,
+    closed: bool,
+}
+
+impl LocalNet {
+    /// Start a fresh world and queue `hello` (`Hello` or `Spectate`) as if
+    /// it had just gone out over a socket.
+    pub fn start(hello: ClientMsg) -> Self {
+        let config = WorldConfig {
+            seed: fresh_seed(),
+            last_kill_slow_motion: true,
+            ..WorldConfig::default()
+        };
+        LocalNet {
+            world: World::new(config),
+            inputs: PlayerInputs::new(),
+            inbox: RefCell::new(vec![hello]),
+            outbox: VecDeque::new(),
+            name: String::new(),
+            unsimulated: 0.0,
+            last_pump: None,
+            closed: false,
+        }
+    }
+
+    /// Apply what the game sent, then tick through the time that has
+    /// passed. Only the latest snapshot goes out; events from every step
+    /// do.
+    fn pump(&mut self, now: f64) {
+        let sent = std::mem::take(&mut *self.inbox.borrow_mut());
+        for msg in sent {
+            self.handle(msg);
+        }
+        if self.closed {
+            return;
+        }
+        let elapsed = self.last_pump.map_or(0.0, |last| now - last);
+        self.last_pump = Some(now);
+        self.unsimulated = (self.unsimulated + elapsed).min(MAX_CATCH_UP);
+        let mut stepped = false;
+        while self.unsimulated >= f64::from(sim::TICK_DT) {
+            self.unsimulated -= f64::from(sim::TICK_DT);
+            self.step();
+            stepped = true;
+        }
+        if stepped {
+            let snapshot = protocol::snapshot_from_world(&self.world);
+            self.outbox.push_back(ServerMsg::Snapshot(snapshot));
+        }
+    }
+
+    fn step(&mut self) {
+        let events = self.world.tick(&self.inputs, sim::TICK_DT);
+        // A fresh life starts with the controls released, as on a server.
+        for event in &events {
+            if let GameEvent::PlayerJoined(pid) = event {
+                self.inputs.remove(pid);
+            }
+        }
+        self.push_events(events);
+    }
+
+    fn push_events(&mut self, events: Vec<GameEvent>) {
+        if !events.is_empty() {
+            let tick = self.world.tick_index();
+            self.outbox.push_back(ServerMsg::Events { tick, events });
+        }
+    }
+
+    fn notice(&mut self, text: &str) {
+        let text = text.to_string();
+        self.outbox.push_back(ServerMsg::Chat { from: None, text });
+    }
+
+    fn handle(&mut self, msg: ClientMsg) {
+        match msg {
+            ClientMsg::Hello { name, ship_class }
+            | ClientMsg::Resume {
+                name, ship_class, ..
+            } => {
+                self.world.set_ship_class(LOCAL_PILOT, ship_class);
+                self.world.add_player(LOCAL_PILOT);
+                self.world.set_player_name(LOCAL_PILOT, name.clone());
+                self.name = name;
+                self.welcome();
+                self.push_events(vec![GameEvent::PlayerJoined(LOCAL_PILOT)]);
+            }
+            ClientMsg::Spectate { name } => {
+                for n in 1..=SPECTATE_BOTS {
+                    let pid = PlayerId(LOCAL_PILOT.0 + n);
+                    self.world.add_bot(pid, format!("WINGMAN {n}"));
+                }
+                self.name = name;
+                self.welcome();
+            }
+            ClientMsg::Input { input, .. } => {
+                self.inputs.insert(LOCAL_PILOT, input);
+            }
+            ClientMsg::Respawn => {
+                if self.world.respawn_player(LOCAL_PILOT).is_some() {
+                    self.inputs.remove(&LOCAL_PILOT);
+                    self.push_events(vec![GameEvent::PlayerJoined(LOCAL_PILOT)]);
+                }
+            }
+            ClientMsg::SelectShip { ship_class } => {
+                self.world.set_ship_class(LOCAL_PILOT, ship_class);
+            }
+            ClientMsg::RequestLeaderboard => {
+                self.outbox.push_back(ServerMsg::Leaderboard(Vec::new()));
+            }
+            ClientMsg::Chat { text } => {
+                let text: String = text.trim().chars().take(protocol::MAX_CHAT_CHARS).collect();
+                if !text.is_empty() {
+                    let from = Some(self.name.clone());
+                    self.outbox.push_back(ServerMsg::Chat { from, text });
+                }
+            }
+            ClientMsg::VotePause { pause } => {
+                if pause != self.world.paused() {
+                    self.world.set_paused(pause);
+                    self.notice(if pause { "Game paused" } else { "Game resumed" });
+                }
+            }
+            ClientMsg::Admin { .. } => {
+                self.outbox.push_back(ServerMsg::AdminReply {
+                    ok: false,
+                    message: "no operator commands offline".into(),
+                });
+            }
+            ClientMsg::Bye => self.closed = true,
+        }
+    }
+
+    fn welcome(&mut self) {
+        let config = self.world.config();
+        self.outbox.push_back(ServerMsg::Welcome {
+            player_id: LOCAL_PILOT,
+            seed: config.seed,
+            world_size: config.world_size.into(),
+            snapshot: protocol::snapshot_from_world(&self.world),
+            resume_token: 0,
+        });
+    }
+}
+
+impl Net for LocalNet {
+    fn try_recv(&mut self) -> Option<ServerMsg> {
+        if self.outbox.is_empty() {
+            self.pump(now_secs());
+        }
+        self.outbox.pop_front()
+    }
+
+    fn send(&self, msg: &ClientMsg) {
+        if !self.closed {
+            self.inbox.borrow_mut().push(msg.clone());
+        }
+    }
+
+    fn status(&self) -> NetStatus {
+        if self.closed {
+            NetStatus::Closed
+        } else {
+            NetStatus::Connected
+        }
+    }
+}
+
+/// Wall-clock seconds from an arbitrary start. `std::time::Instant`
+/// panics on wasm32, so the browser build asks JS instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn now_secs() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_secs() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
+
+/// Seed for a new offline world, different every launch. `RandomState` is
+/// seeded from the OS natively; the browser has `Math.random`.
+#[cfg(not(target_arch = "wasm32"))]
+fn fresh_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn fresh_seed() -> u64 {
+    (js_sys::Math::random() * u64::MAX as f64) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim::{PlayerInput, ShipClass};
+
+    fn hello() -> ClientMsg {
+        ClientMsg::Hello {
+            name: "alice".into(),
+            ship_class: ShipClass::Gunship,
+        }
+    }
+
+    /// Everything `pump(now)` produced.
+    fn drain(net: &mut LocalNet, now: f64) -> Vec<ServerMsg> {
+        net.pump(now);
+        net.outbox.drain(..).collect()
+    }
+
+    #[test]
+    fn hello_is_welcomed_and_the_world_ticks_on_wall_clock_time() {
+        let mut net = LocalNet::start(hello());
+        let msgs = drain(&mut net, 10.0);
+        assert!(matches!(
+            msgs[0],
+            ServerMsg::Welcome {
+                player_id: LOCAL_PILOT,
+                ..
+            }
+        ));
+        let start = net.world.tick_index().0;
+
+        // A little over a tenth of a second is six ticks, reported in one
+        // snapshot.
+        let msgs = drain(&mut net, 10.11);
+        assert_eq!(net.world.tick_index().0 - start, 6);
+        let snapshots = msgs
+            .iter()
+            .filter(|m| matches!(m, ServerMsg::Snapshot(_)))
+            .count();
+        assert_eq!(snapshots, 1);
+
+        // A long stall doesn't get replayed.
+        drain(&mut net, 100.0);
+        let most = (MAX_CATCH_UP / f64::from(sim::TICK_DT)).ceil() as u64;
+        assert!(net.world.tick_index().0 - start <= 6 + most);
+    }
+
+    #[test]
+    fn inputs_steer_the_local_pilot_and_pause_freezes_it() {
+        let mut net = LocalNet::start(hello());
+        drain(&mut net, 0.0);
+        let input = PlayerInput {
+            yaxis: 1.0,
+            ..PlayerInput::default()
+        };
+        net.send(&ClientMsg::Input {
+            tick: sim::Tick(1),
+            input,
+        });
+        drain(&mut net, 0.05);
+        assert_eq!(net.inputs.get(&LOCAL_PILOT), Some(&input));
+
+        net.send(&ClientMsg::VotePause { pause: true });
+        let msgs = drain(&mut net, 0.1);
+        let tick = net.world.tick_index();
+        assert!(msgs
+            .iter()
+            .any(|m| matches!(m, ServerMsg::Chat { from: None, text } if text == "Game paused")));
+        drain(&mut net, 0.2);
+        assert_eq!(net.world.tick_index(), tick);
+
+        net.send(&ClientMsg::Bye);
+        assert!(drain(&mut net, 0.3).is_empty());
+        assert_eq!(net.status(), NetStatus::Closed);
+    }
+}
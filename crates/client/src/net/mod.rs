@@ -2,10 +2,18 @@
 //!
 //! The trait is the only place the client code touches the network.
 //! Everything else uses `&mut dyn Net`. Native uses a tokio-tungstenite
-//! thread; wasm uses `web_sys::WebSocket` driven by the JS event loop.
+//! thread; wasm uses `web_sys::WebSocket` driven by the JS event loop. Both
+//! reconnect on their own when the socket drops, resuming the session with
+//! the token from `Welcome` (see `session`). `local` skips the network
+//! entirely and runs the world in-process, for offline play on either.
 
 use protocol::{ClientMsg, ServerMsg};
 
+pub mod local;
+pub mod session;
+pub use local::LocalNet;
+pub use session::NetStatus;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native;
 #[cfg(not(target_arch = "wasm32"))]
@@ -23,12 +31,12 @@ pub use web::WebNet;
 pub trait Net: Send {
     fn try_recv(&mut self) -> Option<ServerMsg>;
     fn send(&self, msg: &ClientMsg);
-    fn is_connected(&self) -> bool;
+    fn status(&self) -> NetStatus;
 }
 
 #[cfg(target_arch = "wasm32")]
 pub trait Net {
     fn try_recv(&mut self) -> Option<ServerMsg>;
     fn send(&self, msg: &ClientMsg);
-    fn is_connected(&self) -> bool;
+    fn status(&self) -> NetStatus;
 }
@@ -6,9 +6,12 @@
 //!
 //! The std mpsc on the inbound side keeps `try_recv` cheap and avoids
 //! awaiting anything from inside the ggez `update` step.
+//!
+//! When the socket drops the thread reconnects with backoff and opens the
+//! new connection with `Session::opening_msg`, so the game just sees the
+//! status flip to `Reconnecting` and back, followed by a fresh `Welcome`.
 
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 use anyhow::Result;
@@ -18,30 +21,25 @@ use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
 use tracing::{info, warn};
 
+use super::session::{NetStatus, Session};
 use super::Net;
 
 pub struct NativeNet {
     to_net: mpsc::UnboundedSender<ClientMsg>,
     from_net: std::sync::mpsc::Receiver<ServerMsg>,
-    connected: Arc<AtomicBool>,
+    status: Arc<Mutex<NetStatus>>,
     _thread: thread::JoinHandle<()>,
 }
 
 impl NativeNet {
-    /// Connect and send the initial `Hello`. Returns once the runtime
-    /// thread is spawned — the WS connection itself is established
+    /// Connect and send `hello` (`Hello` or `Spectate`). Returns once the
+    /// runtime thread is spawned — the WS connection itself is established
     /// asynchronously and the first message you receive will be `Welcome`.
-    pub fn connect(url: String, name: String) -> Result<Self> {
+    pub fn connect(url: String, hello: ClientMsg) -> Result<Self> {
         let (to_net_tx, to_net_rx) = mpsc::unbounded_channel::<ClientMsg>();
         let (from_net_tx, from_net_rx) = std::sync::mpsc::channel::<ServerMsg>();
-        let connected = Arc::new(AtomicBool::new(true));
-        let conn_for_thread = connected.clone();
-
-        // Send Hello first so the server can place us in the world the moment
-        // it sees the socket.
-        to_net_tx
-            .send(ClientMsg::Hello { name })
-            .map_err(|_| anyhow::anyhow!("couldn't queue Hello"))?;
+        let status = Arc::new(Mutex::new(NetStatus::Connected));
+        let status_for_thread = status.clone();
 
         let handle = thread::Builder::new()
             .name("icarust-net".into())
@@ -53,22 +51,25 @@ impl NativeNet {
                     Ok(rt) => rt,
                     Err(e) => {
                         warn!("failed to start tokio runtime: {e}");
-                        conn_for_thread.store(false, Ordering::Relaxed);
+                        set_status(&status_for_thread, NetStatus::Closed);
                         return;
                     }
                 };
-                let result = rt.block_on(run_connection(url, to_net_rx, from_net_tx));
-                if let Err(e) = result {
-                    warn!("net connection ended: {e:#}");
-                }
-                conn_for_thread.store(false, Ordering::Relaxed);
+                rt.block_on(run(
+                    url,
+                    Session::new(hello),
+                    to_net_rx,
+                    from_net_tx,
+                    &status_for_thread,
+                ));
+                set_status(&status_for_thread, NetStatus::Closed);
             })
             .expect("failed to spawn net thread");
 
         Ok(NativeNet {
             to_net: to_net_tx,
             from_net: from_net_rx,
-            connected,
+            status,
             _thread: handle,
         })
     }
@@ -88,55 +89,105 @@ impl Net for NativeNet {
         let _ = self.to_net.send(msg.clone());
     }
 
-    fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::Relaxed)
+    fn status(&self) -> NetStatus {
+        *self.status.lock().unwrap()
     }
 }
 
-async fn run_connection(
+fn set_status(status: &Mutex<NetStatus>, to: NetStatus) {
+    *status.lock().unwrap() = to;
+}
+
+/// How one connection ended.
+enum Ended {
+    /// The game said `Bye` or dropped its `NativeNet`. Don't come back.
+    Quit,
+    /// The socket went away underneath us.
+    Dropped,
+}
+
+/// Connect, and keep reconnecting with backoff until the game quits or the
+/// session runs out of attempts.
+async fn run(
     url: String,
+    mut session: Session,
     mut to_net_rx: mpsc::UnboundedReceiver<ClientMsg>,
     from_net_tx: std::sync::mpsc::Sender<ServerMsg>,
-) -> Result<()> {
+    status: &Mutex<NetStatus>,
+) {
+    loop {
+        match run_connection(&url, &mut session, &mut to_net_rx, &from_net_tx, status).await {
+            Ok(Ended::Quit) => return,
+            Ok(Ended::Dropped) => warn!("net connection dropped"),
+            Err(e) => warn!("net connection ended: {e:#}"),
+        }
+        let Some(delay) = session.next_backoff() else {
+            warn!("giving up on reconnecting");
+            return;
+        };
+        set_status(status, NetStatus::Reconnecting);
+        info!(?delay, "reconnecting");
+        tokio::time::sleep(delay).await;
+        // Inputs queued while the link was down are stale by now.
+        while to_net_rx.try_recv().is_ok() {}
+    }
+}
+
+async fn run_connection(
+    url: &str,
+    session: &mut Session,
+    to_net_rx: &mut mpsc::UnboundedReceiver<ClientMsg>,
+    from_net_tx: &std::sync::mpsc::Sender<ServerMsg>,
+    status: &Mutex<NetStatus>,
+) -> Result<Ended> {
     info!(%url, "connecting");
-    let (ws, _resp) = tokio_tungstenite::connect_async(&url).await?;
+    let (ws, _resp) = tokio_tungstenite::connect_async(url).await?;
     info!(%url, "connected");
+    set_status(status, NetStatus::Connected);
     let (mut ws_tx, mut ws_rx) = ws.split();
 
-    // Writer task — drains outbound messages.
-    let writer = tokio::spawn(async move {
-        while let Some(msg) = to_net_rx.recv().await {
-            let bytes = protocol::encode(&msg);
-            if let Err(e) = ws_tx.send(Message::Binary(bytes)).await {
-                warn!("ws write failed: {e}");
-                break;
+    // Open with Hello (or Spectate, or Resume after a drop) so the server
+    // can place us in the world the moment it sees the socket.
+    let opening = protocol::encode(&session.opening_msg());
+    ws_tx.send(Message::Binary(opening)).await?;
+
+    loop {
+        tokio::select! {
+            outbound = to_net_rx.recv() => {
+                let Some(msg) = outbound else {
+                    // `NativeNet` dropped; we're done.
+                    let _ = ws_tx.close().await;
+                    return Ok(Ended::Quit);
+                };
+                ws_tx.send(Message::Binary(protocol::encode(&msg))).await?;
+                if matches!(msg, ClientMsg::Bye) {
+                    let _ = ws_tx.close().await;
+                    return Ok(Ended::Quit);
+                }
             }
-        }
-        let _ = ws_tx.close().await;
-    });
-
-    // Reader loop runs on this task.
-    while let Some(frame) = ws_rx.next().await {
-        let frame = frame?;
-        match frame {
-            Message::Binary(b) => {
-                let sm: ServerMsg = match protocol::decode(&b) {
-                    Ok(sm) => sm,
-                    Err(e) => {
-                        warn!("malformed server message: {e}");
-                        continue;
-                    }
+            inbound = ws_rx.next() => {
+                let Some(frame) = inbound else {
+                    return Ok(Ended::Dropped);
                 };
-                if from_net_tx.send(sm).is_err() {
-                    // main thread dropped the receiver; we're done.
-                    break;
+                match frame? {
+                    Message::Binary(b) => {
+                        let sm: ServerMsg = match protocol::decode(&b) {
+                            Ok(sm) => sm,
+                            Err(e) => {
+                                warn!("malformed server message: {e}");
+                                continue;
+                            }
+                        };
+                        session.observe(&sm);
+                        if from_net_tx.send(sm).is_err() {
+                            // main thread dropped the receiver; we're done.
+                            return Ok(Ended::Quit);
+                        }
+                    }
+                    Message::Close(_) => return Ok(Ended::Dropped),
+                    _ => {}
                 }
             }
-            Message::Close(_) => break,
-            _ => {}
         }
     }
-
-    writer.abort();
-    Ok(())
 }
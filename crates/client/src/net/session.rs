@@ -0,0 +1,149 @@
+//! Reconnect bookkeeping shared by the native and web transports: which
+//! message opens each connection, the resume token the server handed out,
+//! and how long to wait between attempts.
+
+use std::time::Duration;
+
+use protocol::{ClientMsg, ServerMsg};
+
+/// Wait before the first reconnect attempt; doubles on each failure.
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(8);
+/// Attempts before giving up — about 40 s of trying with the delays above,
+/// a little past the server's resume grace period.
+const MAX_ATTEMPTS: u32 = 8;
+
+/// Connection state as the game sees it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetStatus {
+    /// Connected, or still making the very first connection.
+    Connected,
+    /// The socket dropped and we're trying to get it back.
+    Reconnecting,
+    /// Gave up, or the game said `Bye`.
+    Closed,
+}
+
+#[derive(Debug)]
+pub struct Session {
+    /// What the game asked to open with: `Hello` or `Spectate`.
+    hello: ClientMsg,
+    /// From the last `Welcome`.
+    token: Option<u64>,
+    /// Failed attempts since the last `Welcome`.
+    attempts: u32,
+    /// The server kicked us or is shutting down; there's nothing to come
+    /// back to.
+    ended: bool,
+}
+
+impl Session {
+    pub fn new(hello: ClientMsg) -> Self {
+        Session {
+            hello,
+            token: None,
+            attempts: 0,
+            ended: false,
+        }
+    }
+
+    /// First message on the next connection. Pilots resume once the
+    /// server has given them a token; spectators just watch again.
+    pub fn opening_msg(&self) -> ClientMsg {
+        match (&self.hello, self.token) {
+            (ClientMsg::Hello { name, ship_class }, Some(token)) => ClientMsg::Resume {
+                token,
+                name: name.clone(),
+                ship_class: *ship_class,
+            },
+            (hello, _) => hello.clone(),
+        }
+    }
+
+    /// Watch inbound traffic for the resume token. A `Welcome` also means
+    /// the connection made it, so the backoff starts over.
+    pub fn observe(&mut self, msg: &ServerMsg) {
+        match msg {
+            ServerMsg::Welcome { resume_token, .. } => {
+                self.token = Some(*resume_token);
+                self.attempts = 0;
+            }
+            ServerMsg::Kicked { .. } | ServerMsg::Shutdown { .. } => self.ended = true,
+            _ => {}
+        }
+    }
+
+    /// Delay before the next reconnect attempt, or `None` once we've run
+    /// out of attempts or the server has ended the session.
+    pub fn next_backoff(&mut self) -> Option<Duration> {
+        if self.ended || self.attempts >= MAX_ATTEMPTS {
+            return None;
+        }
+        let delay = BACKOFF_BASE
+            .saturating_mul(1 << self.attempts.min(16))
+            .min(BACKOFF_MAX);
+        self.attempts += 1;
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim::ShipClass;
+
+    fn hello() -> ClientMsg {
+        ClientMsg::Hello {
+            name: "alice".into(),
+            ship_class: ShipClass::Gunship,
+        }
+    }
+
+    #[test]
+    fn pilots_resume_once_they_have_a_token() {
+        let mut session = Session::new(hello());
+        assert!(matches!(session.opening_msg(), ClientMsg::Hello { .. }));
+        session.observe(&ServerMsg::Welcome {
+            player_id: sim::PlayerId(3),
+            seed: 0,
+            world_size: sim::Vec2::new(1.0, 1.0).into(),
+            snapshot: protocol::snapshot_from_world(&sim::World::new(Default::default())),
+            resume_token: 42,
+        });
+        match session.opening_msg() {
+            ClientMsg::Resume {
+                token, ship_class, ..
+            } => assert_eq!((token, ship_class), (42, ShipClass::Gunship)),
+            other => panic!("expected Resume, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_then_gives_up() {
+        let mut session = Session::new(hello());
+        let delays: Vec<Duration> = std::iter::from_fn(|| session.next_backoff()).collect();
+        assert_eq!(delays.len(), MAX_ATTEMPTS as usize);
+        assert_eq!(delays[0], BACKOFF_BASE);
+        assert_eq!(delays[1], BACKOFF_BASE * 2);
+        assert_eq!(*delays.last().unwrap(), BACKOFF_MAX);
+    }
+
+    #[test]
+    fn kicked_sessions_stay_down() {
+        let mut session = Session::new(hello());
+        session.observe(&ServerMsg::Kicked {
+            reason: "sending too many messages".into(),
+        });
+        assert_eq!(session.next_backoff(), None);
+    }
+
+    #[test]
+    fn sessions_end_with_the_server() {
+        let mut session = Session::new(hello());
+        session.observe(&ServerMsg::Shutdown {
+            reason: "Server restarting.".into(),
+            seconds: 10,
+        });
+        assert_eq!(session.next_backoff(), None);
+    }
+}
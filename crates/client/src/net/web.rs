@@ -9,11 +9,14 @@
 //! tab can grow the queue by ~80 msg/sec forever. We drop the oldest message on overflow,
 //! snapshots are full-state, so losing old ones costs nothing.
 //!
+//! When the socket closes we open a new one after `Session::next_backoff`, via
+//! `setTimeout`, and open it with `Session::opening_msg` so a pilot resumes.
+//!
 //! JS runs things on the main thread and `wasm32-unknown-unknown` has no threads.
 
 use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use anyhow::{anyhow, Result};
 use js_sys::Uint8Array;
@@ -22,6 +25,7 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{BinaryType, ErrorEvent, MessageEvent, WebSocket};
 
+use super::session::{NetStatus, Session};
 use super::Net;
 
 /// ~1s of server traffic; old messages drop on overflow.
@@ -36,126 +40,201 @@ fn document_hidden() -> bool {
         .unwrap_or(false)
 }
 
+/// JS callbacks installed on the current socket. Kept alive for as long as
+/// that socket is; dropping them would deregister the callbacks immediately.
+struct Handlers {
+    _on_open: Closure<dyn FnMut(JsValue)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(ErrorEvent)>,
+    _on_close: Closure<dyn FnMut(JsValue)>,
+}
+
 /// Inner state, shared between the `WebNet` handle and the JS callbacks
 /// installed on the socket. Kept in a `Rc<RefCell<_>>` because callbacks
 /// outlive any single function and JS hands control back to us on its own
-/// schedule.
+/// schedule. Callbacks hold a `Weak` so dropping `WebNet` frees it all.
 struct Inner {
+    url: String,
     ws: WebSocket,
     rx: VecDeque<ServerMsg>,
     /// Anything we tried to send before `onopen` fires gets buffered here
     /// and flushed when the socket is ready.
     pending_tx: Vec<Vec<u8>>,
-    connected: bool,
+    status: NetStatus,
+    session: Session,
     /// Set once `onopen` has run so subsequent sends bypass the queue.
     open: bool,
+    handlers: Option<Handlers>,
 }
 
 pub struct WebNet {
     inner: Rc<RefCell<Inner>>,
-    /// Keep the closures alive for the lifetime of the connection. Dropping
-    /// them would deregister the callbacks immediately.
-    _on_open: Closure<dyn FnMut(JsValue)>,
-    _on_message: Closure<dyn FnMut(MessageEvent)>,
-    _on_error: Closure<dyn FnMut(ErrorEvent)>,
-    _on_close: Closure<dyn FnMut(JsValue)>,
 }
 
 impl WebNet {
-    pub fn connect(url: &str, name: String) -> Result<Self> {
-        let ws = WebSocket::new(url).map_err(js_err("WebSocket::new"))?;
-        ws.set_binary_type(BinaryType::Arraybuffer);
-
+    pub fn connect(url: &str, hello: ClientMsg) -> Result<Self> {
+        let ws = new_socket(url)?;
         let inner = Rc::new(RefCell::new(Inner {
-            ws: ws.clone(),
+            url: url.to_string(),
+            ws,
             rx: VecDeque::new(),
             pending_tx: Vec::new(),
-            connected: true,
+            status: NetStatus::Connected,
+            session: Session::new(hello),
             open: false,
+            handlers: None,
         }));
+        install(&inner);
+        Ok(WebNet { inner })
+    }
+}
 
-        // onopen: flush any buffered Hello/Input frames.
-        let inner_open = inner.clone();
-        let on_open = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
-            let mut s = inner_open.borrow_mut();
-            s.open = true;
-            // Drain `pending_tx`. If a send fails we drop the rest — the
-            // socket is probably already dead.
-            let pending = std::mem::take(&mut s.pending_tx);
-            for buf in pending {
-                if let Err(e) = s.ws.send_with_u8_array(&buf) {
-                    web_sys::console::warn_1(
-                        &format!("ws send_with_u8_array failed: {e:?}").into(),
-                    );
-                    s.connected = false;
-                    break;
-                }
-            }
-        });
-        ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
-
-        // onmessage: decode the binary payload into a ServerMsg and push it
-        // into the queue. Text frames and other types are ignored. While the
-        // tab is hidden we still drain the network frame (so the browser
-        // doesn't buffer it indefinitely on its side), but skip the decode
-        // + allocations entirely — `update` won't process queued messages
-        // anyway, and on un-hide we just resync to the next snapshot.
-        let inner_msg = inner.clone();
-        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |evt: MessageEvent| {
-            if document_hidden() {
-                return;
+impl Drop for WebNet {
+    fn drop(&mut self) {
+        let mut s = self.inner.borrow_mut();
+        s.status = NetStatus::Closed;
+        // Detach first: the handlers are freed with `Inner`, and JS would
+        // otherwise still call them when the close completes.
+        s.ws.set_onopen(None);
+        s.ws.set_onmessage(None);
+        s.ws.set_onerror(None);
+        s.ws.set_onclose(None);
+        let _ = s.ws.close();
+    }
+}
+
+fn new_socket(url: &str) -> Result<WebSocket> {
+    let ws = WebSocket::new(url).map_err(js_err("WebSocket::new"))?;
+    ws.set_binary_type(BinaryType::Arraybuffer);
+    Ok(ws)
+}
+
+/// Install callbacks on `inner.ws` and queue the opening message. Hello (or
+/// Spectate, or Resume after a drop) goes first so the server can place us
+/// in the world the moment the WS handshake finishes.
+fn install(inner: &Rc<RefCell<Inner>>) {
+    // onopen: flush any buffered Hello/Input frames.
+    let weak = Rc::downgrade(inner);
+    let on_open = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+        let Some(inner) = weak.upgrade() else { return };
+        let mut s = inner.borrow_mut();
+        s.open = true;
+        s.status = NetStatus::Connected;
+        // Drain `pending_tx`. If a send fails we drop the rest — the
+        // socket is probably already dead, and onclose will reconnect.
+        let pending = std::mem::take(&mut s.pending_tx);
+        for buf in pending {
+            if let Err(e) = s.ws.send_with_u8_array(&buf) {
+                web_sys::console::warn_1(&format!("ws send_with_u8_array failed: {e:?}").into());
+                break;
             }
-            let data = evt.data();
-            // We requested ArrayBuffer above; anything else means the server
-            // is doing something unexpected.
-            let Ok(buffer) = data.dyn_into::<js_sys::ArrayBuffer>() else {
-                web_sys::console::warn_1(&"ws got non-ArrayBuffer message".into());
-                return;
-            };
-            let arr = Uint8Array::new(&buffer);
-            let bytes = arr.to_vec();
-            match protocol::decode::<ServerMsg>(&bytes) {
-                Ok(msg) => {
-                    let mut s = inner_msg.borrow_mut();
-                    while s.rx.len() >= MAX_RX_QUEUE {
-                        s.rx.pop_front();
-                    }
-                    s.rx.push_back(msg);
-                }
-                Err(e) => {
-                    web_sys::console::warn_1(&format!("malformed server message: {e}").into())
+        }
+    });
+
+    // onmessage: decode the binary payload into a ServerMsg and push it
+    // into the queue. Text frames and other types are ignored. While the
+    // tab is hidden we still drain the network frame (so the browser
+    // doesn't buffer it indefinitely on its side), but skip the decode
+    // + allocations entirely — `update` won't process queued messages
+    // anyway, and on un-hide we just resync to the next snapshot.
+    let weak = Rc::downgrade(inner);
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |evt: MessageEvent| {
+        if document_hidden() {
+            return;
+        }
+        let Some(inner) = weak.upgrade() else { return };
+        let data = evt.data();
+        // We requested ArrayBuffer above; anything else means the server
+        // is doing something unexpected.
+        let Ok(buffer) = data.dyn_into::<js_sys::ArrayBuffer>() else {
+            web_sys::console::warn_1(&"ws got non-ArrayBuffer message".into());
+            return;
+        };
+        let arr = Uint8Array::new(&buffer);
+        let bytes = arr.to_vec();
+        match protocol::decode::<ServerMsg>(&bytes) {
+            Ok(msg) => {
+                let mut s = inner.borrow_mut();
+                s.session.observe(&msg);
+                while s.rx.len() >= MAX_RX_QUEUE {
+                    s.rx.pop_front();
                 }
+                s.rx.push_back(msg);
             }
-        });
-        ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
-
-        let inner_err = inner.clone();
-        let on_error = Closure::<dyn FnMut(ErrorEvent)>::new(move |evt: ErrorEvent| {
-            web_sys::console::warn_1(&format!("ws error: {}", evt.message()).into());
-            inner_err.borrow_mut().connected = false;
-        });
-        ws.set_onerror(Some(on_error.as_ref().unchecked_ref()));
-
-        let inner_close = inner.clone();
-        let on_close = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
-            inner_close.borrow_mut().connected = false;
-        });
-        ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
-
-        let net = WebNet {
-            inner: inner.clone(),
-            _on_open: on_open,
-            _on_message: on_message,
-            _on_error: on_error,
-            _on_close: on_close,
-        };
+            Err(e) => web_sys::console::warn_1(&format!("malformed server message: {e}").into()),
+        }
+    });
+
+    // onerror is always followed by onclose, which does the reconnecting.
+    let on_error = Closure::<dyn FnMut(ErrorEvent)>::new(move |evt: ErrorEvent| {
+        web_sys::console::warn_1(&format!("ws error: {}", evt.message()).into());
+    });
+
+    let weak = Rc::downgrade(inner);
+    let on_close = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+        if let Some(inner) = weak.upgrade() {
+            schedule_reconnect(&inner);
+        }
+    });
 
-        // Send Hello first so the server can place us in the world the moment
-        // the WS handshake finishes. The socket isn't open yet — `send` will
-        // buffer until onopen flushes it.
-        net.send(&ClientMsg::Hello { name });
+    let mut s = inner.borrow_mut();
+    s.ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    s.ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    s.ws.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    s.ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+    s.handlers = Some(Handlers {
+        _on_open: on_open,
+        _on_message: on_message,
+        _on_error: on_error,
+        _on_close: on_close,
+    });
+    s.open = false;
+    let opening = protocol::encode(&s.session.opening_msg());
+    s.pending_tx = vec![opening];
+}
+
+/// The socket closed: wait out the backoff, then open a new one. Gives up
+/// (status `Closed`) once the session is out of attempts or after a `Bye`.
+fn schedule_reconnect(inner: &Rc<RefCell<Inner>>) {
+    let delay = {
+        let mut s = inner.borrow_mut();
+        s.open = false;
+        if s.status == NetStatus::Closed {
+            return;
+        }
+        match s.session.next_backoff() {
+            Some(delay) => delay,
+            None => {
+                s.status = NetStatus::Closed;
+                return;
+            }
+        }
+    };
+    inner.borrow_mut().status = NetStatus::Reconnecting;
 
-        Ok(net)
+    let weak: Weak<RefCell<Inner>> = Rc::downgrade(inner);
+    let reopen = Closure::once_into_js(move || {
+        let Some(inner) = weak.upgrade() else { return };
+        let url = inner.borrow().url.clone();
+        match new_socket(&url) {
+            Ok(ws) => {
+                inner.borrow_mut().ws = ws;
+                install(&inner);
+            }
+            Err(e) => {
+                web_sys::console::warn_1(&format!("{e:#}").into());
+                schedule_reconnect(&inner);
+            }
+        }
+    });
+    let scheduled = web_sys::window().map(|w| {
+        w.set_timeout_with_callback_and_timeout_and_arguments_0(
+            reopen.unchecked_ref(),
+            delay.as_millis() as i32,
+        )
+    });
+    if !matches!(scheduled, Some(Ok(_))) {
+        inner.borrow_mut().status = NetStatus::Closed;
     }
 }
 
@@ -166,22 +245,27 @@ impl Net for WebNet {
 
     fn send(&self, msg: &ClientMsg) {
         let mut s = self.inner.borrow_mut();
-        if !s.connected {
+        // While reconnecting, inputs would only be stale by the time the
+        // new socket opens; the opening message is already queued.
+        if s.status != NetStatus::Connected {
             return;
         }
         let bytes = protocol::encode(msg);
         if s.open {
             if let Err(e) = s.ws.send_with_u8_array(&bytes) {
                 web_sys::console::warn_1(&format!("ws send failed: {e:?}").into());
-                s.connected = false;
             }
         } else {
             s.pending_tx.push(bytes);
         }
+        if matches!(msg, ClientMsg::Bye) {
+            s.status = NetStatus::Closed;
+            let _ = s.ws.close();
+        }
     }
 
-    fn is_connected(&self) -> bool {
-        self.inner.borrow().connected
+    fn status(&self) -> NetStatus {
+        self.inner.borrow().status
     }
 }
 
@@ -0,0 +1,157 @@
+//! Co-op objective HUD: a briefing line under the top edge while a round
+//! is running ("DEFEND THE CONVOY  45%"), and a results screen over the
+//! world once the server decides it — outcome, team or pilot scores, and a
+//! note that the next round is coming.
+//!
+//! `MainState` calls `sync` with every snapshot; like the menu, everything
+//! is positioned in screen pixels at draw time.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect};
+use ggez::{Context, GameResult};
+use protocol::Snapshot;
+use sim::{ObjectiveKind, ObjectiveOutcome, ObjectiveStatus, PlayerId};
+
+use crate::assets::AssetManager;
+use crate::render::camera::Point2;
+use crate::widget::TextWidget;
+
+const BRIEFING_COLOR: Color = Color::new(0.28, 0.08, 0.12, 1.0);
+const SUCCESS_COLOR: Color = Color::new(0.20, 0.42, 0.18, 1.0);
+const FAILURE_COLOR: Color = Color::new(0.72, 0.20, 0.16, 1.0);
+const DETAIL_COLOR: Color = Color::new(0.22, 0.12, 0.10, 1.0);
+/// Cream card behind the results so they read over a busy sky.
+const CARD_COLOR: Color = Color::new(0.98, 0.95, 0.86, 0.88);
+const CARD_PADDING: f32 = 24.0;
+/// Pilot rows shown on the results card when there are no teams.
+const RESULT_ROWS: usize = 6;
+
+pub struct ObjectiveHud {
+    briefing: TextWidget,
+    outcome: TextWidget,
+    details: TextWidget,
+    card: Mesh,
+    /// Decided outcome currently on show, if any.
+    showing: Option<ObjectiveOutcome>,
+}
+
+impl ObjectiveHud {
+    pub fn new(ctx: &mut Context, am: &mut AssetManager) -> GameResult<Self> {
+        let card = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, 1.0, 1.0),
+            Color::WHITE,
+        )?;
+        Ok(ObjectiveHud {
+            briefing: TextWidget::new(ctx, am, 20.0)?,
+            outcome: TextWidget::new(ctx, am, 44.0)?,
+            details: TextWidget::new(ctx, am, 20.0)?,
+            card,
+            showing: None,
+        })
+    }
+
+    /// Refresh from the latest snapshot. Clears everything when the
+    /// server isn't running an objective.
+    pub fn sync(&mut self, snap: &Snapshot, local: Option<PlayerId>) {
+        let Some(status) = snap.objective else {
+            self.briefing.set_text("", 20.0);
+            self.showing = None;
+            return;
+        };
+        self.briefing.set_text(&briefing_line(&status), 20.0);
+        if status.outcome == self.showing {
+            return;
+        }
+        self.showing = status.outcome;
+        let Some(outcome) = status.outcome else {
+            return;
+        };
+        let title = match outcome {
+            ObjectiveOutcome::Success => "MISSION COMPLETE",
+            ObjectiveOutcome::Failure => "MISSION FAILED",
+        };
+        self.outcome.set_text(title, 44.0);
+        self.details
+            .set_text(&results_text(&status, snap, local), 20.0);
+    }
+
+    pub fn draw(&mut self, ctx: &Context, canvas: &mut Canvas, screen: Vec2) {
+        let briefing_w = self.briefing.width(ctx);
+        self.briefing
+            .set_position(Point2::new((screen.x - briefing_w) / 2.0, 40.0));
+        self.briefing.draw_with(canvas, BRIEFING_COLOR);
+
+        let Some(outcome) = self.showing else {
+            return;
+        };
+        let (title_w, title_h) = (self.outcome.width(ctx), self.outcome.height(ctx));
+        let (detail_w, detail_h) = (self.details.width(ctx), self.details.height(ctx));
+        let card_w = title_w.max(detail_w) + CARD_PADDING * 2.0;
+        let card_h = title_h + 12.0 + detail_h + CARD_PADDING * 2.0;
+        let card_x = (screen.x - card_w) / 2.0;
+        let card_y = (screen.y - card_h) / 2.0;
+        canvas.draw(
+            &self.card,
+            DrawParam::new()
+                .dest([card_x, card_y])
+                .scale([card_w, card_h])
+                .color(CARD_COLOR),
+        );
+        self.outcome.set_position(Point2::new(
+            (screen.x - title_w) / 2.0,
+            card_y + CARD_PADDING,
+        ));
+        self.outcome.draw_with(
+            canvas,
+            match outcome {
+                ObjectiveOutcome::Success => SUCCESS_COLOR,
+                ObjectiveOutcome::Failure => FAILURE_COLOR,
+            },
+        );
+        self.details.set_position(Point2::new(
+            (screen.x - detail_w) / 2.0,
+            card_y + CARD_PADDING + title_h + 12.0,
+        ));
+        self.details.draw_with(canvas, DETAIL_COLOR);
+    }
+}
+
+fn briefing_line(status: &ObjectiveStatus) -> String {
+    let progress = match status.kind {
+        ObjectiveKind::DestroyTanks => {
+            let quota = sim::objective::TANK_HUNT_QUOTA;
+            let kills = (status.progress * quota as f32).round() as u32;
+            let secs = status.time_left.unwrap_or(0.0).ceil() as u32;
+            format!("{kills}/{quota}  {}:{:02}", secs / 60, secs % 60)
+        }
+        _ => format!("{:.0}%", status.progress * 100.0),
+    };
+    format!("{}  {progress}", status.kind.name())
+}
+
+/// Round summary: mission and round, then team totals when the world has
+/// teams, otherwise the top pilots with the local one marked.
+fn results_text(status: &ObjectiveStatus, snap: &Snapshot, local: Option<PlayerId>) -> String {
+    let mut text = format!("{} — ROUND {}\n", status.kind.name(), status.round);
+    if !snap.team_scores.is_empty() {
+        for (team, score) in &snap.team_scores {
+            text.push_str(&format!("\nTEAM {}   {score:>7}", team + 1));
+        }
+    } else {
+        let mut pilots: Vec<(PlayerId, i32)> = snap.score_by_player.clone();
+        pilots.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        for (pid, score) in pilots.into_iter().take(RESULT_ROWS) {
+            let name = snap
+                .player_names
+                .iter()
+                .find(|(p, _)| *p == pid)
+                .map_or_else(|| format!("PILOT {}", pid.0), |(_, n)| n.to_uppercase());
+            let marker = if Some(pid) == local { ">" } else { " " };
+            text.push_str(&format!("\n{marker} {name:<12} {score:>7}"));
+        }
+    }
+    text.push_str("\n\nNEXT ROUND SOON");
+    text
+}
@@ -0,0 +1,214 @@
+//! Radar: the whole toroidal world squeezed into a HUD strip, plus arrows
+//! on the screen edge pointing at hostiles outside the view.
+//!
+//! The strip is centred on the camera, so the pilot (or whatever a
+//! spectator is watching) always sits in the middle and everything else
+//! at its shortest wrapped offset: a tank just across the seam shows up
+//! beside you rather than at the far end. Height maps the world's full
+//! altitude range. `render::radar` draws it.
+
+use ggez::glam::Vec2;
+use protocol::{EntityState, Snapshot};
+use sim::entity::{EntityKind, ShotOwner};
+use sim::util::toroidal_offset;
+use sim::PlayerId;
+
+/// Most edge arrows shown at once; the nearest threats win.
+pub const MAX_ARROWS: usize = 3;
+
+/// What a radar blip stands for, which picks its color and size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blip {
+    /// The local pilot's own ship.
+    Me,
+    Pilot,
+    Enemy,
+    Tank,
+    /// Convoy or bomber the pilots are escorting.
+    Escort,
+    /// A hostile shell heading towards the middle of the view.
+    Shell,
+}
+
+/// Blip for `e`, which sits `offset` away from the radar's centre. Player
+/// shots and hostile shells flying away aren't shown.
+pub fn blip(e: &EntityState, offset: Vec2, me: Option<PlayerId>) -> Option<Blip> {
+    if !e.alive {
+        return None;
+    }
+    Some(match e.kind {
+        EntityKind::Player { player_id } if Some(player_id) == me => Blip::Me,
+        EntityKind::Player { .. } => Blip::Pilot,
+        EntityKind::Enemy => Blip::Enemy,
+        EntityKind::Tank => Blip::Tank,
+        EntityKind::Convoy | EntityKind::Bomber => Blip::Escort,
+        EntityKind::Shot {
+            owner: ShotOwner::Enemy | ShotOwner::Tank,
+        } if offset.dot(Vec2::from(e.vel)) < 0.0 => Blip::Shell,
+        EntityKind::Shot { .. } => return None,
+    })
+}
+
+/// Where a point lands on the strip, as fractions of its width and height
+/// from the top-left: `offset_x` is the wrapped offset from the centre,
+/// `y` the world altitude.
+pub fn strip_point(offset_x: f32, y: f32, world: Vec2) -> Vec2 {
+    Vec2::new(
+        (offset_x / world.x + 0.5).clamp(0.0, 1.0),
+        (1.0 - y / world.y).clamp(0.0, 1.0),
+    )
+}
+
+/// Every blip in `snap`, with its position on the strip (see
+/// `strip_point`). The local pilot comes last so it draws on top.
+pub fn blips(
+    snap: &Snapshot,
+    centre: Vec2,
+    world: Vec2,
+    me: Option<PlayerId>,
+) -> Vec<(Blip, Vec2)> {
+    let mut out: Vec<(Blip, Vec2)> = snap
+        .entities
+        .iter()
+        .filter_map(|e| {
+            let pos = Vec2::from(e.pos);
+            let offset = toroidal_offset(centre, pos, world.x);
+            let kind = blip(e, offset, me)?;
+            Some((kind, strip_point(offset.x, pos.y, world)))
+        })
+        .collect();
+    out.sort_by_key(|(kind, _)| *kind == Blip::Me);
+    out
+}
+
+/// Arrows for the nearest enemies and tanks outside a `visible` world-unit
+/// view centred on `centre`, as `(kind, offset)` with the wrapped offset
+/// in world units, nearest first.
+pub fn threats(snap: &Snapshot, centre: Vec2, world: Vec2, visible: Vec2) -> Vec<(Blip, Vec2)> {
+    let half = visible * 0.5;
+    let mut out: Vec<(Blip, Vec2)> = snap
+        .entities
+        .iter()
+        .filter(|e| e.alive)
+        .filter_map(|e| {
+            let kind = match e.kind {
+                EntityKind::Enemy => Blip::Enemy,
+                EntityKind::Tank => Blip::Tank,
+                _ => return None,
+            };
+            let offset = toroidal_offset(centre, Vec2::from(e.pos), world.x);
+            let off_screen = offset.x.abs() > half.x || offset.y.abs() > half.y;
+            off_screen.then_some((kind, offset))
+        })
+        .collect();
+    out.sort_by(|a, b| a.1.length_squared().total_cmp(&b.1.length_squared()));
+    out.truncate(MAX_ARROWS);
+    out
+}
+
+/// Screen position and angle (radians, clockwise from +X) of an arrow
+/// pointing at something `offset` pixels from the screen centre (Y down),
+/// kept `margin` pixels inside the edge. `None` if it's on screen.
+pub fn edge_arrow(offset: Vec2, screen: Vec2, margin: f32) -> Option<(Vec2, f32)> {
+    let half = screen * 0.5;
+    if offset.x.abs() <= half.x && offset.y.abs() <= half.y {
+        return None;
+    }
+    let inner = (half - Vec2::splat(margin)).max(Vec2::ZERO);
+    // Shrink the offset until it touches the inset rectangle.
+    let fit = (inner.x / offset.x.abs()).min(inner.y / offset.y.abs());
+    Some((half + offset * fit, offset.y.atan2(offset.x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim::{Entity, EntityId, ShipClass, Tick};
+
+    fn state(kind: EntityKind, pos: Vec2, vel: Vec2) -> EntityState {
+        let mut e = Entity::player(EntityId(1), PlayerId(1), pos, ShipClass::Fighter);
+        e.kind = kind;
+        e.vel = vel;
+        EntityState::from_entity(&e)
+    }
+
+    fn snapshot(entities: Vec<EntityState>) -> Snapshot {
+        Snapshot {
+            tick: Tick(0),
+            entities,
+            score_by_player: Vec::new(),
+            player_names: Vec::new(),
+            pilot_stats: Vec::new(),
+            bots: Vec::new(),
+            teams: Vec::new(),
+            team_scores: Vec::new(),
+            objective: None,
+            spectators: 0,
+            level: 1,
+            paused: false,
+            time_scale: 1.0,
+            terrain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn strip_wraps_around_the_centre() {
+        let world = Vec2::new(1000.0, 400.0);
+        let centre = Vec2::new(950.0, 200.0);
+        let me = EntityKind::Player {
+            player_id: PlayerId(7),
+        };
+        let snap = snapshot(vec![
+            state(me, centre, Vec2::ZERO),
+            // Just across the seam: lands right of the middle.
+            state(EntityKind::Tank, Vec2::new(50.0, 0.0), Vec2::ZERO),
+            state(EntityKind::Enemy, Vec2::new(800.0, 400.0), Vec2::ZERO),
+        ]);
+        let blips = blips(&snap, centre, world, Some(PlayerId(7)));
+        let kinds: Vec<Blip> = blips.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [Blip::Tank, Blip::Enemy, Blip::Me]);
+        for (got, want) in blips.iter().zip([(0.6, 1.0), (0.35, 0.0), (0.5, 0.5)]) {
+            assert!(got.1.distance(Vec2::from(want)) < 1e-5, "{got:?}");
+        }
+    }
+
+    #[test]
+    fn only_incoming_hostile_shells_show() {
+        let offset = Vec2::new(100.0, 0.0);
+        let shot = |owner, vx| {
+            let e = state(EntityKind::Shot { owner }, Vec2::ZERO, Vec2::new(vx, 0.0));
+            blip(&e, offset, None)
+        };
+        assert_eq!(shot(ShotOwner::Tank, -50.0), Some(Blip::Shell));
+        assert_eq!(shot(ShotOwner::Enemy, 50.0), None);
+        assert_eq!(shot(ShotOwner::Player(PlayerId(1)), -50.0), None);
+    }
+
+    #[test]
+    fn arrows_point_at_the_nearest_threats_from_the_edge() {
+        let world = Vec2::new(4000.0, 600.0);
+        let centre = Vec2::new(100.0, 300.0);
+        let snap = snapshot(vec![
+            state(EntityKind::Enemy, Vec2::new(300.0, 300.0), Vec2::ZERO),
+            state(EntityKind::Tank, Vec2::new(3500.0, 0.0), Vec2::ZERO),
+            state(EntityKind::Enemy, Vec2::new(1500.0, 300.0), Vec2::ZERO),
+            state(EntityKind::Convoy, Vec2::new(900.0, 0.0), Vec2::ZERO),
+        ]);
+        let threats = threats(&snap, centre, world, Vec2::new(800.0, 600.0));
+        assert_eq!(
+            threats,
+            [
+                (Blip::Tank, Vec2::new(-600.0, -300.0)),
+                (Blip::Enemy, Vec2::new(1400.0, 0.0)),
+            ]
+        );
+
+        let screen = Vec2::new(800.0, 600.0);
+        assert_eq!(edge_arrow(Vec2::new(300.0, 200.0), screen, 20.0), None);
+        let (pos, angle) = edge_arrow(Vec2::new(1200.0, 0.0), screen, 20.0).unwrap();
+        assert_eq!((pos, angle), (Vec2::new(780.0, 300.0), 0.0));
+        // Up and to the left, steeper than the screen's diagonal: top edge.
+        let (pos, _) = edge_arrow(Vec2::new(-400.0, -800.0), screen, 20.0).unwrap();
+        assert_eq!(pos, Vec2::new(260.0, 20.0));
+    }
+}
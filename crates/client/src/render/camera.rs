@@ -8,6 +8,12 @@
 //! more than `BELOW_GROUND_VIEW_RATIO` of a screen height below the
 //! ground line, and the top never reads past the world ceiling.
 //!
+//! A portrait screen (a phone held upright) would shrink the whole view
+//! to a sliver if it were letterboxed, so there the view fills
+//! `PORTRAIT_VIEW_FILL` of the screen height instead and shows a narrower,
+//! taller slice of the world. Callers wanting the extent actually on
+//! screen use `visible_size`, not `view_size`.
+//!
 //! The world's X axis is toroidal (`sim::util::wrap_coord`), so an entity
 //! near the seam may need to be drawn twice; `world_x_offsets_for` returns
 //! up to three candidate offsets to try.
@@ -21,13 +27,17 @@ pub type Point2 = Vec2;
 /// pilot peek further under the horizon when crashing; lowering it pins
 /// the ground closer to the bottom of the screen.
 pub const BELOW_GROUND_VIEW_RATIO: f32 = 0.20;
+/// Fraction of a portrait screen's height the view fills. Less than 1 so
+/// the pilot sees more sky and ground than a landscape player does, in
+/// exchange for seeing less to either side.
+pub const PORTRAIT_VIEW_FILL: f32 = 0.6;
 
 pub struct Camera {
     /// Total world extent in world units. X is toroidal at this width;
     /// Y is a hard `[0, world_size.y]` box.
     world_size: Vec2,
-    /// Visible chunk in world units (the camera's "lens"). The screen is
-    /// letterboxed to match this aspect ratio.
+    /// Visible chunk in world units (the camera's "lens"). Landscape
+    /// screens are letterboxed to match this aspect ratio.
     view_size: Vec2,
     /// Backing surface size in screen pixels.
     screen_size: Vec2,
@@ -71,6 +81,8 @@ impl Camera {
     pub fn set_screen_size(&mut self, w: f32, h: f32) {
         self.screen_size = Vec2::new(w, h);
         self.recompute_scale();
+        self.recompute_y_range();
+        self.center.y = self.clamp_center_y(self.center.y);
     }
 
     /// Update the floor reference. Recomputes the vertical scroll bounds.
@@ -137,17 +149,22 @@ impl Camera {
     fn recompute_scale(&mut self) {
         let sx = self.screen_size.x / self.view_size.x;
         let sy = self.screen_size.y / self.view_size.y;
-        self.scale = sx.min(sy);
+        self.scale = if self.screen_size.y > self.screen_size.x {
+            sy * PORTRAIT_VIEW_FILL
+        } else {
+            sx.min(sy)
+        };
     }
 
     /// Compute `(min_y, max_y)` for the camera center. The bottom bound
-    /// caps how far below ground the viewport may peek; the top bound
-    /// keeps the viewport entirely inside the world ceiling. If the world
-    /// is shorter than the view, the range collapses and clamping just
-    /// pins the camera at world center.
+    /// caps how far below ground the visible area may peek; the top bound
+    /// keeps it entirely inside the world ceiling. If the world is shorter
+    /// than what's visible, the range collapses and clamping just pins the
+    /// camera at world center.
     fn recompute_y_range(&mut self) {
-        let view_half = self.view_size.y * 0.5;
-        let min_y = self.ground_y + view_half - self.view_size.y * BELOW_GROUND_VIEW_RATIO;
+        let visible_h = self.visible_size().y;
+        let view_half = visible_h * 0.5;
+        let min_y = self.ground_y + view_half - visible_h * BELOW_GROUND_VIEW_RATIO;
         let max_y = self.world_size.y - view_half;
         // Guard against a degenerate world (`world_h < view_h`): in that
         // case there's nowhere to scroll, so just park at the midpoint.
@@ -172,6 +189,12 @@ impl Camera {
         self.view_size
     }
 
+    /// World units actually on screen: `view_size` on a screen of the
+    /// same shape, more on either axis when letterboxed or in portrait.
+    pub fn visible_size(&self) -> Vec2 {
+        self.screen_size / self.scale
+    }
+
     pub fn world_size(&self) -> Vec2 {
         self.world_size
     }
@@ -198,7 +221,7 @@ impl Camera {
         let world_w = self.world_size.x;
         // Visible half-width in world units, plus a margin so we don't pop
         // sprites in/out at the edge.
-        let half_view = self.view_size.x * 0.5 + sprite_half + 4.0;
+        let half_view = self.visible_size().x * 0.5 + sprite_half + 4.0;
         let mut out = [None; 3];
         for (i, dx) in [-world_w, 0.0, world_w].iter().enumerate() {
             let candidate = world_x + *dx;
@@ -273,6 +296,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn portrait_shows_a_narrower_taller_slice() {
+        let mut cam = make_camera();
+        cam.set_screen_size(390.0, 844.0);
+        let visible = cam.visible_size();
+        assert!((visible.y - 540.0 / PORTRAIT_VIEW_FILL).abs() < 1e-2);
+        assert!(visible.x < 540.0, "portrait view too wide: {visible}");
+        // The taller view still can't show more than 20% of itself below
+        // the ground.
+        let (min_y, _) = cam.y_range();
+        assert!((min_y - visible.y * (0.5 - BELOW_GROUND_VIEW_RATIO) - 26.0).abs() < 1e-2);
+
+        cam.set_screen_size(1280.0, 540.0);
+        assert_eq!(cam.visible_size(), Vec2::new(1280.0, 540.0));
+    }
+
     #[test]
     fn snap_to_within_range_is_identity() {
         let mut cam = make_camera();
@@ -0,0 +1,93 @@
+//! Chat overlay in the bottom-left corner: recent `ServerMsg::Chat` lines
+//! and announcements, fading like the kill feed, plus the line being typed
+//! while `InputState` is in chat mode. Opening the prompt brings faded
+//! lines back so there's context to reply to.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawParam, Text};
+
+/// Seconds a line stays on screen, fade included.
+const LINE_LIFE: f32 = 10.0;
+/// Final stretch of `LINE_LIFE` spent fading out.
+const FADE_TIME: f32 = 2.0;
+/// Lines kept for the open prompt; older ones drop off.
+const MAX_LINES: usize = 8;
+const LINE_SIZE: f32 = 16.0;
+const LINE_SPACING: f32 = 20.0;
+/// Gap from the left edge, and from the bottom edge to the prompt row.
+const MARGIN: f32 = 10.0;
+/// Room left under the prompt for the spectator status line.
+const BOTTOM_CLEARANCE: f32 = 48.0;
+
+const CHAT_COLOR: Color = Color::new(0.12, 0.10, 0.22, 1.0);
+/// Server notices and operator announcements.
+const NOTICE_COLOR: Color = Color::new(0.55, 0.35, 0.05, 1.0);
+const PROMPT_COLOR: Color = Color::new(0.05, 0.30, 0.45, 1.0);
+
+struct Line {
+    text: Text,
+    color: Color,
+    age: f32,
+}
+
+pub struct ChatLog {
+    font: &'static str,
+    lines: Vec<Line>,
+}
+
+impl ChatLog {
+    pub fn new(font: &'static str) -> Self {
+        ChatLog {
+            font,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Add a line. `from` is `None` for the server's own notices.
+    pub fn push(&mut self, from: Option<&str>, text: &str) {
+        let (line, color) = match from {
+            Some(name) => (format!("{name}: {text}"), CHAT_COLOR),
+            None => (format!("* {text}"), NOTICE_COLOR),
+        };
+        let mut text = Text::new(line);
+        text.set_font(self.font).set_scale(LINE_SIZE);
+        self.lines.push(Line {
+            text,
+            color,
+            age: 0.0,
+        });
+        if self.lines.len() > MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for line in &mut self.lines {
+            line.age += dt;
+        }
+    }
+
+    /// Newest line at the bottom. `draft` is the line being typed, if the
+    /// prompt is open.
+    pub fn draw(&self, canvas: &mut Canvas, screen: Vec2, draft: Option<&str>) {
+        let prompt_y = screen.y - BOTTOM_CLEARANCE - MARGIN;
+        if let Some(draft) = draft {
+            let mut prompt = Text::new(format!("say: {draft}_"));
+            prompt.set_font(self.font).set_scale(LINE_SIZE);
+            let dest = Vec2::new(MARGIN, prompt_y);
+            canvas.draw(&prompt, DrawParam::new().dest(dest).color(PROMPT_COLOR));
+        }
+        for (row, line) in self.lines.iter().rev().enumerate() {
+            let alpha = match draft {
+                Some(_) => 1.0,
+                None => ((LINE_LIFE - line.age) / FADE_TIME).clamp(0.0, 1.0),
+            };
+            if alpha <= 0.0 {
+                continue;
+            }
+            let color = Color::new(line.color.r, line.color.g, line.color.b, alpha);
+            let dest = Vec2::new(MARGIN, prompt_y - (row + 1) as f32 * LINE_SPACING);
+            canvas.draw(&line.text, DrawParam::new().dest(dest).color(color));
+        }
+    }
+}
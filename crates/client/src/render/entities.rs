@@ -18,17 +18,30 @@
 use ggez::glam::Vec2;
 use ggez::graphics::{Color, DrawMode, Mesh, MeshBuilder, Rect};
 use ggez::{Context, GameResult};
+use sim::ShipClass;
 
 /// Maroon ink we use for the player ship body (matches the Luftrauser
 /// reference). Tints stay close to a 2-color palette so the world reads
 /// as a single illustration rather than a mash-up of asset styles.
 pub const PLAYER_COLOR: Color = Color::new(0.36, 0.13, 0.17, 1.0);
+/// PvP team hull tints, indexed by team. Navy against rust so the two
+/// sides read apart even at a glance; teams past the end wrap around.
+pub const TEAM_COLORS: [Color; 2] = [
+    Color::new(0.16, 0.22, 0.40, 1.0),
+    Color::new(0.62, 0.26, 0.10, 1.0),
+];
 /// Enemy hulls are the same dark maroon — tone-matched so the player has
 /// to read shape, not color, to spot them.
 pub const ENEMY_COLOR: Color = Color::new(0.30, 0.10, 0.13, 1.0);
 /// Tank hulls — olive-drab so they read as armored ground vehicles
 /// against the dusty terrain.
 pub const TANK_COLOR: Color = Color::new(0.28, 0.30, 0.18, 1.0);
+/// Objective convoy — a dusty sand hull on the tank mesh, so the pilots
+/// can tell the truck they're guarding from the tanks hunting it.
+pub const CONVOY_COLOR: Color = Color::new(0.62, 0.52, 0.30, 1.0);
+/// Objective bomber — slate blue on the bomber mesh, away from both the
+/// maroon hostiles and the pilots' own hulls.
+pub const ESCORT_BOMBER_COLOR: Color = Color::new(0.24, 0.34, 0.46, 1.0);
 /// Tread "link" overlay color — drawn over the dark tread band each
 /// frame to fake the rolling-track motion. Kept independent of
 /// `TANK_COLOR` so the contrast against the band stays readable even
@@ -114,25 +127,46 @@ pub const TANK_TREAD_BAND_Y: f32 = -5.0;
 pub const TANK_TREAD_HALF_WIDTH: f32 = 14.5;
 
 pub struct EntityMeshes {
+    /// `ShipClass::Fighter` — the original player silhouette.
     pub player: ShipMesh,
+    pub interceptor: ShipMesh,
+    pub gunship: ShipMesh,
+    pub bomber: ShipMesh,
     pub enemy: ShipMesh,
     pub tank: TankMesh,
     pub shot: Mesh,
     /// Tank-fired shell. Bigger and stubbier than `shot` so the heavy
     /// artillery reads as a different threat at a glance.
     pub tank_shell: Mesh,
+    /// Bomber payload. Round-nosed with tail fins so it can't be mistaken
+    /// for a tank shell heading the other way.
+    pub bomb: Mesh,
 }
 
 impl EntityMeshes {
     pub fn build(ctx: &mut Context) -> GameResult<Self> {
         Ok(Self {
             player: build_player(ctx)?,
+            interceptor: build_interceptor(ctx)?,
+            gunship: build_gunship(ctx)?,
+            bomber: build_bomber(ctx)?,
             enemy: build_enemy(ctx)?,
             tank: build_tank(ctx)?,
             shot: build_shot(ctx)?,
             tank_shell: build_tank_shell(ctx)?,
+            bomb: build_bomb(ctx)?,
         })
     }
+
+    /// Ship silhouette for a player loadout.
+    pub fn ship_for(&self, class: ShipClass) -> &ShipMesh {
+        match class {
+            ShipClass::Fighter => &self.player,
+            ShipClass::Interceptor => &self.interceptor,
+            ShipClass::Gunship => &self.gunship,
+            ShipClass::Bomber => &self.bomber,
+        }
+    }
 }
 
 /// Plane silhouette pointed up at facing 0. Body = fuselage + tail + nose
@@ -178,6 +212,145 @@ fn build_player(ctx: &mut Context) -> GameResult<ShipMesh> {
     })
 }
 
+/// Interceptor — a needle fuselage with small swept delta wings. Thinner
+/// and longer than the fighter so its speed reads in the silhouette.
+fn build_interceptor(ctx: &mut Context) -> GameResult<ShipMesh> {
+    let mut body = MeshBuilder::new();
+    // Needle fuselage, nose at -16, tail at +8.
+    body.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(0.0, -16.0),
+            Vec2::new(1.8, -8.0),
+            Vec2::new(1.8, 8.0),
+            Vec2::new(-1.8, 8.0),
+            Vec2::new(-1.8, -8.0),
+        ],
+        Color::WHITE,
+    )?;
+    // Small V tail.
+    body.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(-4.5, 9.0),
+            Vec2::new(0.0, 4.0),
+            Vec2::new(4.5, 9.0),
+        ],
+        Color::WHITE,
+    )?;
+    let body_data = body.build();
+
+    let mut wings = MeshBuilder::new();
+    // Delta wings, mirrored so foreshortening stays symmetric.
+    wings.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(-1.5, -4.0),
+            Vec2::new(-9.0, 4.0),
+            Vec2::new(-1.5, 4.0),
+        ],
+        Color::WHITE,
+    )?;
+    wings.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(1.5, -4.0),
+            Vec2::new(9.0, 4.0),
+            Vec2::new(1.5, 4.0),
+        ],
+        Color::WHITE,
+    )?;
+    let wings_data = wings.build();
+
+    Ok(ShipMesh {
+        body: Mesh::from_data(ctx, body_data),
+        wings: Mesh::from_data(ctx, wings_data),
+    })
+}
+
+/// Gunship — a broad fuselage with a barrel poking forward on each side
+/// of the nose, matching the twin-gun spacing in `sim::loadout`.
+fn build_gunship(ctx: &mut Context) -> GameResult<ShipMesh> {
+    let mut body = MeshBuilder::new();
+    // Wide fuselage.
+    body.rectangle(
+        DrawMode::fill(),
+        Rect::new(-3.5, -11.0, 7.0, 21.0),
+        Color::WHITE,
+    )?;
+    // Twin barrels either side of the nose.
+    for x in [-6.0_f32, 5.0] {
+        body.rectangle(DrawMode::fill(), Rect::new(x, -15.0, 1.6, 9.0), Color::WHITE)?;
+    }
+    // Heavy tailplane.
+    body.rectangle(
+        DrawMode::fill(),
+        Rect::new(-7.0, 7.0, 14.0, 3.5),
+        Color::WHITE,
+    )?;
+    let body_data = body.build();
+
+    let mut wings = MeshBuilder::new();
+    wings.rectangle(
+        DrawMode::fill(),
+        Rect::new(-15.0, -3.0, 30.0, 6.5),
+        Color::WHITE,
+    )?;
+    let wings_data = wings.build();
+
+    Ok(ShipMesh {
+        body: Mesh::from_data(ctx, body_data),
+        wings: Mesh::from_data(ctx, wings_data),
+    })
+}
+
+/// Bomber — a fat, rounded fuselage on long straight wings, with a dark
+/// bomb-bay stripe on the belly.
+fn build_bomber(ctx: &mut Context) -> GameResult<ShipMesh> {
+    let mut body = MeshBuilder::new();
+    // Rounded fuselage — octagon stretched along the nose axis.
+    body.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(-2.0, -13.0),
+            Vec2::new(2.0, -13.0),
+            Vec2::new(4.5, -9.0),
+            Vec2::new(4.5, 6.0),
+            Vec2::new(2.0, 10.0),
+            Vec2::new(-2.0, 10.0),
+            Vec2::new(-4.5, 6.0),
+            Vec2::new(-4.5, -9.0),
+        ],
+        Color::WHITE,
+    )?;
+    // Bomb bay.
+    body.rectangle(
+        DrawMode::fill(),
+        Rect::new(-1.5, -3.0, 3.0, 7.0),
+        Color::new(0.18, 0.18, 0.18, 1.0),
+    )?;
+    // Tail.
+    body.rectangle(
+        DrawMode::fill(),
+        Rect::new(-6.5, 8.0, 13.0, 3.0),
+        Color::WHITE,
+    )?;
+    let body_data = body.build();
+
+    let mut wings = MeshBuilder::new();
+    wings.rectangle(
+        DrawMode::fill(),
+        Rect::new(-17.0, -4.0, 34.0, 4.0),
+        Color::WHITE,
+    )?;
+    let wings_data = wings.build();
+
+    Ok(ShipMesh {
+        body: Mesh::from_data(ctx, body_data),
+        wings: Mesh::from_data(ctx, wings_data),
+    })
+}
+
 /// Enemy ship — slightly squatter than the player, with a swept-back
 /// wing so it reads as "the bad guy" even at a quick glance. Wings live
 /// in their own mesh so banking foreshortens them the same way the
@@ -399,6 +572,31 @@ fn build_tank_shell(ctx: &mut Context) -> GameResult<Mesh> {
     Ok(Mesh::from_data(ctx, data))
 }
 
+/// Bomb — a round-nosed casing with a cross of tail fins. Same +Y
+/// orientation convention as `build_shot`.
+fn build_bomb(ctx: &mut Context) -> GameResult<Mesh> {
+    let mut mb = MeshBuilder::new();
+    mb.circle(DrawMode::fill(), Vec2::new(0.0, -2.0), 3.5, 0.2, Color::WHITE)?;
+    mb.rectangle(
+        DrawMode::fill(),
+        Rect::new(-3.5, -2.0, 7.0, 4.0),
+        Color::WHITE,
+    )?;
+    // Tail fins.
+    mb.polygon(
+        DrawMode::fill(),
+        &[
+            Vec2::new(-4.0, 6.5),
+            Vec2::new(-1.5, 2.0),
+            Vec2::new(1.5, 2.0),
+            Vec2::new(4.0, 6.5),
+        ],
+        Color::WHITE,
+    )?;
+    let data = mb.build();
+    Ok(Mesh::from_data(ctx, data))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
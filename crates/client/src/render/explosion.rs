@@ -32,7 +32,9 @@ impl ExplosionStyle {
     pub fn for_cause(cause: &DeathCause) -> Self {
         match cause {
             DeathCause::Terrain(TerrainKind::Ground) => ExplosionStyle::DustAndEmbers,
-            DeathCause::Enemy | DeathCause::EnemyShot => ExplosionStyle::FieryBurst,
+            DeathCause::Enemy | DeathCause::EnemyShot | DeathCause::Player(_) => {
+                ExplosionStyle::FieryBurst
+            }
         }
     }
 }
@@ -57,6 +57,20 @@ impl InstanceQuadBatch {
         );
     }
 
+    /// Rectangle with its top-left corner at `top_left`, `size` screen
+    /// pixels across. For HUD panels built out of the same quad.
+    pub fn push_rect(&mut self, top_left: Vec2, size: Vec2, color: Color) {
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+        self.instances.push(
+            DrawParam::new()
+                .dest(top_left)
+                .scale([size.x, size.y])
+                .color(color),
+        );
+    }
+
     /// Centered square at `world_pos` (Y-up world coords) with a world-space
     /// `radius`. Issues one push per visible wrap copy so particles straddling
     /// the X seam don't half-vanish.
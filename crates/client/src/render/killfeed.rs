@@ -0,0 +1,86 @@
+//! PvP kill feed. Each `PlayerKilled` with `DeathCause::Player` pushes a
+//! "KILLER > VICTIM" line into the top-right corner of the screen; lines
+//! hold for a few seconds, then fade. Screen-space, unlike `popups`.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawParam, Text};
+use ggez::Context;
+
+/// Seconds a line stays on screen, fade included.
+const LINE_LIFE: f32 = 5.0;
+/// Final stretch of `LINE_LIFE` spent fading out.
+const FADE_TIME: f32 = 1.0;
+/// Oldest lines drop off once the feed is this long.
+const MAX_LINES: usize = 5;
+const LINE_SIZE: f32 = 16.0;
+const LINE_SPACING: f32 = 20.0;
+/// Gap from the top and right screen edges.
+const MARGIN: f32 = 10.0;
+
+const LINE_COLOR: Color = Color::new(0.28, 0.08, 0.12, 1.0);
+/// Lines the local pilot is in stand out.
+const LOCAL_COLOR: Color = Color::new(0.82, 0.45, 0.10, 1.0);
+
+struct Line {
+    text: Text,
+    color: Color,
+    age: f32,
+}
+
+pub struct KillFeed {
+    font: &'static str,
+    lines: Vec<Line>,
+}
+
+impl KillFeed {
+    pub fn new(font: &'static str) -> Self {
+        KillFeed {
+            font,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Add a kill. `involves_local` highlights kills by or of this client's
+    /// pilot.
+    pub fn push(&mut self, killer: &str, victim: &str, involves_local: bool) {
+        let mut text = Text::new(format!(
+            "{} > {}",
+            killer.to_uppercase(),
+            victim.to_uppercase()
+        ));
+        text.set_font(self.font).set_scale(LINE_SIZE);
+        self.lines.push(Line {
+            text,
+            color: if involves_local {
+                LOCAL_COLOR
+            } else {
+                LINE_COLOR
+            },
+            age: 0.0,
+        });
+        if self.lines.len() > MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for line in &mut self.lines {
+            line.age += dt;
+        }
+        self.lines.retain(|l| l.age < LINE_LIFE);
+    }
+
+    /// Newest line on top, right-aligned to `screen_width`.
+    pub fn draw(&self, ctx: &Context, canvas: &mut Canvas, screen_width: f32) {
+        for (row, line) in self.lines.iter().rev().enumerate() {
+            let alpha = ((LINE_LIFE - line.age) / FADE_TIME).clamp(0.0, 1.0);
+            let color = Color::new(line.color.r, line.color.g, line.color.b, alpha);
+            let width = line.text.measure(ctx).map_or(0.0, |size| size.x);
+            let dest = Vec2::new(
+                screen_width - MARGIN - width,
+                MARGIN + row as f32 * LINE_SPACING,
+            );
+            canvas.draw(&line.text, DrawParam::new().dest(dest).color(color));
+        }
+    }
+}
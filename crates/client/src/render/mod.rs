@@ -1,7 +1,12 @@
 pub mod camera;
+pub mod chat;
 pub mod entities;
 pub mod explosion;
 pub mod instance_batch;
+pub mod killfeed;
 pub mod particles;
+pub mod popups;
+pub mod radar;
 pub mod sky;
 pub mod terrain;
+pub mod touch;
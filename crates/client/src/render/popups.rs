@@ -0,0 +1,94 @@
+//! Floating score popups. Each `ScoreAwarded` event spawns a short-lived
+//! label at the kill position that drifts upward and fades. Pure
+//! cosmetic, like `explosion`.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawParam, Text};
+use sim::ScoreReason;
+
+use crate::render::camera::Camera;
+
+/// Seconds a popup stays on screen.
+const POPUP_LIFE: f32 = 1.2;
+/// Upward drift in world units per second.
+const POPUP_RISE_SPEED: f32 = 40.0;
+/// Base kill popups use this size; bonus lines are a notch smaller.
+const POPUP_SIZE: f32 = 20.0;
+const BONUS_SIZE: f32 = 16.0;
+/// Bonus lines start this far above the base line so a multi-part payout
+/// stacks instead of overlapping.
+const BONUS_OFFSET_Y: f32 = 18.0;
+/// Rough half-width of a popup label in world units, for wrap mirroring.
+const POPUP_HALF_EXTENT: f32 = 60.0;
+
+const KILL_COLOR: Color = Color::new(0.28, 0.08, 0.12, 1.0);
+const BONUS_COLOR: Color = Color::new(0.82, 0.45, 0.10, 1.0);
+
+struct Popup {
+    /// World coords (Y-up).
+    pos: Vec2,
+    age: f32,
+    text: Text,
+    color: Color,
+}
+
+pub struct ScorePopups {
+    font: &'static str,
+    popups: Vec<Popup>,
+}
+
+impl ScorePopups {
+    pub fn new(font: &'static str) -> Self {
+        ScorePopups {
+            font,
+            popups: Vec::new(),
+        }
+    }
+
+    pub fn spawn(&mut self, pos: Vec2, points: i32, reason: ScoreReason, multiplier: i32) {
+        let (label, size, color, offset) = match reason {
+            ScoreReason::EnemyKill | ScoreReason::TankKill | ScoreReason::PlayerKill => {
+                (String::new(), POPUP_SIZE, KILL_COLOR, 0.0)
+            }
+            ScoreReason::AirStrike => ("AIR STRIKE ".to_string(), BONUS_SIZE, BONUS_COLOR, 1.0),
+            ScoreReason::LowFlying => ("LOW PASS ".to_string(), BONUS_SIZE, BONUS_COLOR, 2.0),
+            ScoreReason::Objective => ("MISSION ".to_string(), BONUS_SIZE, BONUS_COLOR, 1.0),
+        };
+        let combo = if multiplier > 1 {
+            format!("  x{multiplier}")
+        } else {
+            String::new()
+        };
+        let mut text = Text::new(format!("{label}+{points}{combo}"));
+        text.set_font(self.font).set_scale(size);
+        self.popups.push(Popup {
+            pos: pos + Vec2::new(0.0, BONUS_OFFSET_Y * offset),
+            age: 0.0,
+            text,
+            color,
+        });
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for p in &mut self.popups {
+            p.age += dt;
+            p.pos.y += POPUP_RISE_SPEED * dt;
+        }
+        self.popups.retain(|p| p.age < POPUP_LIFE);
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, camera: &Camera) {
+        for p in &self.popups {
+            let alpha = (1.0 - p.age / POPUP_LIFE).clamp(0.0, 1.0);
+            let color = Color::new(p.color.r, p.color.g, p.color.b, alpha);
+            for cand in camera
+                .world_x_offsets_for(p.pos.x, POPUP_HALF_EXTENT)
+                .into_iter()
+                .flatten()
+            {
+                let screen = camera.world_to_screen(Vec2::new(cand, p.pos.y));
+                canvas.draw(&p.text, DrawParam::new().dest(screen).color(color));
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Radar strip along the bottom of the HUD and the off-screen threat
+//! arrows, for `radar`. The strip is a pale card with the terrain
+//! silhouette and one square per blip, all in one batched draw; arrows
+//! are a single triangle mesh drawn at most `radar::MAX_ARROWS` times.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh};
+use ggez::{Context, GameResult};
+use protocol::Snapshot;
+use sim::terrain::surface_y_at;
+use sim::PlayerId;
+
+use crate::radar::{self, Blip};
+use crate::render::camera::Camera;
+use crate::render::instance_batch::InstanceQuadBatch;
+
+/// Strip width as a fraction of the screen, and its bounds in pixels.
+const STRIP_WIDTH: f32 = 0.4;
+const MIN_STRIP_WIDTH: f32 = 240.0;
+const MAX_STRIP_WIDTH: f32 = 560.0;
+const STRIP_HEIGHT: f32 = 56.0;
+/// Gap under the strip, leaving room for the spectator status line.
+const STRIP_BOTTOM: f32 = 48.0;
+/// Terrain is sampled this many times across the strip.
+const TERRAIN_COLUMNS: usize = 120;
+/// Arrows sit this far inside the screen edge.
+const ARROW_MARGIN: f32 = 24.0;
+const ARROW_SIZE: f32 = 12.0;
+
+const CARD_COLOR: Color = Color::new(0.98, 0.95, 0.86, 0.7);
+/// Tints the slice of the strip that's on screen.
+const VIEW_COLOR: Color = Color::new(0.28, 0.08, 0.12, 0.1);
+const TERRAIN_COLOR: Color = Color::new(0.66, 0.50, 0.36, 0.9);
+const ME_COLOR: Color = Color::new(0.82, 0.45, 0.10, 1.0);
+const PILOT_COLOR: Color = Color::new(0.13, 0.20, 0.36, 1.0);
+const ENEMY_COLOR: Color = Color::new(0.80, 0.20, 0.16, 1.0);
+const TANK_COLOR: Color = Color::new(0.28, 0.30, 0.18, 1.0);
+const ESCORT_COLOR: Color = Color::new(0.20, 0.42, 0.18, 1.0);
+const SHELL_COLOR: Color = Color::new(0.82, 0.32, 0.14, 1.0);
+
+fn blip_style(blip: Blip) -> (Color, f32) {
+    match blip {
+        Blip::Me => (ME_COLOR, 7.0),
+        Blip::Pilot => (PILOT_COLOR, 5.0),
+        Blip::Enemy => (ENEMY_COLOR, 5.0),
+        Blip::Tank => (TANK_COLOR, 5.0),
+        Blip::Escort => (ESCORT_COLOR, 6.0),
+        Blip::Shell => (SHELL_COLOR, 3.0),
+    }
+}
+
+pub struct Radar {
+    batch: InstanceQuadBatch,
+    /// Triangle pointing along +X, `ARROW_SIZE` from centre to tip.
+    arrow: Mesh,
+}
+
+impl Radar {
+    pub fn new(ctx: &mut Context) -> GameResult<Self> {
+        let s = ARROW_SIZE;
+        let points = [
+            Vec2::new(s, 0.0),
+            Vec2::new(-s * 0.6, s * 0.7),
+            Vec2::new(-s * 0.6, -s * 0.7),
+        ];
+        Ok(Radar {
+            batch: InstanceQuadBatch::new(ctx)?,
+            arrow: Mesh::new_polygon(ctx, DrawMode::fill(), &points, Color::WHITE)?,
+        })
+    }
+
+    pub fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        camera: &Camera,
+        snap: &Snapshot,
+        me: Option<PlayerId>,
+    ) {
+        let screen = camera.screen_size();
+        let world = camera.world_size();
+        let centre = camera.center();
+        let width = (screen.x * STRIP_WIDTH).clamp(MIN_STRIP_WIDTH, MAX_STRIP_WIDTH);
+        let size = Vec2::new(width, STRIP_HEIGHT);
+        let origin = Vec2::new(
+            (screen.x - width) * 0.5,
+            screen.y - STRIP_BOTTOM - STRIP_HEIGHT,
+        );
+
+        self.batch.begin();
+        self.batch.push_rect(origin, size, CARD_COLOR);
+        let view = (camera.visible_size().x / world.x).min(1.0) * width;
+        self.batch.push_rect(
+            origin + Vec2::new((width - view) * 0.5, 0.0),
+            Vec2::new(view, size.y),
+            VIEW_COLOR,
+        );
+
+        let column = width / TERRAIN_COLUMNS as f32;
+        for i in 0..TERRAIN_COLUMNS {
+            let offset_x = ((i as f32 + 0.5) / TERRAIN_COLUMNS as f32 - 0.5) * world.x;
+            let ground = surface_y_at(centre.x + offset_x, &snap.terrain);
+            let top = radar::strip_point(offset_x, ground, world).y;
+            self.batch.push_rect(
+                origin + Vec2::new(i as f32 * column, top * size.y),
+                // A hair wider than the column so neighbours don't leave
+                // seams between them.
+                Vec2::new(column + 0.5, (1.0 - top) * size.y),
+                TERRAIN_COLOR,
+            );
+        }
+
+        for (blip, at) in radar::blips(snap, centre, world, me) {
+            let (color, px) = blip_style(blip);
+            self.batch.push_screen(origin + at * size, px, color);
+        }
+        self.batch.flush(canvas);
+
+        let scale = camera.scale();
+        for (blip, offset) in radar::threats(snap, centre, world, camera.visible_size()) {
+            let on_screen = Vec2::new(offset.x * scale, -offset.y * scale);
+            let Some((at, angle)) = radar::edge_arrow(on_screen, screen, ARROW_MARGIN) else {
+                continue;
+            };
+            let (color, _) = blip_style(blip);
+            canvas.draw(
+                &self.arrow,
+                DrawParam::new().dest(at).rotation(angle).color(color),
+            );
+        }
+    }
+}
@@ -52,9 +52,9 @@ const PATCH_SPACING: f32 = 14.0;
 const PATCH_MAX_DEPTH: f32 = 36.0;
 /// How far below world Y=0 the soil polygon's bottom edge sits. We
 /// over-extend so the camera diving into a valley never sees the
-/// mesh's open bottom. Stored in screen-down (Y-flipped) coords, so
-/// this is positive.
-const FLOOR_DEPTH: f32 = 80.0;
+/// mesh's open bottom, even on the taller portrait view. Stored in
+/// screen-down (Y-flipped) coords, so this is positive.
+const FLOOR_DEPTH: f32 = 200.0;
 /// Horizon stripe thickness (world units).
 const HORIZON_STRIPE: f32 = 2.0;
 
@@ -183,7 +183,7 @@ impl TerrainRenderer {
             return;
         };
         let scale = camera.scale();
-        for dx in copies_for(camera.center().x, self.world_width, camera.view_size().x) {
+        for dx in copies_for(camera.center().x, self.world_width, camera.visible_size().x) {
             let origin = camera.world_to_screen(Vec2::new(dx, 0.0));
             canvas.draw(
                 mesh,
@@ -0,0 +1,64 @@
+//! On-screen joystick and fire button for `touch::TouchControls`. Drawn
+//! translucent over the HUD, and only once the controls are enabled.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh};
+use ggez::{Context, GameResult};
+
+use crate::touch::TouchControls;
+
+/// Radius the meshes are built at; draws scale them to the real size.
+const MESH_RADIUS: f32 = 100.0;
+const RING_WIDTH: f32 = 5.0;
+const CONTROL_COLOR: Color = Color::new(0.13, 0.20, 0.36, 0.35);
+const ACTIVE_COLOR: Color = Color::new(0.13, 0.20, 0.36, 0.6);
+const FIRE_COLOR: Color = Color::new(0.80, 0.25, 0.18, 0.35);
+const FIRE_ACTIVE_COLOR: Color = Color::new(0.80, 0.25, 0.18, 0.65);
+
+pub struct TouchOverlay {
+    ring: Mesh,
+    disc: Mesh,
+}
+
+impl TouchOverlay {
+    pub fn new(ctx: &mut Context) -> GameResult<Self> {
+        let (at, tolerance) = (Vec2::ZERO, 0.5);
+        let (stroke, fill) = (DrawMode::stroke(RING_WIDTH), DrawMode::fill());
+        Ok(TouchOverlay {
+            ring: Mesh::new_circle(ctx, stroke, at, MESH_RADIUS, tolerance, Color::WHITE)?,
+            disc: Mesh::new_circle(ctx, fill, at, MESH_RADIUS, tolerance, Color::WHITE)?,
+        })
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, touch: &TouchControls) {
+        if !touch.enabled() {
+            return;
+        }
+        let r = touch.stick_radius();
+        let (centre, knob) = touch.stick();
+        let knob_color = if centre == knob {
+            CONTROL_COLOR
+        } else {
+            ACTIVE_COLOR
+        };
+        put(canvas, &self.ring, centre, r, CONTROL_COLOR);
+        put(canvas, &self.disc, knob, r * 0.45, knob_color);
+
+        let (fire, fire_r) = touch.fire_button();
+        let fire_color = if touch.firing() {
+            FIRE_ACTIVE_COLOR
+        } else {
+            FIRE_COLOR
+        };
+        put(canvas, &self.disc, fire, fire_r, fire_color);
+    }
+}
+
+/// Draw a `MESH_RADIUS` mesh centred on `at` at `radius`.
+fn put(canvas: &mut Canvas, mesh: &Mesh, at: Vec2, radius: f32, color: Color) {
+    let scale = radius / MESH_RADIUS;
+    canvas.draw(
+        mesh,
+        DrawParam::new().dest(at).scale([scale, scale]).color(color),
+    );
+}
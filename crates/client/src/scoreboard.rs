@@ -0,0 +1,310 @@
+//! Scoreboard and end-of-run summary. The scoreboard is held up over the
+//! world with the Scoreboard key (Tab by default): every pilot's name,
+//! score, kills and whether they're flying. The summary replaces the old
+//! bare "GAME OVER" once the local pilot goes down: how long the run
+//! lasted, the level it reached, kills by kind and gun accuracy.
+//!
+//! Both read the per-pilot `sim::PilotStats` that ride on snapshots.
+//! `MainState` calls `sync` with every snapshot and positions everything
+//! in screen pixels at draw time, like `objective_hud`.
+
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect};
+use ggez::{Context, GameResult};
+use protocol::Snapshot;
+use sim::{EntityKind, PlayerId, RunStats};
+
+use crate::assets::AssetManager;
+use crate::render::camera::Point2;
+use crate::widget::TextWidget;
+
+const TITLE_COLOR: Color = Color::new(0.28, 0.08, 0.12, 1.0);
+const DETAIL_COLOR: Color = Color::new(0.22, 0.12, 0.10, 1.0);
+const HINT_COLOR: Color = Color::new(0.42, 0.30, 0.22, 1.0);
+/// Cream card behind the text so it reads over a busy sky.
+const CARD_COLOR: Color = Color::new(0.98, 0.95, 0.86, 0.88);
+const CARD_PADDING: f32 = 24.0;
+/// Gap between stacked blocks of text on a card.
+const LINE_GAP: f32 = 12.0;
+/// Pilot rows on the scoreboard; the rest are summed up in one line.
+const SCOREBOARD_ROWS: usize = 12;
+
+pub struct Scoreboard {
+    title: TextWidget,
+    table: TextWidget,
+    card: Mesh,
+    /// Table text currently set, to skip re-laying out identical text.
+    text: String,
+}
+
+impl Scoreboard {
+    pub fn new(ctx: &mut Context, am: &mut AssetManager) -> GameResult<Self> {
+        let mut title = TextWidget::new(ctx, am, 32.0)?;
+        title.set_text("SCOREBOARD", 32.0);
+        Ok(Scoreboard {
+            title,
+            table: TextWidget::new(ctx, am, 20.0)?,
+            card: card_mesh(ctx)?,
+            text: String::new(),
+        })
+    }
+
+    pub fn sync(&mut self, snap: &Snapshot, local: Option<PlayerId>) {
+        let text = table_text(snap, local);
+        if text != self.text {
+            self.table.set_text(&text, 20.0);
+            self.text = text;
+        }
+    }
+
+    pub fn draw(&mut self, ctx: &Context, canvas: &mut Canvas, screen: Vec2) {
+        draw_card(
+            ctx,
+            canvas,
+            &self.card,
+            screen,
+            &mut [
+                (&mut self.title, TITLE_COLOR),
+                (&mut self.table, DETAIL_COLOR),
+            ],
+        );
+    }
+}
+
+pub struct RunSummary {
+    title: TextWidget,
+    details: TextWidget,
+    hint: TextWidget,
+    card: Mesh,
+    /// The finished run and score on show.
+    shown: Option<(RunStats, i32)>,
+}
+
+impl RunSummary {
+    pub fn new(ctx: &mut Context, am: &mut AssetManager) -> GameResult<Self> {
+        let mut title = TextWidget::new(ctx, am, 48.0)?;
+        title.set_text("GAME OVER", 48.0);
+        let mut hint = TextWidget::new(ctx, am, 22.0)?;
+        hint.set_text("press any key for menu", 22.0);
+        Ok(RunSummary {
+            title,
+            details: TextWidget::new(ctx, am, 20.0)?,
+            hint,
+            card: card_mesh(ctx)?,
+            shown: None,
+        })
+    }
+
+    /// Pick up the local pilot's last run once it has ended. A run still
+    /// in progress leaves the previous summary alone.
+    pub fn sync(&mut self, snap: &Snapshot, local: Option<PlayerId>) {
+        let Some(pid) = local else {
+            return;
+        };
+        let Some((_, stats)) = snap.pilot_stats.iter().find(|(p, _)| *p == pid) else {
+            return;
+        };
+        if stats.run.ended.is_none() {
+            return;
+        }
+        let score = score_of(snap, pid);
+        if self.shown == Some((stats.run, score)) {
+            return;
+        }
+        self.shown = Some((stats.run, score));
+        self.details
+            .set_text(&summary_text(&stats.run, score), 20.0);
+    }
+
+    pub fn draw(&mut self, ctx: &Context, canvas: &mut Canvas, screen: Vec2) {
+        let mut blocks = vec![(&mut self.title, TITLE_COLOR)];
+        if self.shown.is_some() {
+            blocks.push((&mut self.details, DETAIL_COLOR));
+        }
+        blocks.push((&mut self.hint, HINT_COLOR));
+        draw_card(ctx, canvas, &self.card, screen, &mut blocks);
+    }
+}
+
+fn card_mesh(ctx: &mut Context) -> GameResult<Mesh> {
+    Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        Rect::new(0.0, 0.0, 1.0, 1.0),
+        Color::WHITE,
+    )
+}
+
+/// A card centred on the screen with `blocks` stacked down it, each
+/// centred horizontally.
+fn draw_card(
+    ctx: &Context,
+    canvas: &mut Canvas,
+    card: &Mesh,
+    screen: Vec2,
+    blocks: &mut [(&mut TextWidget, Color)],
+) {
+    let sizes: Vec<(f32, f32)> = blocks
+        .iter()
+        .map(|(w, _)| (w.width(ctx), w.height(ctx)))
+        .collect();
+    let inner_w = sizes.iter().map(|s| s.0).fold(0.0, f32::max);
+    let inner_h =
+        sizes.iter().map(|s| s.1).sum::<f32>() + LINE_GAP * blocks.len().saturating_sub(1) as f32;
+    let card_w = inner_w + CARD_PADDING * 2.0;
+    let card_h = inner_h + CARD_PADDING * 2.0;
+    let card_y = (screen.y - card_h) / 2.0;
+    canvas.draw(
+        card,
+        DrawParam::new()
+            .dest([(screen.x - card_w) / 2.0, card_y])
+            .scale([card_w, card_h])
+            .color(CARD_COLOR),
+    );
+    let mut y = card_y + CARD_PADDING;
+    for ((widget, color), (w, h)) in blocks.iter_mut().zip(sizes) {
+        widget.set_position(Point2::new((screen.x - w) / 2.0, y));
+        widget.draw_with(canvas, *color);
+        y += h + LINE_GAP;
+    }
+}
+
+fn score_of(snap: &Snapshot, pid: PlayerId) -> i32 {
+    snap.score_by_player
+        .iter()
+        .find(|(p, _)| *p == pid)
+        .map_or(0, |(_, s)| *s)
+}
+
+/// One line per pilot, best score first, with the local pilot marked;
+/// team totals on top when the world has teams.
+fn table_text(snap: &Snapshot, local: Option<PlayerId>) -> String {
+    let mut text = String::new();
+    for (team, score) in &snap.team_scores {
+        text.push_str(&format!("TEAM {} {score:>7}   ", team + 1));
+    }
+    if !text.is_empty() {
+        text = format!("{}\n\n", text.trim_end());
+    }
+    text.push_str(&format!(
+        "  {:<12} {:>7} {:>5}  STATUS",
+        "PILOT", "SCORE", "KILLS"
+    ));
+
+    let mut pilots: Vec<(PlayerId, i32)> = snap.score_by_player.clone();
+    pilots.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    for &(pid, score) in pilots.iter().take(SCOREBOARD_ROWS) {
+        let name = snap
+            .player_names
+            .iter()
+            .find(|(p, _)| *p == pid)
+            .map_or_else(|| format!("PILOT {}", pid.0), |(_, n)| n.to_uppercase());
+        let kills = snap
+            .pilot_stats
+            .iter()
+            .find(|(p, _)| *p == pid)
+            .map_or(0, |(_, s)| s.kills);
+        let flying = snap.entities.iter().any(|e| {
+            e.alive && matches!(e.kind, EntityKind::Player { player_id } if player_id == pid)
+        });
+        let mut status = if flying { "FLYING" } else { "DOWN" }.to_string();
+        if snap.bots.contains(&pid) {
+            status.push_str(" (BOT)");
+        }
+        let marker = if Some(pid) == local { ">" } else { " " };
+        text.push_str(&format!(
+            "\n{marker} {name:<12} {score:>7} {kills:>5}  {status}"
+        ));
+    }
+    if pilots.len() > SCOREBOARD_ROWS {
+        text.push_str(&format!("\n  +{} MORE", pilots.len() - SCOREBOARD_ROWS));
+    }
+    if snap.spectators > 0 {
+        text.push_str(&format!("\n\n{} WATCHING", snap.spectators));
+    }
+    text
+}
+
+/// The end-of-run card: time and level, score, kills by kind and accuracy.
+fn summary_text(run: &RunStats, score: i32) -> String {
+    let secs = run.duration_secs(run.ended.unwrap_or(run.started)).round() as u32;
+    let mut kills = format!("{} SHIPS   {} TANKS", run.enemy_kills, run.tank_kills);
+    if run.pilot_kills > 0 {
+        kills.push_str(&format!("   {} PILOTS", run.pilot_kills));
+    }
+    let accuracy = match run.accuracy() {
+        Some(a) => format!(
+            "ACCURACY {:.0}%  ({} OF {} SHOTS)",
+            a * 100.0,
+            run.shots_hit,
+            run.shots_fired
+        ),
+        None => "NO SHOTS FIRED".to_string(),
+    };
+    format!(
+        "FLEW {}:{:02}  —  REACHED LEVEL {}\nSCORE {score}\n\n{kills}\n{accuracy}",
+        secs / 60,
+        secs % 60,
+        run.level
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocol::EntityState;
+    use sim::{Entity, EntityId, PilotStats, ShipClass, Tick};
+
+    fn snapshot() -> Snapshot {
+        let mut downed = PilotStats::new(Tick(0), 1);
+        downed.killed(EntityKind::Enemy);
+        downed.died(Tick(60));
+        let ship = Entity::player(EntityId(1), PlayerId(2), Vec2::ZERO, ShipClass::Fighter);
+        Snapshot {
+            tick: Tick(90),
+            entities: vec![EntityState::from_entity(&ship)],
+            score_by_player: vec![(PlayerId(1), 300), (PlayerId(2), 100)],
+            player_names: vec![(PlayerId(1), "ada".into()), (PlayerId(2), "wing".into())],
+            pilot_stats: vec![
+                (PlayerId(1), downed),
+                (PlayerId(2), PilotStats::new(Tick(0), 1)),
+            ],
+            bots: vec![PlayerId(2)],
+            teams: Vec::new(),
+            team_scores: Vec::new(),
+            objective: None,
+            spectators: 1,
+            level: 2,
+            paused: false,
+            time_scale: 1.0,
+            terrain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn scoreboard_lists_every_pilot_best_first() {
+        let text = table_text(&snapshot(), Some(PlayerId(1)));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "> ADA              300     1  DOWN");
+        assert_eq!(lines[2], "  WING             100     0  FLYING (BOT)");
+        assert_eq!(lines.last(), Some(&"1 WATCHING"));
+    }
+
+    #[test]
+    fn summary_covers_time_kills_and_accuracy() {
+        let mut run = RunStats::new(Tick(0), 1);
+        run.ended = Some(Tick(60 * 95));
+        run.level = 3;
+        run.enemy_kills = 4;
+        run.tank_kills = 1;
+        run.shots_fired = 20;
+        run.shots_hit = 7;
+        assert_eq!(
+            summary_text(&run, 650),
+            "FLEW 1:35  —  REACHED LEVEL 3\nSCORE 650\n\n\
+             4 SHIPS   1 TANKS\nACCURACY 35%  (7 OF 20 SHOTS)"
+        );
+        run.shots_fired = 0;
+        assert!(summary_text(&run, 650).ends_with("NO SHOTS FIRED"));
+    }
+}
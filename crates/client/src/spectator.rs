@@ -0,0 +1,133 @@
+//! Spectator camera. A client that joined with `ClientMsg::Spectate` has no
+//! ship of its own, so the camera either follows one of the pilots (Tab or
+//! `]` for the next, `[` for the previous) or free-roams on the arrow keys
+//! (F toggles, and any arrow drops out of follow mode).
+
+use ggez::glam::Vec2;
+use ggez::input::keyboard::KeyCode;
+use protocol::{EntityState, Snapshot};
+use sim::{EntityKind, PlayerId};
+
+/// Free-camera pan speed (world units/s).
+const FREE_CAM_SPEED: f32 = 900.0;
+
+#[derive(Debug, Default)]
+pub struct SpectatorCam {
+    /// Pilot the camera follows; `None` while free-roaming.
+    target: Option<PlayerId>,
+    left_held: bool,
+    right_held: bool,
+    up_held: bool,
+    down_held: bool,
+}
+
+impl SpectatorCam {
+    pub fn target(&self) -> Option<PlayerId> {
+        self.target
+    }
+
+    pub fn handle_key_down(&mut self, code: KeyCode, snap: Option<&Snapshot>) {
+        match code {
+            KeyCode::Tab | KeyCode::BracketRight => self.cycle(snap, 1),
+            KeyCode::BracketLeft => self.cycle(snap, -1),
+            KeyCode::KeyF if self.target.is_some() => self.target = None,
+            KeyCode::KeyF => self.cycle(snap, 1),
+            _ => self.set_held(code, true),
+        }
+    }
+
+    pub fn handle_key_up(&mut self, code: KeyCode) {
+        self.set_held(code, false);
+    }
+
+    fn set_held(&mut self, code: KeyCode, held: bool) {
+        let flag = match code {
+            KeyCode::ArrowLeft => &mut self.left_held,
+            KeyCode::ArrowRight => &mut self.right_held,
+            KeyCode::ArrowUp => &mut self.up_held,
+            KeyCode::ArrowDown => &mut self.down_held,
+            _ => return,
+        };
+        *flag = held;
+        if held {
+            self.target = None;
+        }
+    }
+
+    /// Step to the next (`step > 0`) or previous pilot in id order,
+    /// wrapping around. Starts from the first pilot when free-roaming.
+    fn cycle(&mut self, snap: Option<&Snapshot>, step: isize) {
+        let Some(snap) = snap else { return };
+        let pilots: Vec<PlayerId> = snap.player_names.iter().map(|(p, _)| *p).collect();
+        if pilots.is_empty() {
+            self.target = None;
+            return;
+        }
+        let n = pilots.len() as isize;
+        let next = match self
+            .target
+            .and_then(|t| pilots.iter().position(|p| *p == t))
+        {
+            Some(i) => (i as isize + step).rem_euclid(n),
+            None if step > 0 => 0,
+            None => n - 1,
+        };
+        self.target = Some(pilots[next as usize]);
+    }
+
+    /// Start out following the first pilot, if there is one.
+    pub fn follow_first(&mut self, snap: &Snapshot) {
+        self.target = None;
+        self.cycle(Some(snap), 1);
+    }
+
+    /// Ship the camera should follow this frame. Moves on to the next
+    /// pilot when the followed one leaves the game. `None` while
+    /// free-roaming or while the pilot is waiting to respawn — the camera
+    /// holds still then.
+    pub fn followed<'a>(&mut self, snap: &'a Snapshot) -> Option<&'a EntityState> {
+        let gone = self
+            .target
+            .is_some_and(|t| !snap.player_names.iter().any(|(p, _)| *p == t));
+        if gone {
+            self.follow_first(snap);
+        }
+        let target = self.target?;
+        snap.entities.iter().find(|e| match e.kind {
+            EntityKind::Player { player_id } => player_id == target && e.alive,
+            _ => false,
+        })
+    }
+
+    /// Free-camera velocity from the held arrow keys (world units/s, Y-up).
+    pub fn pan_velocity(&self) -> Vec2 {
+        let axis = |neg: bool, pos: bool| match (neg, pos) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => 0.0,
+        };
+        Vec2::new(
+            axis(self.left_held, self.right_held),
+            axis(self.down_held, self.up_held),
+        ) * FREE_CAM_SPEED
+    }
+
+    /// Bottom-of-screen status line: who's being watched and the controls.
+    pub fn status_line(&self, snap: &Snapshot) -> String {
+        let watching = match self.target {
+            Some(t) => {
+                let name = snap
+                    .player_names
+                    .iter()
+                    .find(|(p, _)| *p == t)
+                    .map_or_else(|| format!("PILOT {}", t.0), |(_, n)| n.to_uppercase());
+                format!("WATCHING {name}")
+            }
+            None => "FREE CAMERA".to_string(),
+        };
+        format!(
+            "{watching}   [TAB] next pilot   [F] free camera   {} watching",
+            snap.spectators
+        )
+    }
+}
@@ -0,0 +1,204 @@
+//! Touch-screen controls: a floating virtual joystick on the left half of
+//! the screen and a fire button on the right, turned into analog
+//! `PlayerInput` the way a gamepad stick is. Pushing the stick sideways
+//! turns, pushing it up thrusts; both are proportional to how far it goes.
+//!
+//! ggez passes touch events on without a finger id, so fingers are told
+//! apart by position: a touch that starts on the left half grabs the stick
+//! (or fire, if the stick is already held), one on the right half holds
+//! fire, and moves and lifts go to whichever of the two is nearest.
+//!
+//! The controls switch on at the first touch, or at startup when the
+//! browser reports a touch screen. `render::touch` draws them.
+
+use ggez::glam::Vec2;
+use ggez::winit::event::TouchPhase;
+use sim::PlayerInput;
+
+use crate::input::past_dead_zone;
+
+/// Stick travel ignored around its centre, as a fraction of its radius.
+const STICK_DEAD_ZONE: f32 = 0.15;
+/// Stick radius as a fraction of the shorter screen side, and its bounds
+/// in pixels.
+const STICK_SIZE: f32 = 0.12;
+const MIN_STICK_RADIUS: f32 = 48.0;
+const MAX_STICK_RADIUS: f32 = 110.0;
+
+#[derive(Debug, Default)]
+pub struct TouchControls {
+    enabled: bool,
+    screen: Vec2,
+    /// Where the stick finger came down, which becomes the stick's
+    /// centre, and where it is now.
+    stick: Option<(Vec2, Vec2)>,
+    /// Where the fire finger is.
+    fire: Option<Vec2>,
+}
+
+enum Finger {
+    Stick,
+    Fire,
+}
+
+impl TouchControls {
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn set_screen_size(&mut self, screen: Vec2) {
+        self.screen = screen;
+    }
+
+    pub fn stick_radius(&self) -> f32 {
+        (self.screen.min_element() * STICK_SIZE).clamp(MIN_STICK_RADIUS, MAX_STICK_RADIUS)
+    }
+
+    /// The stick's centre and knob, in screen pixels. An idle stick rests
+    /// in the bottom-left corner.
+    pub fn stick(&self) -> (Vec2, Vec2) {
+        let r = self.stick_radius();
+        match self.stick {
+            Some((centre, pos)) => (centre, centre + (pos - centre).clamp_length_max(r)),
+            None => {
+                let home = Vec2::new(r * 1.8, self.screen.y - r * 1.8);
+                (home, home)
+            }
+        }
+    }
+
+    /// The fire button's centre and radius, in the bottom-right corner.
+    pub fn fire_button(&self) -> (Vec2, f32) {
+        let r = self.stick_radius();
+        (
+            Vec2::new(self.screen.x - r * 1.8, self.screen.y - r * 1.8),
+            r * 0.8,
+        )
+    }
+
+    pub fn firing(&self) -> bool {
+        self.fire.is_some()
+    }
+
+    /// A touch event at `pos`, in screen pixels. Switches the controls on.
+    pub fn handle(&mut self, phase: TouchPhase, pos: Vec2) {
+        self.enabled = true;
+        match phase {
+            TouchPhase::Started => {
+                if pos.x < self.screen.x * 0.5 && self.stick.is_none() {
+                    self.stick = Some((pos, pos));
+                } else if self.fire.is_none() {
+                    self.fire = Some(pos);
+                }
+            }
+            TouchPhase::Moved => match self.nearest(pos) {
+                Some(Finger::Stick) => {
+                    if let Some((_, at)) = &mut self.stick {
+                        *at = pos;
+                    }
+                }
+                Some(Finger::Fire) => self.fire = Some(pos),
+                None => {}
+            },
+            TouchPhase::Ended | TouchPhase::Cancelled => match self.nearest(pos) {
+                Some(Finger::Stick) => self.stick = None,
+                Some(Finger::Fire) => self.fire = None,
+                None => {}
+            },
+        }
+    }
+
+    /// Let go of both controls, e.g. when the screen changes under them.
+    pub fn release(&mut self) {
+        self.stick = None;
+        self.fire = None;
+    }
+
+    pub fn to_player_input(&self) -> PlayerInput {
+        let fire = self.firing();
+        let Some((centre, pos)) = self.stick else {
+            return PlayerInput {
+                fire,
+                ..PlayerInput::default()
+            };
+        };
+        let push = (pos - centre) / self.stick_radius();
+        PlayerInput {
+            xaxis: past_dead_zone(push.x.clamp(-1.0, 1.0), STICK_DEAD_ZONE),
+            // Screen Y grows downwards; pulling the stick down does nothing.
+            yaxis: past_dead_zone((-push.y).clamp(0.0, 1.0), STICK_DEAD_ZONE),
+            fire,
+        }
+    }
+
+    fn nearest(&self, pos: Vec2) -> Option<Finger> {
+        let stick = self.stick.map(|(_, at)| at.distance_squared(pos));
+        let fire = self.fire.map(|at| at.distance_squared(pos));
+        match (stick, fire) {
+            (Some(s), Some(f)) if f < s => Some(Finger::Fire),
+            (Some(_), _) => Some(Finger::Stick),
+            (None, Some(_)) => Some(Finger::Fire),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controls() -> TouchControls {
+        let mut touch = TouchControls::default();
+        touch.set_screen_size(Vec2::new(800.0, 400.0));
+        touch
+    }
+
+    #[test]
+    fn stick_steers_and_thrusts_in_proportion() {
+        let mut touch = controls();
+        let r = touch.stick_radius();
+        let centre = Vec2::new(100.0, 300.0);
+        touch.handle(TouchPhase::Started, centre);
+        assert_eq!(touch.to_player_input(), PlayerInput::default());
+
+        touch.handle(TouchPhase::Moved, centre + Vec2::new(r, -r * 0.575));
+        let input = touch.to_player_input();
+        assert_eq!(input.xaxis, 1.0);
+        assert!((input.yaxis - 0.5).abs() < 1e-3);
+
+        // Pulled down and well past the rim: no reverse, full left.
+        touch.handle(TouchPhase::Moved, centre + Vec2::new(-3.0 * r, r));
+        let input = touch.to_player_input();
+        assert_eq!((input.xaxis, input.yaxis), (-1.0, 0.0));
+        assert_eq!(
+            touch.stick().1,
+            centre + (Vec2::new(-3.0, 1.0) * r).clamp_length_max(r)
+        );
+    }
+
+    #[test]
+    fn two_fingers_are_told_apart_by_position() {
+        let mut touch = controls();
+        assert!(!touch.enabled());
+        touch.handle(TouchPhase::Started, Vec2::new(100.0, 300.0));
+        touch.handle(TouchPhase::Started, Vec2::new(700.0, 300.0));
+        assert!(touch.enabled());
+        assert!(touch.to_player_input().fire);
+
+        // The fire finger wanders; the stick stays put.
+        touch.handle(TouchPhase::Moved, Vec2::new(650.0, 280.0));
+        assert_eq!(touch.to_player_input().xaxis, 0.0);
+
+        touch.handle(TouchPhase::Ended, Vec2::new(650.0, 280.0));
+        assert!(!touch.firing());
+        assert!(touch.stick.is_some());
+
+        // A second finger on the left while steering fires.
+        touch.handle(TouchPhase::Started, Vec2::new(300.0, 100.0));
+        assert!(touch.firing());
+    }
+}
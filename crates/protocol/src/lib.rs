@@ -7,16 +7,26 @@ use serde::{Deserialize, Serialize};
 use sim::entity::{EntityId, EntityKind, PlayerId, Tick};
 use sim::terrain::TerrainBand;
 use sim::util::WireVec2;
-use sim::{GameEvent, PlayerInput};
+use sim::{GameEvent, ObjectiveStatus, PilotStats, PlayerInput, ShipClass};
 
 /// Default WebSocket address the server listens on and the client connects
 /// to. Override with `ICARUST_SERVER` or a CLI flag in the client.
 pub const DEFAULT_ADDR: &str = "127.0.0.1:4015";
 
+/// Longest chat line the server passes on, in characters. The client caps
+/// its prompt at the same length.
+pub const MAX_CHAT_CHARS: usize = 160;
+
+/// Longest pilot name the server accepts, in characters. Longer names in
+/// `Hello`, `Resume` or `Spectate` are cut short.
+pub const MAX_NAME_CHARS: usize = 16;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClientMsg {
     Hello {
         name: String,
+        /// Loadout to spawn with.
+        ship_class: ShipClass,
     },
     Input {
         tick: Tick,
@@ -25,6 +35,91 @@ pub enum ClientMsg {
     Bye,
     /// Ask the server to put the player back in the world after dying.
     Respawn,
+    /// Switch loadout. Applies on the next respawn, or immediately if the
+    /// ship is still waiting at spawn.
+    SelectShip {
+        ship_class: ShipClass,
+    },
+    /// Ask for the high-score table. Answered with `ServerMsg::Leaderboard`
+    /// to this client only.
+    RequestLeaderboard,
+    /// Sent instead of `Hello` to watch without flying. The server answers
+    /// with the usual `Welcome` and broadcasts, but never spawns a ship for
+    /// the connection and ignores its `Input`, `Respawn` and `SelectShip`.
+    Spectate {
+        name: String,
+    },
+    /// Sent instead of `Hello` when reconnecting after a dropped socket.
+    /// `token` is the `resume_token` from the last `Welcome`; if the server
+    /// is still holding that pilot it hands the same `PlayerId`, ship and
+    /// score back. An unknown or expired token joins fresh, as `Hello` would.
+    Resume {
+        token: u64,
+        name: String,
+        ship_class: ShipClass,
+    },
+    /// Say something to everyone. The server cleans the line up, caps its
+    /// length and rate, and echoes it back to all connections, the sender
+    /// included, as `ServerMsg::Chat`.
+    Chat {
+        text: String,
+    },
+    /// Operator command. `token` must match the server's admin token; the
+    /// server answers with `ServerMsg::AdminReply` either way. May be sent
+    /// as the first message for a one-off command without joining.
+    Admin {
+        token: String,
+        command: AdminCommand,
+    },
+    /// Vote to pause the game (`true`), or take the vote back. The game
+    /// pauses while more than half the pilots flying want it to; an
+    /// operator pause holds whatever the vote. Spectators don't get a say.
+    VotePause {
+        pause: bool,
+    },
+}
+
+/// What an operator can do to a running server. Pilots are named as they
+/// appear on the scoreboard, ignoring case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AdminCommand {
+    Kick {
+        name: String,
+    },
+    /// Kick, and refuse the name on later joins until the server restarts.
+    Ban {
+        name: String,
+    },
+    SetLevel {
+        level: i32,
+    },
+    /// Drop `count` enemies or tanks (`kind`) around a world position.
+    Spawn {
+        kind: EntityKind,
+        count: u32,
+        pos: WireVec2,
+    },
+    /// Make a pilot invulnerable. `None` toggles.
+    God {
+        name: String,
+        on: Option<bool>,
+    },
+    /// Freeze the game loop; connections stay up and keep getting
+    /// snapshots.
+    Pause,
+    Resume,
+    /// Run the world faster or slower than real time; 1 is normal.
+    TimeScale {
+        scale: f32,
+    },
+    /// Seed for the world the next time it resets after a wipe.
+    SetSeed {
+        seed: u64,
+    },
+    /// Show `text` to everyone.
+    Broadcast {
+        text: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,12 +129,48 @@ pub enum ServerMsg {
         seed: u64,
         world_size: WireVec2,
         snapshot: Snapshot,
+        /// Secret to present in `ClientMsg::Resume` if the socket drops.
+        resume_token: u64,
     },
     Snapshot(Snapshot),
     Events {
         tick: Tick,
         events: Vec<GameEvent>,
     },
+    /// Top runs, best first. Reply to `ClientMsg::RequestLeaderboard`.
+    Leaderboard(Vec<LeaderboardEntry>),
+    /// Last message before the server closes a connection that broke its
+    /// limits (flooding, oversized or invalid messages). Don't reconnect.
+    Kicked { reason: String },
+    /// Answer to `ClientMsg::Admin`, to the sender only.
+    AdminReply { ok: bool, message: String },
+    /// Operator message for every connection.
+    Announcement { text: String },
+    /// A chat line. `from` is the sender's display name; `None` marks a
+    /// notice from the server itself (joins, leaves, a refused line).
+    Chat { from: Option<String>, text: String },
+    /// The server is going down in `seconds`. Scores are banked at that
+    /// point and every socket gets a close frame.
+    Shutdown { reason: String, seconds: u32 },
+}
+
+/// One finished run on the server's high-score table. A run lasts from
+/// spawn (or respawn) to death, or to leaving while still alive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    /// Points scored during this run only.
+    pub score: i32,
+    /// Highest world level reached during the run.
+    pub level: i32,
+    pub enemy_kills: u32,
+    pub tank_kills: u32,
+    /// Rival pilots downed in PvP. Defaulted so tables saved before PvP
+    /// still load.
+    #[serde(default)]
+    pub pilot_kills: u32,
+    /// Seconds from spawn to the end of the run.
+    pub duration_secs: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,7 +178,28 @@ pub struct Snapshot {
     pub tick: Tick,
     pub entities: Vec<EntityState>,
     pub score_by_player: Vec<(PlayerId, i32)>,
+    /// Display name for every pilot in `score_by_player`, bots included.
+    pub player_names: Vec<(PlayerId, String)>,
+    /// Shots, hits and kills per pilot, covering the same pilots as
+    /// `score_by_player`. The client's scoreboard and run summary read it.
+    pub pilot_stats: Vec<(PlayerId, PilotStats)>,
+    /// Pilots flown by the server's wingman AI rather than a connection.
+    pub bots: Vec<PlayerId>,
+    /// Team per pilot; empty when the world has no teams.
+    pub teams: Vec<(PlayerId, u8)>,
+    /// Session score per team.
+    pub team_scores: Vec<(u8, i32)>,
+    /// Current co-op objective round, if the server runs one.
+    pub objective: Option<ObjectiveStatus>,
+    /// Connections watching without a ship.
+    pub spectators: u32,
     pub level: i32,
+    /// The world is frozen: extrapolation, particles and timers should stop
+    /// until a snapshot says otherwise.
+    pub paused: bool,
+    /// How fast world time runs against real time. Below 1 during slow
+    /// motion; scale client-side motion and effects by it too.
+    pub time_scale: f32,
     /// Active terrain layout. Re-sent every snapshot so the client can
     /// pick up new terrain when levels eventually change it.
     pub terrain: Vec<TerrainBand>,
@@ -74,6 +226,18 @@ pub struct EntityState {
     /// True if a player entity is firing thrust this tick. Client uses
     /// this to draw exhaust flames behind the ship.
     pub thrusting: bool,
+    /// True while a player's wing is stalled (aerodynamic flight model
+    /// only). Client flashes a stall warning on the HUD.
+    pub stalled: bool,
+    /// Player loadout, so the client can pick the matching ship mesh.
+    /// Default (`Fighter`) on non-player entities.
+    pub ship_class: ShipClass,
+    /// Gun heat as a fraction of `sim::player::GUN_HEAT_MAX`. Players only.
+    pub gun_heat: f32,
+    /// True while the gun is locked out after overheating.
+    pub gun_overheated: bool,
+    /// Player's team, when the world has teams.
+    pub team: Option<u8>,
 }
 
 impl EntityState {
@@ -89,6 +253,11 @@ impl EntityState {
             hp: e.hp,
             max_hp: e.max_hp,
             thrusting: e.thrusting,
+            stalled: e.stalled,
+            ship_class: e.ship_class,
+            gun_heat: e.gun_heat / sim::player::GUN_HEAT_MAX,
+            gun_overheated: e.gun_lock > 0.0,
+            team: e.team,
         }
     }
 }
@@ -101,7 +270,9 @@ pub fn decode<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, postcard::Er
     postcard::from_bytes(bytes)
 }
 
-/// Build a `Snapshot` from a `sim::World`. Server convenience.
+/// Build a `Snapshot` from a `sim::World`. Server convenience. The world
+/// doesn't know about spectators, so `spectators` is left at 0 for the
+/// server to fill in.
 pub fn snapshot_from_world(world: &sim::World) -> Snapshot {
     let entities = world
         .entities()
@@ -109,12 +280,26 @@ pub fn snapshot_from_world(world: &sim::World) -> Snapshot {
         .map(EntityState::from_entity)
         .collect();
     let score_by_player = world.scores().iter().map(|(p, s)| (*p, *s)).collect();
+    let player_names = world
+        .player_names()
+        .iter()
+        .map(|(p, n)| (*p, n.clone()))
+        .collect();
     let terrain = world.terrain().to_vec();
     Snapshot {
         tick: world.tick_index(),
         entities,
         score_by_player,
+        player_names,
+        pilot_stats: world.stats().iter().map(|(p, s)| (*p, *s)).collect(),
+        bots: world.bots().collect(),
+        teams: world.teams().iter().map(|(p, t)| (*p, *t)).collect(),
+        team_scores: world.team_scores().into_iter().collect(),
+        objective: world.objective(),
+        spectators: 0,
         level: world.level(),
+        paused: world.paused(),
+        time_scale: world.time_scale(),
         terrain,
     }
 }
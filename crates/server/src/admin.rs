@@ -0,0 +1,107 @@
+//! Operator commands. Any connection can send `ClientMsg::Admin` with the
+//! server's admin token (`ServerConfig::admin_token`); the game loop
+//! applies the command and answers with `ServerMsg::AdminReply`.
+//! `icarust-admin` sends one from the command line, written in the text
+//! form [`parse`] reads:
+//!
+//! ```text
+//! kick <name>             ban <name>
+//! level <n>               seed <n>
+//! spawn enemy|tank <count> <x> <y>
+//! god <name> [on|off]     pause | resume
+//! speed <scale>           say <message>
+//! ```
+//!
+//! Names match ignoring case. A name more than one pilot is flying under
+//! is refused rather than guessed at.
+
+use protocol::AdminCommand;
+use sim::util::WireVec2;
+use sim::EntityKind;
+
+/// Most hostiles one `spawn` may drop.
+pub const MAX_SPAWN: u32 = 64;
+
+/// Parse one command line. The error is a usage hint.
+pub fn parse(line: &str) -> Result<AdminCommand, String> {
+    let line = line.trim();
+    let (verb, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    let name = || match rest {
+        "" => Err(format!("usage: {verb} <name>")),
+        name => Ok(name.to_string()),
+    };
+    match verb {
+        "kick" => Ok(AdminCommand::Kick { name: name()? }),
+        "ban" => Ok(AdminCommand::Ban { name: name()? }),
+        "level" => match args[..] {
+            [level] => Ok(AdminCommand::SetLevel {
+                level: number(level)?,
+            }),
+            _ => Err("usage: level <n>".into()),
+        },
+        "seed" => match args[..] {
+            [seed] => Ok(AdminCommand::SetSeed {
+                seed: number(seed)?,
+            }),
+            _ => Err("usage: seed <n>".into()),
+        },
+        "spawn" => {
+            let [kind, count, x, y] = args[..] else {
+                return Err("usage: spawn enemy|tank <count> <x> <y>".into());
+            };
+            let kind = match kind {
+                "enemy" | "enemies" => EntityKind::Enemy,
+                "tank" | "tanks" => EntityKind::Tank,
+                other => return Err(format!("can't spawn {other:?}; enemy or tank")),
+            };
+            let count = number(count)?;
+            if count == 0 || count > MAX_SPAWN {
+                return Err(format!("count must be 1..={MAX_SPAWN}"));
+            }
+            let pos = WireVec2 {
+                x: number(x)?,
+                y: number(y)?,
+            };
+            if !(pos.x.is_finite() && pos.y.is_finite()) {
+                return Err("position must be finite".into());
+            }
+            Ok(AdminCommand::Spawn { kind, count, pos })
+        }
+        "god" => {
+            // The switch is optional and names may contain spaces.
+            let (name, on) = match rest.rsplit_once(char::is_whitespace) {
+                Some((name, "on")) => (name.trim_end(), Some(true)),
+                Some((name, "off")) => (name.trim_end(), Some(false)),
+                _ => (rest, None),
+            };
+            if name.is_empty() {
+                return Err("usage: god <name> [on|off]".into());
+            }
+            Ok(AdminCommand::God {
+                name: name.to_string(),
+                on,
+            })
+        }
+        "pause" => Ok(AdminCommand::Pause),
+        "resume" => Ok(AdminCommand::Resume),
+        "speed" => match args[..] {
+            [scale] => match number(scale)? {
+                scale if f32::is_finite(scale) => Ok(AdminCommand::TimeScale { scale }),
+                _ => Err(format!("{scale:?} is not a finite speed")),
+            },
+            _ => Err("usage: speed <scale>".into()),
+        },
+        "say" if !rest.is_empty() => Ok(AdminCommand::Broadcast {
+            text: rest.to_string(),
+        }),
+        "say" => Err("usage: say <message>".into()),
+        "" => Err("empty command".into()),
+        other => Err(format!("unknown command {other:?}")),
+    }
+}
+
+fn number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("{s:?} is not a number"))
+}
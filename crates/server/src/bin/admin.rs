@@ -0,0 +1,44 @@
+//! `icarust-admin <command…>`: send one admin command to a running server
+//! and print its answer. The server comes from `ICARUST_SERVER` and the
+//! token from `ICARUST_ADMIN_TOKEN`; see `server::admin` for the commands.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, ServerMsg};
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let line = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    let command = server::admin::parse(&line).map_err(|e| anyhow!(e))?;
+    let token = std::env::var("ICARUST_ADMIN_TOKEN").context("ICARUST_ADMIN_TOKEN is not set")?;
+    let url = std::env::var("ICARUST_SERVER")
+        .unwrap_or_else(|_| format!("ws://{}", protocol::DEFAULT_ADDR));
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url)
+        .await
+        .with_context(|| format!("connecting to {url}"))?;
+    let msg = ClientMsg::Admin { token, command };
+    ws.send(Message::Binary(protocol::encode(&msg))).await?;
+    let reply = timeout(Duration::from_secs(5), async {
+        while let Some(frame) = ws.next().await {
+            if let Message::Binary(b) = frame? {
+                if let ServerMsg::AdminReply { ok, message } = protocol::decode(&b)? {
+                    return Ok((ok, message));
+                }
+            }
+        }
+        bail!("server closed without answering")
+    })
+    .await
+    .context("no answer from the server")??;
+
+    match reply {
+        (true, message) => println!("{message}"),
+        (false, message) => bail!("{message}"),
+    }
+    Ok(())
+}
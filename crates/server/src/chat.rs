@@ -0,0 +1,57 @@
+//! Cleaning up chat lines before they go out to everyone. Deliberately
+//! simple: strip the characters that can break or spoof other clients'
+//! text rendering, mask a short list of slurs and swears, and cap the
+//! length.
+
+/// Word stems masked wherever a word starts with one, so inflections are
+/// caught too ("fucking", "shitty").
+const MASKED_STEMS: [&str; 7] = [
+    "fuck", "shit", "cunt", "bitch", "asshole", "bastard", "wank",
+];
+
+/// Sanitise `text` for broadcast. `None` if nothing printable is left.
+///
+/// - Control characters, zero-width and bidi-override characters, and
+///   combining marks (the raw material of "zalgo" text) are dropped.
+/// - Runs of whitespace collapse to one space.
+/// - Words starting with a masked stem become asterisks.
+/// - The result is cut to `max_chars` characters.
+pub fn sanitize(text: &str, max_chars: usize) -> Option<String> {
+    let cleaned: String = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .filter(|&c| !is_stripped(c))
+        .collect();
+    let line = cleaned
+        .split_whitespace()
+        .map(mask_word)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let line: String = line.chars().take(max_chars).collect();
+    let line = line.trim_end();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+fn is_stripped(c: char) -> bool {
+    c.is_control()
+        || matches!(c,
+            // Zero-width spaces and joiners, word joiner, BOM.
+            '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}'
+            // Bidi embeddings, overrides and isolates.
+            | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+            // Combining marks.
+            | '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}')
+}
+
+/// Asterisks for a word that starts with a masked stem, ignoring case and
+/// any leading punctuation; the word itself otherwise.
+fn mask_word(word: &str) -> String {
+    let letters = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let lower = letters.to_lowercase();
+    if MASKED_STEMS.iter().any(|stem| lower.starts_with(stem)) {
+        "*".repeat(word.chars().count())
+    } else {
+        word.to_string()
+    }
+}
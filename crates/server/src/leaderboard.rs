@@ -0,0 +1,168 @@
+//! Persistent high-score table. Finished runs are kept sorted best-first
+//! and written to a JSON file after every change, so the table survives
+//! server restarts. The writes happen on a background task, off the game
+//! loop. With no path configured the table lives in memory only.
+//!
+//! [`Run`] tracks one life from spawn to death; the game loop turns it into
+//! a [`LeaderboardEntry`] when it ends.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use protocol::LeaderboardEntry;
+use sim::{PlayerId, World};
+use tokio::sync::mpsc;
+use tokio::task::{self, JoinHandle};
+use tracing::warn;
+
+/// Entries returned to a `ClientMsg::RequestLeaderboard`.
+pub const LEADERBOARD_SIZE: usize = 10;
+/// Entries kept on disk. Anything below this rank can never make the
+/// served table again, so there's no point keeping it.
+const MAX_STORED: usize = 100;
+
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+    /// `None` for a table that's never written anywhere.
+    saver: Option<Saver>,
+}
+
+/// The task writing a table to disk, and the channel that feeds it.
+struct Saver {
+    tables: mpsc::UnboundedSender<Vec<LeaderboardEntry>>,
+    task: JoinHandle<()>,
+}
+
+impl Leaderboard {
+    /// A table that's never written anywhere.
+    pub fn in_memory() -> Self {
+        Leaderboard {
+            entries: Vec::new(),
+            saver: None,
+        }
+    }
+
+    /// Load the table at `path` and start the task that saves it. A
+    /// missing file is an empty table; it's created on the first `record`.
+    /// Must be called from within a Tokio runtime.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let entries = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("parsing leaderboard {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading leaderboard {}", path.display()))
+            }
+        };
+        let (tables, queued) = mpsc::unbounded_channel();
+        let task = tokio::spawn(write_loop(path, queued));
+        let mut board = Leaderboard {
+            entries,
+            saver: Some(Saver { tables, task }),
+        };
+        board.sort();
+        Ok(board)
+    }
+
+    /// Insert a finished run and queue the table for saving. Write
+    /// failures are logged rather than returned — losing a high score
+    /// shouldn't take the game down.
+    pub fn record(&mut self, entry: LeaderboardEntry) {
+        self.entries.push(entry);
+        self.sort();
+        self.entries.truncate(MAX_STORED);
+        if let Some(saver) = &self.saver {
+            // The writer only stops once `tables` is dropped.
+            let _ = saver.tables.send(self.entries.clone());
+        }
+    }
+
+    /// Wait for every queued save to land. Call it before relying on the
+    /// file, e.g. on shutdown or before reopening it.
+    pub async fn close(self) {
+        if let Some(Saver { tables, task }) = self.saver {
+            drop(tables);
+            let _ = task.await;
+        }
+    }
+
+    /// Best `n` runs, best first.
+    pub fn top(&self, n: usize) -> Vec<LeaderboardEntry> {
+        self.entries.iter().take(n).cloned().collect()
+    }
+
+    /// Highest score first; ties go to the faster run.
+    fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.duration_secs.total_cmp(&b.duration_secs))
+        });
+    }
+}
+
+/// Save each table sent down `tables`, skipping to the newest if several
+/// queued up while the disk was busy. The writes run on the blocking pool.
+async fn write_loop(path: PathBuf, mut tables: mpsc::UnboundedReceiver<Vec<LeaderboardEntry>>) {
+    while let Some(mut entries) = tables.recv().await {
+        while let Ok(newer) = tables.try_recv() {
+            entries = newer;
+        }
+        let path = path.clone();
+        match task::spawn_blocking(move || save(&path, &entries)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("failed to save leaderboard: {e:#}"),
+            Err(e) => warn!("leaderboard save panicked: {e}"),
+        }
+    }
+}
+
+/// Write to a sibling temp file and rename over the real one so a crash
+/// mid-write can't leave a truncated table behind.
+fn save(path: &Path, entries: &[LeaderboardEntry]) -> Result<()> {
+    let json = serde_json::to_vec_pretty(entries)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).with_context(|| format!("writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("replacing {}", path.display()))?;
+    Ok(())
+}
+
+/// One life in progress. The world keeps the run's own stats (see
+/// `sim::RunStats`); this adds what the table needs on top.
+#[derive(Debug, Clone)]
+pub struct Run {
+    name: String,
+    /// Session score at spawn. Scores carry over between lives, so the
+    /// run's own score is the difference at the end.
+    start_score: i32,
+}
+
+impl Run {
+    pub fn start(name: String, world: &World, player_id: PlayerId) -> Self {
+        Run {
+            name,
+            start_score: world.score(player_id),
+        }
+    }
+
+    /// Close the run out. `None` for a run that never scored — a pilot who
+    /// sat on the menu and left doesn't belong on the table.
+    pub fn finish(self, world: &World, player_id: PlayerId) -> Option<LeaderboardEntry> {
+        let score = world.score(player_id) - self.start_score;
+        let run = world.pilot_stats(player_id)?.run;
+        if score <= 0 {
+            return None;
+        }
+        Some(LeaderboardEntry {
+            name: self.name,
+            score,
+            level: run.level,
+            enemy_kills: run.enemy_kills,
+            tank_kills: run.tank_kills,
+            pilot_kills: run.pilot_kills,
+            duration_secs: run.duration_secs(world.tick_index()),
+        })
+    }
+}
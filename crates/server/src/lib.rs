@@ -2,40 +2,173 @@
 //! WebSocket connections, broadcasts snapshots at 20 Hz and game events as
 //! they fire.
 
-use std::net::SocketAddr;
+pub mod admin;
+pub mod chat;
+pub mod leaderboard;
+pub mod limits;
+pub mod metrics;
+pub mod outbox;
+pub mod shutdown;
+
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{anyhow, Result};
+use futures_util::stream::SplitStream;
 use futures_util::{SinkExt, StreamExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinSet;
 use tokio::time::{self, Duration, MissedTickBehavior};
-use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::WebSocketStream;
 use tracing::{info, warn};
 
-use protocol::{snapshot_from_world, ClientMsg, ServerMsg, Snapshot};
-use sim::{GameEvent, PlayerId, PlayerInput, PlayerInputs, Vec2, World, WorldConfig, TICK_DT};
+use leaderboard::{Leaderboard, Run, LEADERBOARD_SIZE};
+use limits::{AdminThrottle, Guard, Kick, Limits, Verdict};
+use metrics::Metrics;
+use outbox::Outbox;
+use shutdown::Shutdown;
+use protocol::{
+    snapshot_from_world, AdminCommand, ClientMsg, LeaderboardEntry, ServerMsg, Snapshot,
+    MAX_NAME_CHARS,
+};
+use sim::{
+    GameEvent, PlayerId, PlayerInput, PlayerInputs, ShipClass, Tick, Vec2, World, WorldConfig,
+    TICK_DT,
+};
 
 /// Send a snapshot every Nth tick. 60 Hz / 3 = 20 Hz.
 const SNAPSHOT_EVERY: u64 = 3;
 /// Broadcast channel capacity per receiver. Tuned so a brief stall on one
 /// client does not lag the rest.
 const BROADCAST_CAP: usize = 256;
+/// How long a pilot whose socket dropped stays in the world waiting for
+/// their client to come back with `ClientMsg::Resume`.
+pub const RESUME_GRACE: Duration = Duration::from_secs(30);
+/// Admin reply to a command with the wrong token.
+const BAD_ADMIN_TOKEN: &str = "bad admin token";
+/// Admin reply to any command from an address locked out by
+/// `AdminThrottle`.
+const ADMIN_LOCKED_OUT: &str = "too many bad admin tokens, try again later";
+/// After the game loop stops, how long connections get to flush and close
+/// before they're cut off.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Knobs for one server instance. `Default` is the stock game.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Handed to `World::new` when the game loop starts.
+    pub world: WorldConfig,
+    /// JSON file the high-score table is loaded from and saved to. `None`
+    /// keeps the table in memory for the life of the process.
+    pub leaderboard_path: Option<PathBuf>,
+    /// Keep at least this many pilots in the world by topping up with
+    /// wingman bots. Each human who joins bumps one bot; each who leaves
+    /// brings one back. `0` disables bots.
+    pub bot_fill: usize,
+    /// Keep a dropped pilot's ship and score this long for a resume.
+    pub resume_grace: Duration,
+    /// Per-connection frame size, message rate, validity and send-queue
+    /// limits, and the per-address admin lockout.
+    pub limits: Limits,
+    /// Counters the server updates as it runs. Keep a clone of the `Arc`
+    /// to read them.
+    pub metrics: Arc<Metrics>,
+    /// Secret `ClientMsg::Admin` must carry. `None` refuses every admin
+    /// command.
+    pub admin_token: Option<String>,
+    /// Trigger a clone of this to shut the server down gracefully.
+    pub shutdown: Shutdown,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            world: WorldConfig::default(),
+            leaderboard_path: None,
+            bot_fill: 0,
+            resume_grace: RESUME_GRACE,
+            limits: Limits::default(),
+            metrics: Arc::default(),
+            admin_token: None,
+            shutdown: Shutdown::default(),
+        }
+    }
+}
 
 #[derive(Debug)]
 enum Command {
+    /// `Hello`, or `Resume` with its token. `player_id` is the fresh id the
+    /// connection was given; a successful resume answers with the held
+    /// pilot's id instead. A banned name is refused. `kick` ends the
+    /// connection if an operator kicks the pilot.
     Join {
         player_id: PlayerId,
-        reply: oneshot::Sender<JoinAck>,
+        name: String,
+        ship_class: ShipClass,
+        resume: Option<u64>,
+        kick: oneshot::Sender<Kick>,
+        reply: oneshot::Sender<Result<JoinAck, Kick>>,
+    },
+    /// Watch without a ship. The id only tracks the connection; it never
+    /// reaches the world.
+    Spectate {
+        player_id: PlayerId,
+        reply: oneshot::Sender<Result<JoinAck, Kick>>,
+    },
+    /// The connection serving `session` closed. `hold` keeps the pilot in
+    /// the world for `resume_grace` (the socket dropped); otherwise they
+    /// leave now (they said `Bye`).
+    Disconnect {
+        player_id: PlayerId,
+        session: u64,
+        hold: bool,
     },
-    Leave(PlayerId),
     Input(PlayerId, PlayerInput),
     Respawn(PlayerId),
+    SelectShip(PlayerId, ShipClass),
+    Leaderboard(oneshot::Sender<Vec<LeaderboardEntry>>),
+    /// Chat line from the connection that joined as `from`, rate-limited
+    /// but not yet cleaned up.
+    Chat {
+        from: String,
+        text: String,
+    },
+    /// Operator command, applied only if `token` is the admin token and
+    /// `peer` isn't locked out for guessing. The reply is what to tell the
+    /// operator.
+    Admin {
+        peer: IpAddr,
+        token: String,
+        command: AdminCommand,
+        reply: oneshot::Sender<Result<String, String>>,
+    },
+    /// A pilot voting to pause, or taking the vote back.
+    VotePause(PlayerId, bool),
+    /// Warn everyone, play on for `grace`, then bank every run and stop.
+    Shutdown {
+        reason: String,
+        grace: Duration,
+    },
 }
 
 #[derive(Debug, Clone)]
 struct JoinAck {
+    /// Pilot the connection now speaks for.
+    player_id: PlayerId,
+    /// Bumped every time a pilot gets a new connection, so a stale
+    /// connection closing late can't drop a resumed pilot.
+    session: u64,
+    /// Spectators can't resume (they just watch again), so theirs is 0.
+    resume_token: u64,
     snapshot: Snapshot,
     seed: u64,
     world_size: Vec2,
@@ -44,15 +177,44 @@ struct JoinAck {
 /// Run the server using a pre-bound listener. Useful from tests that bind
 /// to port 0 to grab a free port.
 pub async fn run_with_listener(listener: TcpListener) -> Result<()> {
+    run_with_config(listener, ServerConfig::default()).await
+}
+
+/// Same as [`run_with_listener`] but with explicit [`ServerConfig`].
+/// Returns once `config.shutdown` is triggered and the server has wound
+/// down.
+pub async fn run_with_config(listener: TcpListener, config: ServerConfig) -> Result<()> {
     let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<Command>();
     let (out_tx, _) = broadcast::channel::<Arc<ServerMsg>>(BROADCAST_CAP);
+    let leaderboard = match &config.leaderboard_path {
+        Some(path) => Leaderboard::open(path.clone())?,
+        None => Leaderboard::in_memory(),
+    };
 
-    tokio::spawn(game_loop(cmd_rx, out_tx.clone()));
-
+    // Shared with the game loop, which hands out bot ids from the same
+    // sequence so they never collide with a connection's.
     let next_pid = Arc::new(AtomicU32::new(1));
+    let limits = config.limits;
+    let metrics = config.metrics.clone();
+    let shutdown = config.shutdown.clone();
 
-    loop {
-        let (stream, peer) = match listener.accept().await {
+    let game = tokio::spawn(game_loop(
+        config,
+        leaderboard,
+        next_pid.clone(),
+        cmd_rx,
+        out_tx.clone(),
+    ));
+
+    let mut connections = JoinSet::new();
+    let request = loop {
+        let accepted = tokio::select! {
+            accepted = listener.accept() => accepted,
+            // Reap finished connections so the set doesn't grow forever.
+            Some(_) = connections.join_next() => continue,
+            request = shutdown.requested() => break request,
+        };
+        let (stream, peer) = match accepted {
             Ok(p) => p,
             Err(e) => {
                 warn!("accept failed: {e}");
@@ -62,14 +224,35 @@ pub async fn run_with_listener(listener: TcpListener) -> Result<()> {
         let pid = PlayerId(next_pid.fetch_add(1, Ordering::Relaxed));
         let cmd_tx = cmd_tx.clone();
         let out_rx = out_tx.subscribe();
-        tokio::spawn(async move {
-            let cmd_for_leave = cmd_tx.clone();
-            if let Err(e) = serve_connection(stream, peer.to_string(), pid, cmd_tx, out_rx).await {
+        let outbox = Arc::new(Outbox::new(&limits, metrics.clone()));
+        connections.spawn(async move {
+            let served =
+                serve_connection(stream, peer, pid, limits, outbox, cmd_tx, out_rx);
+            if let Err(e) = served.await {
                 warn!(?pid, "connection ended: {e:#}");
             }
-            let _ = cmd_for_leave.send(Command::Leave(pid));
         });
+    };
+
+    info!(reason = %request.reason, grace = ?request.grace, "shutting down");
+    drop(listener);
+    let _ = cmd_tx.send(Command::Shutdown {
+        reason: request.reason,
+        grace: request.grace,
+    });
+    // Once the game loop is gone, every connection sees its command channel
+    // close, flushes its queue and sends a close frame.
+    drop((cmd_tx, out_tx));
+    game.await?;
+    let drained = time::timeout(CLOSE_TIMEOUT, async {
+        while connections.join_next().await.is_some() {}
+    });
+    if drained.await.is_err() {
+        warn!(left = connections.len(), "connections didn't close in time, cutting them off");
+        connections.shutdown().await;
     }
+    info!("server stopped");
+    Ok(())
 }
 
 /// Bind on `addr` and run forever.
@@ -81,15 +264,23 @@ pub async fn run(addr: SocketAddr) -> Result<()> {
 
 async fn serve_connection(
     stream: TcpStream,
-    peer: String,
+    peer: SocketAddr,
     pid: PlayerId,
+    limits: Limits,
+    outbox: Arc<Outbox>,
     cmd_tx: mpsc::UnboundedSender<Command>,
     mut out_rx: broadcast::Receiver<Arc<ServerMsg>>,
 ) -> Result<()> {
-    let ws = tokio_tungstenite::accept_async(stream).await?;
+    // Tungstenite refuses anything bigger before buffering it all.
+    let ws_config = WebSocketConfig {
+        max_message_size: Some(limits.max_frame_bytes),
+        max_frame_size: Some(limits.max_frame_bytes),
+        ..Default::default()
+    };
+    let ws = tokio_tungstenite::accept_async_with_config(stream, Some(ws_config)).await?;
     let (mut ws_tx, mut ws_rx) = ws.split();
 
-    // First message must be Hello.
+    // First message must be Hello, or Spectate to watch without a ship.
     let hello_frame = ws_rx
         .next()
         .await
@@ -100,170 +291,876 @@ async fn serve_connection(
         Message::Close(_) => return Ok(()),
         other => return Err(anyhow!("unexpected first frame: {other:?}")),
     };
-    let name = match protocol::decode::<ClientMsg>(&bytes)? {
-        ClientMsg::Hello { name } => name,
-        other => return Err(anyhow!("first message must be Hello, got {other:?}")),
-    };
-
     let (reply_tx, reply_rx) = oneshot::channel();
-    cmd_tx
-        .send(Command::Join {
-            player_id: pid,
-            reply: reply_tx,
-        })
-        .map_err(|_| anyhow!("game loop dropped"))?;
-    let ack = reply_rx.await?;
-
-    info!(?pid, %peer, %name, "player joined");
+    let (kick_tx, kick_rx) = oneshot::channel();
+    // The name chat lines from this connection go out under.
+    let (spectating, name) = match protocol::decode::<ClientMsg>(&bytes)? {
+        ClientMsg::Hello { name, ship_class } => {
+            let name = pilot_name(&name);
+            info!(?pid, %peer, %name, ?ship_class, "player joined");
+            cmd_tx
+                .send(Command::Join {
+                    player_id: pid,
+                    name: name.clone(),
+                    ship_class,
+                    resume: None,
+                    kick: kick_tx,
+                    reply: reply_tx,
+                })
+                .map_err(|_| anyhow!("game loop dropped"))?;
+            (false, name)
+        }
+        ClientMsg::Resume {
+            token,
+            name,
+            ship_class,
+        } => {
+            let name = pilot_name(&name);
+            info!(?pid, %peer, %name, "player resuming");
+            cmd_tx
+                .send(Command::Join {
+                    player_id: pid,
+                    name: name.clone(),
+                    ship_class,
+                    resume: Some(token),
+                    kick: kick_tx,
+                    reply: reply_tx,
+                })
+                .map_err(|_| anyhow!("game loop dropped"))?;
+            (false, name)
+        }
+        ClientMsg::Spectate { name } => {
+            let name = pilot_name(&name);
+            info!(?pid, %peer, %name, "spectator joined");
+            cmd_tx
+                .send(Command::Spectate {
+                    player_id: pid,
+                    reply: reply_tx,
+                })
+                .map_err(|_| anyhow!("game loop dropped"))?;
+            (true, name)
+        }
+        // A one-off command from `icarust-admin`: answer and hang up.
+        ClientMsg::Admin { token, command } => {
+            info!(%peer, ?command, "admin command");
+            let reply = admin(&cmd_tx, peer.ip(), token, command).await?;
+            ws_tx.send(Message::Binary(protocol::encode(&reply))).await?;
+            let _ = ws_tx.close().await;
+            return Ok(());
+        }
+        other => {
+            return Err(anyhow!(
+                "first message must be Hello or Spectate, got {other:?}"
+            ))
+        }
+    };
+    let ack = match reply_rx.await? {
+        Ok(ack) => ack,
+        Err(kick) => {
+            warn!(?pid, %peer, %kick, "join refused");
+            let refusal = ServerMsg::Kicked {
+                reason: kick.to_string(),
+            };
+            ws_tx.send(Message::Binary(protocol::encode(&refusal))).await?;
+            let _ = ws_tx.close().await;
+            return Ok(());
+        }
+    };
+    let pid = ack.player_id;
+    let session = ack.session;
 
     let welcome = ServerMsg::Welcome {
         player_id: pid,
         seed: ack.seed,
         world_size: ack.world_size.into(),
         snapshot: ack.snapshot,
+        resume_token: ack.resume_token,
     };
-    ws_tx.send(Message::Binary(protocol::encode(&welcome))).await?;
+    if let Err(e) = ws_tx.send(Message::Binary(protocol::encode(&welcome))).await {
+        let _ = cmd_tx.send(Command::Disconnect {
+            player_id: pid,
+            session,
+            hold: true,
+        });
+        return Err(e.into());
+    }
 
-    // Writer task — drains incoming broadcasts and writes to the socket.
-    let (write_tx, mut write_rx) = mpsc::unbounded_channel::<Arc<ServerMsg>>();
+    // Writer task — drains the outbox and writes to the socket.
     let write_pid = pid;
+    let write_outbox = outbox.clone();
     let writer = tokio::spawn(async move {
-        while let Some(msg) = write_rx.recv().await {
+        while let Some(msg) = write_outbox.next().await {
             let bytes = protocol::encode(&*msg);
+            let len = bytes.len();
             if let Err(e) = ws_tx.send(Message::Binary(bytes)).await {
                 warn!(?write_pid, "ws write failed: {e}");
                 break;
             }
+            write_outbox.written(&msg, len);
         }
         let _ = ws_tx.close().await;
     });
 
-    // Bridge broadcast → writer mpsc. Lets us drop subscribers cleanly when
-    // the writer exits (no orphaned broadcast slots).
-    let bridge_tx = write_tx.clone();
+    // Bridge broadcast → outbox. The outbox never blocks, so the bridge
+    // keeps up with the broadcast channel however slow the client reads.
+    let bridge_outbox = outbox.clone();
     let bridge = tokio::spawn(async move {
         loop {
             match out_rx.recv().await {
-                Ok(m) => {
-                    if bridge_tx.send(m).is_err() {
-                        break;
-                    }
-                }
+                Ok(m) => bridge_outbox.push(m, Instant::now()),
                 Err(broadcast::error::RecvError::Lagged(n)) => {
                     warn!(?pid, "broadcast lagged by {n}");
+                    bridge_outbox.lagged(n);
                 }
                 Err(broadcast::error::RecvError::Closed) => break,
             }
         }
     });
 
-    // Reader loop.
+    let kicked = async {
+        match kick_rx.await {
+            Ok(kick) => kick,
+            // Spectators are never handed a kicker.
+            Err(_) => std::future::pending().await,
+        }
+    };
+    let mut guard = Guard::new(limits, Instant::now());
+    let caller = Caller {
+        pid,
+        ip: peer.ip(),
+        name: &name,
+        spectating,
+    };
+    let reading = read_loop(caller, &mut guard, &mut ws_rx, &cmd_tx, &outbox);
+    let ended = tokio::select! {
+        ended = reading => ended,
+        _ = outbox.stalled() => Ok(Ended::Stalled),
+        kick = kicked => Ok(Ended::Kicked(kick)),
+        _ = cmd_tx.closed() => Ok(Ended::ServerClosed),
+    };
+    match ended {
+        Ok(Ended::Kicked(kick)) => {
+            warn!(?pid, %kick, "kicking client");
+            let reason = kick.to_string();
+            outbox.push(Arc::new(ServerMsg::Kicked { reason }), Instant::now());
+        }
+        Ok(Ended::Stalled) => warn!(?pid, queued = outbox.len(), "client stalled, dropping"),
+        _ => {}
+    }
+
+    if let Ok(Ended::ServerClosed) = ended {
+        // The broadcast channel is closing too; let the bridge hand over
+        // the last messages before the outbox stops taking them.
+        let _ = bridge.await;
+    } else {
+        bridge.abort();
+    }
+    outbox.close();
+    if let Ok(Ended::Stalled) = ended {
+        // The socket isn't draining; a close frame would just queue behind
+        // everything else.
+        writer.abort();
+    } else {
+        let _ = writer.await;
+    }
+    let _ = cmd_tx.send(Command::Disconnect {
+        player_id: pid,
+        session,
+        hold: matches!(ended, Ok(Ended::Dropped | Ended::Stalled) | Err(_)),
+    });
+    ended.map(|_| ())
+}
+
+/// A name from `Hello`, `Resume` or `Spectate`, cleaned up like a chat
+/// line (everyone sees it too) and cut to `MAX_NAME_CHARS`. A name with
+/// nothing printable left becomes the client's default.
+fn pilot_name(name: &str) -> String {
+    chat::sanitize(name, MAX_NAME_CHARS).unwrap_or_else(|| "pilot".into())
+}
+
+/// What two names have in common when they differ only in case. Bans and
+/// operator lookups both compare through it.
+fn name_key(name: &str) -> String {
+    name.to_lowercase()
+}
+
+/// Who a connection speaks for, as its read loop needs to know.
+struct Caller<'a> {
+    pid: PlayerId,
+    /// Where the connection comes from, for `AdminThrottle`.
+    ip: IpAddr,
+    /// What chat lines go out under.
+    name: &'a str,
+    spectating: bool,
+}
+
+/// How a connection's read loop finished.
+#[derive(Debug)]
+enum Ended {
+    /// `Bye` or a close frame: the client left on purpose.
+    Bye,
+    /// The socket just went away; hold the pilot for a resume.
+    Dropped,
+    /// The client stopped reading and its outbox stayed full. Held like a
+    /// drop, since the client may well come back.
+    Stalled,
+    /// The client broke its limits, or an operator kicked it, and it leaves
+    /// for good.
+    Kicked(Kick),
+    /// The game loop stopped: the server is shutting down.
+    ServerClosed,
+}
+
+async fn read_loop(
+    caller: Caller<'_>,
+    guard: &mut Guard,
+    ws_rx: &mut SplitStream<WebSocketStream<TcpStream>>,
+    cmd_tx: &mpsc::UnboundedSender<Command>,
+    outbox: &Outbox,
+) -> Result<Ended> {
+    let Caller {
+        pid,
+        ip,
+        name,
+        spectating,
+    } = caller;
     while let Some(frame) = ws_rx.next().await {
-        let frame = frame?;
-        match frame {
-            Message::Binary(b) => {
-                let cm: ClientMsg = match protocol::decode(&b) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        warn!(?pid, "malformed client message: {e}");
-                        continue;
+        let frame = match frame {
+            Ok(f) => f,
+            Err(tungstenite::Error::Capacity(_)) => return Ok(Ended::Kicked(Kick::FrameTooLarge)),
+            Err(e) => return Err(e.into()),
+        };
+        // A close frame is a deliberate goodbye too.
+        if let Message::Close(_) = frame {
+            return Ok(Ended::Bye);
+        }
+        match guard.admit(Instant::now()) {
+            Verdict::Accept => {}
+            Verdict::Drop => continue,
+            Verdict::Kick(kick) => return Ok(Ended::Kicked(kick)),
+        }
+        let Message::Binary(b) = frame else {
+            continue;
+        };
+        let cm: ClientMsg = match protocol::decode(&b) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(?pid, "malformed client message: {e}");
+                match guard.invalid() {
+                    Some(kick) => return Ok(Ended::Kicked(kick)),
+                    None => continue,
+                }
+            }
+        };
+        match cm {
+            // Spectators have no ship to steer or respawn.
+            ClientMsg::Input { .. }
+            | ClientMsg::Respawn
+            | ClientMsg::SelectShip { .. }
+                if spectating => {}
+            ClientMsg::Input { input, .. } => match limits::sanitize_input(input) {
+                Some(input) => {
+                    let _ = cmd_tx.send(Command::Input(pid, input));
+                }
+                None => {
+                    warn!(?pid, ?input, "rejected input");
+                    if let Some(kick) = guard.invalid() {
+                        return Ok(Ended::Kicked(kick));
                     }
+                }
+            },
+            ClientMsg::Respawn => {
+                let _ = cmd_tx.send(Command::Respawn(pid));
+            }
+            ClientMsg::SelectShip { ship_class } => {
+                let _ = cmd_tx.send(Command::SelectShip(pid, ship_class));
+            }
+            ClientMsg::RequestLeaderboard => {
+                let (reply_tx, reply_rx) = oneshot::channel();
+                if cmd_tx.send(Command::Leaderboard(reply_tx)).is_err() {
+                    break;
+                }
+                if let Ok(entries) = reply_rx.await {
+                    outbox.push(Arc::new(ServerMsg::Leaderboard(entries)), Instant::now());
+                }
+            }
+            ClientMsg::Chat { text } => {
+                if guard.chat(Instant::now()) {
+                    let from = name.to_string();
+                    let _ = cmd_tx.send(Command::Chat { from, text });
+                } else {
+                    let text = "You're chatting too fast; that line wasn't sent.".to_string();
+                    let refused = ServerMsg::Chat { from: None, text };
+                    outbox.push(Arc::new(refused), Instant::now());
+                }
+            }
+            ClientMsg::Admin { token, command } => {
+                info!(?pid, ?command, "admin command");
+                let Ok(reply) = admin(cmd_tx, ip, token, command).await else {
+                    break;
                 };
-                match cm {
-                    ClientMsg::Input { input, .. } => {
-                        let _ = cmd_tx.send(Command::Input(pid, input));
-                    }
-                    ClientMsg::Respawn => {
-                        let _ = cmd_tx.send(Command::Respawn(pid));
+                // Guessing at the token counts against the connection too,
+                // so a pilot who keeps at it is kicked.
+                let refused = matches!(
+                    &reply,
+                    ServerMsg::AdminReply { ok: false, message }
+                        if message == BAD_ADMIN_TOKEN || message == ADMIN_LOCKED_OUT
+                );
+                outbox.push(Arc::new(reply), Instant::now());
+                if refused {
+                    if let Some(kick) = guard.invalid() {
+                        return Ok(Ended::Kicked(kick));
                     }
-                    ClientMsg::Bye => break,
-                    ClientMsg::Hello { .. } => {} // ignore re-hello
                 }
             }
-            Message::Close(_) => break,
-            _ => {}
+            ClientMsg::VotePause { .. } if spectating => {}
+            ClientMsg::VotePause { pause } => {
+                let _ = cmd_tx.send(Command::VotePause(pid, pause));
+            }
+            ClientMsg::Bye => return Ok(Ended::Bye),
+            // ignore re-hello
+            ClientMsg::Hello { .. }
+            | ClientMsg::Spectate { .. }
+            | ClientMsg::Resume { .. } => {}
         }
     }
+    Ok(Ended::Dropped)
+}
 
-    drop(write_tx);
-    bridge.abort();
-    let _ = writer.await;
-    Ok(())
+/// Hand an admin command to the game loop and wrap its answer for the
+/// operator.
+async fn admin(
+    cmd_tx: &mpsc::UnboundedSender<Command>,
+    peer: IpAddr,
+    token: String,
+    command: AdminCommand,
+) -> Result<ServerMsg> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    cmd_tx
+        .send(Command::Admin {
+            peer,
+            token,
+            command,
+            reply: reply_tx,
+        })
+        .map_err(|_| anyhow!("game loop dropped"))?;
+    let (ok, message) = match reply_rx.await? {
+        Ok(message) => (true, message),
+        Err(message) => (false, message),
+    };
+    Ok(ServerMsg::AdminReply { ok, message })
 }
 
 async fn game_loop(
+    server_config: ServerConfig,
+    leaderboard: Leaderboard,
+    next_pid: Arc<AtomicU32>,
     mut cmd_rx: mpsc::UnboundedReceiver<Command>,
     out_tx: broadcast::Sender<Arc<ServerMsg>>,
 ) {
-    let config = WorldConfig::default();
-    let mut world = World::new(config);
-    let world_size = world.world_size();
-    let seed = config.seed;
-    let mut current_inputs: PlayerInputs = PlayerInputs::new();
+    let mut game = Game {
+        world: World::new(server_config.world),
+        runs: BTreeMap::new(),
+        leaderboard,
+        current_inputs: PlayerInputs::new(),
+        next_pid,
+        out_tx,
+        bot_fill: server_config.bot_fill,
+    };
+    let world_size = game.world.world_size();
+    // Connections watching without a ship. Kept out of the world entirely,
+    // so they never count as pilots for the wave director or bot fill.
+    let mut spectators: BTreeSet<PlayerId> = BTreeSet::new();
+    // Resume token per pilot, issued on join, replaced on every resume and
+    // dropped when they leave.
+    let mut resume_tokens: BTreeMap<PlayerId, u64> = BTreeMap::new();
+    // Current connection session per connected pilot, and when each
+    // dropped pilot's grace period runs out. Wall-clock, like `closing_at`,
+    // so it runs out while paused too.
+    let mut sessions: BTreeMap<PlayerId, u64> = BTreeMap::new();
+    let mut next_session: u64 = 1;
+    let mut held: BTreeMap<PlayerId, Instant> = BTreeMap::new();
+    // Ends a connected pilot's connection with a kick.
+    let mut kickers: BTreeMap<PlayerId, oneshot::Sender<Kick>> = BTreeMap::new();
+    // Names (by `name_key`) refused at join, until the server restarts.
+    let mut banned: BTreeSet<String> = BTreeSet::new();
+    let mut admin_throttle = AdminThrottle::new(server_config.limits);
+    // While paused commands still drain and snapshots still go out, but
+    // the world doesn't tick. An operator pause holds on its own; otherwise
+    // the game pauses while most human pilots vote for it.
+    let mut admin_paused = false;
+    let mut pause_votes: BTreeSet<PlayerId> = BTreeSet::new();
+    let mut frames: u64 = 0;
+    // Wall-clock deadline once a shutdown has been asked for. Not ticks, so
+    // a paused world still shuts down on time.
+    let mut closing_at: Option<Instant> = None;
+    balance_bots(&mut game.world, game.bot_fill, &game.next_pid);
 
-    let mut interval = time::interval(Duration::from_secs_f32(TICK_DT));
+    let metrics = server_config.metrics.clone();
+    let tick_period = Duration::from_secs_f32(TICK_DT);
+    let mut interval = time::interval(tick_period);
     interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
     loop {
-        interval.tick().await;
+        let scheduled = interval.tick().await;
+        let started = Instant::now();
+        let overran = scheduled.elapsed() >= tick_period;
 
         // Drain commands without blocking.
         loop {
             match cmd_rx.try_recv() {
-                Ok(Command::Join { player_id, reply }) => {
-                    world.add_player(player_id);
-                    let snap = snapshot_from_world(&world);
-                    let _ = reply.send(JoinAck {
-                        snapshot: snap,
-                        seed,
-                        world_size,
-                    });
-                    let msg = Arc::new(ServerMsg::Events {
-                        tick: world.tick_index(),
-                        events: vec![GameEvent::PlayerJoined(player_id)],
-                    });
-                    let _ = out_tx.send(msg);
-                }
-                Ok(Command::Leave(pid)) => {
-                    if !world.has_player(pid) {
+                Ok(Command::Join {
+                    player_id,
+                    name,
+                    ship_class,
+                    resume,
+                    kick,
+                    reply,
+                }) => {
+                    if banned.contains(&name_key(&name)) {
+                        let _ = reply.send(Err(Kick::Banned));
                         continue;
                     }
-                    world.remove_player(pid);
-                    current_inputs.remove(&pid);
-                    let msg = Arc::new(ServerMsg::Events {
-                        tick: world.tick_index(),
-                        events: vec![GameEvent::PlayerLeft(pid)],
+                    // A known token gets the pilot back, ship and score
+                    // intact, whether or not we'd noticed the old socket
+                    // drop yet.
+                    let resumed = resume.and_then(|token| {
+                        resume_tokens
+                            .iter()
+                            .find(|(_, t)| **t == token)
+                            .map(|(pid, _)| *pid)
                     });
-                    let _ = out_tx.send(msg);
+                    let player_id = match resumed {
+                        Some(pid) => {
+                            info!(?pid, "pilot resumed");
+                            held.remove(&pid);
+                            game.current_inputs.remove(&pid);
+                            // The token has been used; the new connection
+                            // gets its own.
+                            resume_tokens.insert(pid, new_resume_token(pid));
+                            pid
+                        }
+                        None => {
+                            game.world.set_ship_class(player_id, ship_class);
+                            game.world.add_player(player_id);
+                            let run = Run::start(name.clone(), &game.world, player_id);
+                            game.runs.insert(player_id, run);
+                            notice(&game.out_tx, format!("{name} joined"));
+                            game.world.set_player_name(player_id, name);
+                            resume_tokens.insert(player_id, new_resume_token(player_id));
+                            let mut events = vec![GameEvent::PlayerJoined(player_id)];
+                            events.extend(balance_bots(
+                                &mut game.world,
+                                game.bot_fill,
+                                &game.next_pid,
+                            ));
+                            let msg = Arc::new(ServerMsg::Events {
+                                tick: game.world.tick_index(),
+                                events,
+                            });
+                            let _ = game.out_tx.send(msg);
+                            player_id
+                        }
+                    };
+                    let session = next_session;
+                    next_session += 1;
+                    sessions.insert(player_id, session);
+                    // Whoever held the token before is shut out, or the old
+                    // socket would go on steering the same ship.
+                    if let Some(old) = kickers.insert(player_id, kick) {
+                        let _ = old.send(Kick::Superseded);
+                    }
+                    let _ = reply.send(Ok(JoinAck {
+                        player_id,
+                        session,
+                        resume_token: resume_tokens[&player_id],
+                        snapshot: snapshot(&game.world, &spectators),
+                        seed: game.world.config().seed,
+                        world_size,
+                    }));
+                }
+                Ok(Command::Spectate { player_id, reply }) => {
+                    spectators.insert(player_id);
+                    let _ = reply.send(Ok(JoinAck {
+                        player_id,
+                        session: 0,
+                        resume_token: 0,
+                        snapshot: snapshot(&game.world, &spectators),
+                        seed: game.world.config().seed,
+                        world_size,
+                    }));
+                }
+                Ok(Command::Disconnect { player_id, .. }) if spectators.remove(&player_id) => {}
+                // A newer connection already resumed this pilot.
+                Ok(Command::Disconnect {
+                    player_id, session, ..
+                }) if sessions.get(&player_id) != Some(&session) => {}
+                Ok(Command::Disconnect {
+                    player_id, hold, ..
+                }) => {
+                    sessions.remove(&player_id);
+                    kickers.remove(&player_id);
+                    if hold {
+                        // Park the ship with its controls released until the
+                        // client resumes or the grace period runs out.
+                        info!(?player_id, "pilot dropped, holding for resume");
+                        game.current_inputs.remove(&player_id);
+                        // A pilot who isn't there can't keep the game
+                        // paused.
+                        pause_votes.remove(&player_id);
+                        held.insert(player_id, Instant::now() + server_config.resume_grace);
+                    } else {
+                        resume_tokens.remove(&player_id);
+                        game.remove_pilot(player_id);
+                    }
                 }
                 Ok(Command::Input(pid, input)) => {
-                    current_inputs.insert(pid, input);
+                    game.current_inputs.insert(pid, input);
+                }
+                Ok(Command::SelectShip(pid, ship_class)) => {
+                    game.world.set_ship_class(pid, ship_class);
                 }
                 Ok(Command::Respawn(pid)) => {
-                    if world.respawn_player(pid).is_some() {
+                    if game.world.respawn_player(pid).is_some() {
+                        if let Some(name) = game.world.player_name(pid) {
+                            let run = Run::start(name.to_string(), &game.world, pid);
+                            game.runs.insert(pid, run);
+                        }
                         // Drop any held input from before death so the
                         // respawned ship doesn't immediately fly off.
-                        current_inputs.remove(&pid);
+                        game.current_inputs.remove(&pid);
                         let msg = Arc::new(ServerMsg::Events {
-                            tick: world.tick_index(),
+                            tick: game.world.tick_index(),
                             events: vec![GameEvent::PlayerJoined(pid)],
                         });
-                        let _ = out_tx.send(msg);
+                        let _ = game.out_tx.send(msg);
                     }
                 }
+                Ok(Command::VotePause(pid, pause)) => {
+                    let changed = if pause {
+                        pause_votes.insert(pid)
+                    } else {
+                        pause_votes.remove(&pid)
+                    };
+                    if changed && pause && !game.world.paused() {
+                        let name = game.world.player_name(pid).unwrap_or("someone");
+                        let pilots = human_pilots(&game.world);
+                        let votes = pause_votes.len();
+                        let text = format!("{name} votes to pause ({votes}/{pilots})");
+                        notice(&game.out_tx, text);
+                    }
+                }
+                Ok(Command::Leaderboard(reply)) => {
+                    let _ = reply.send(game.leaderboard.top(LEADERBOARD_SIZE));
+                }
+                Ok(Command::Shutdown { reason, grace }) => {
+                    let seconds = grace.as_secs_f32().ceil() as u32;
+                    let msg = ServerMsg::Shutdown { reason, seconds };
+                    let _ = game.out_tx.send(Arc::new(msg));
+                    closing_at = Some(Instant::now() + grace);
+                }
+                Ok(Command::Chat { from, text }) => {
+                    let max_chars = server_config.limits.max_chat_chars;
+                    if let Some(text) = chat::sanitize(&text, max_chars) {
+                        let from = Some(from);
+                        let _ = game.out_tx.send(Arc::new(ServerMsg::Chat { from, text }));
+                    }
+                }
+                // Checked before the token, so a locked-out address learns
+                // nothing from its guesses.
+                Ok(Command::Admin { peer, reply, .. })
+                    if admin_throttle.locked(peer, Instant::now()) =>
+                {
+                    warn!(%peer, "admin command from a locked-out address");
+                    let _ = reply.send(Err(ADMIN_LOCKED_OUT.into()));
+                }
+                Ok(Command::Admin { peer, token, reply, .. })
+                    if server_config.admin_token.as_deref() != Some(token.as_str()) =>
+                {
+                    warn!(%peer, "admin command with a bad token");
+                    admin_throttle.missed(peer, Instant::now());
+                    let _ = reply.send(Err(BAD_ADMIN_TOKEN.into()));
+                }
+                Ok(Command::Admin { peer, command, reply, .. }) => {
+                    admin_throttle.cleared(peer);
+                    info!(?command, "applying admin command");
+                    let ban = matches!(command, AdminCommand::Ban { .. });
+                    let result = match command {
+                        AdminCommand::Kick { name } | AdminCommand::Ban { name } => {
+                            let found = find_pilot(&game.world, &name);
+                            if ban && found.is_ok() {
+                                banned.insert(name_key(&name));
+                            }
+                            match found {
+                                Err(e) => Err(e),
+                                Ok(Some(pid)) => {
+                                    let kick = if ban { Kick::Banned } else { Kick::Admin };
+                                    // A connected pilot leaves through its
+                                    // connection's `Disconnect`; a held one
+                                    // has none, so goes now.
+                                    let sent = kickers.remove(&pid).map(|k| k.send(kick));
+                                    if !matches!(sent, Some(Ok(()))) {
+                                        held.remove(&pid);
+                                        sessions.remove(&pid);
+                                        resume_tokens.remove(&pid);
+                                        game.remove_pilot(pid);
+                                    }
+                                    Ok(format!("{} {name}", if ban { "banned" } else { "kicked" }))
+                                }
+                                Ok(None) if ban => Ok(format!("banned {name}")),
+                                Ok(None) => Err(format!("no pilot named {name}")),
+                            }
+                        }
+                        AdminCommand::SetLevel { level } => {
+                            game.world.set_level(level);
+                            let events = vec![GameEvent::LevelUp(game.world.level())];
+                            broadcast_events(&game.out_tx, game.world.tick_index(), events);
+                            Ok(format!("level {}", game.world.level()))
+                        }
+                        AdminCommand::Spawn { pos, .. } if !in_world(pos.into(), world_size) => {
+                            Err(format!("({}, {}) is outside the world", pos.x, pos.y))
+                        }
+                        AdminCommand::Spawn { kind, count, pos } => {
+                            let count = count.min(admin::MAX_SPAWN);
+                            match game.world.spawn_hostiles(kind, count, pos.into()) {
+                                0 => Err(format!("can't spawn {kind:?}")),
+                                n => Ok(format!("spawned {n} {kind:?}")),
+                            }
+                        }
+                        AdminCommand::God { name, on } => match find_pilot(&game.world, &name) {
+                            Err(e) => Err(e),
+                            Ok(Some(pid)) => {
+                                let on = on.unwrap_or(!game.world.god_mode(pid));
+                                game.world.set_god_mode(pid, on);
+                                let state = if on { "on" } else { "off" };
+                                Ok(format!("god mode {state} for {name}"))
+                            }
+                            Ok(None) => Err(format!("no pilot named {name}")),
+                        },
+                        AdminCommand::Pause => {
+                            admin_paused = true;
+                            Ok("paused".into())
+                        }
+                        AdminCommand::Resume => {
+                            admin_paused = false;
+                            // Start the vote over too, or a standing
+                            // majority would pause it straight back.
+                            pause_votes.clear();
+                            Ok("resumed".into())
+                        }
+                        AdminCommand::TimeScale { scale } => {
+                            game.world.set_time_scale(scale);
+                            Ok(format!("time scale {}", game.world.time_scale()))
+                        }
+                        AdminCommand::SetSeed { seed } => {
+                            game.world.set_next_seed(seed);
+                            Ok(format!("seed {seed} from the next reset"))
+                        }
+                        AdminCommand::Broadcast { text } => {
+                            let _ = game.out_tx.send(Arc::new(ServerMsg::Announcement { text }));
+                            Ok("sent".into())
+                        }
+                    };
+                    let _ = reply.send(result);
+                }
                 Err(mpsc::error::TryRecvError::Empty) => break,
-                Err(mpsc::error::TryRecvError::Disconnected) => return,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    game.leaderboard.close().await;
+                    return;
+                }
             }
         }
 
-        let events = world.tick(&current_inputs, TICK_DT);
-        let tick = world.tick_index();
-        if !events.is_empty() {
-            let _ = out_tx.send(Arc::new(ServerMsg::Events { tick, events }));
+        // Pilots whose client never came back.
+        let now = Instant::now();
+        let expired: Vec<PlayerId> = held
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(pid, _)| *pid)
+            .collect();
+        for pid in expired {
+            info!(?pid, "resume grace expired");
+            held.remove(&pid);
+            resume_tokens.remove(&pid);
+            game.remove_pilot(pid);
         }
-        if tick.0 % SNAPSHOT_EVERY == 0 {
-            let snap = snapshot_from_world(&world);
-            let _ = out_tx.send(Arc::new(ServerMsg::Snapshot(snap)));
+
+        // Votes lapse when their pilot leaves, and joins and leaves move
+        // the majority, so settle the pause every frame.
+        pause_votes.retain(|pid| game.world.player_name(*pid).is_some());
+        let paused = admin_paused || pause_votes.len() * 2 > human_pilots(&game.world);
+        if paused != game.world.paused() {
+            game.world.set_paused(paused);
+            let text = if paused { "Game paused" } else { "Game resumed" };
+            notice(&game.out_tx, text.into());
         }
+
+        let events = game.world.tick(&game.current_inputs, TICK_DT);
+        let tick = game.world.tick_index();
+        for event in &events {
+            match event {
+                GameEvent::PlayerKilled { player_id, .. } => {
+                    if let Some(entry) = game
+                        .runs
+                        .remove(player_id)
+                        .and_then(|r| r.finish(&game.world, *player_id))
+                    {
+                        game.leaderboard.record(entry);
+                    }
+                }
+                // PvP pilots respawn on the world's own timer; start their
+                // next run here and drop input held from the last life.
+                GameEvent::PlayerJoined(pid) if !game.world.is_bot(*pid) => {
+                    if let Some(name) = game.world.player_name(*pid) {
+                        let run = Run::start(name.to_string(), &game.world, *pid);
+                        game.runs.insert(*pid, run);
+                    }
+                    game.current_inputs.remove(pid);
+                }
+                _ => {}
+            }
+        }
+        broadcast_events(&game.out_tx, tick, events);
+        // Counted apart from the world's tick, which stands still while
+        // paused.
+        frames += 1;
+        if frames % SNAPSHOT_EVERY == 0 {
+            let msg = ServerMsg::Snapshot(snapshot(&game.world, &spectators));
+            metrics.observe_world(&game.world, spectators.len());
+            let _ = game.out_tx.send(Arc::new(msg));
+        }
+        metrics.tick(started, overran);
+
+        if closing_at.is_some_and(|at| Instant::now() >= at) {
+            // Runs still in the air count as if the pilots had left.
+            for (pid, run) in std::mem::take(&mut game.runs) {
+                if let Some(entry) = run.finish(&game.world, pid) {
+                    game.leaderboard.record(entry);
+                }
+            }
+            game.leaderboard.close().await;
+            info!("final scores banked, game loop stopping");
+            return;
+        }
+    }
+}
+
+/// Pilots in the world flown by a connection, held ones included.
+fn human_pilots(world: &World) -> usize {
+    world.player_names().keys().filter(|pid| !world.is_bot(**pid)).count()
+}
+
+/// The human pilot with this display name, ignoring case. Names aren't
+/// unique, so a name two pilots share is an error rather than a guess.
+fn find_pilot(world: &World, name: &str) -> Result<Option<PlayerId>, String> {
+    let key = name_key(name);
+    let found: Vec<PlayerId> = world
+        .player_names()
+        .iter()
+        .filter(|(pid, n)| !world.is_bot(**pid) && name_key(n) == key)
+        .map(|(pid, _)| *pid)
+        .collect();
+    match found[..] {
+        [] => Ok(None),
+        [pid] => Ok(Some(pid)),
+        _ => Err(format!("{} pilots are named {name}", found.len())),
+    }
+}
+
+/// Whether `pos` lies inside a world of `size`. False for NaN and
+/// infinities too.
+fn in_world(pos: Vec2, size: Vec2) -> bool {
+    (0.0..=size.x).contains(&pos.x) && (0.0..=size.y).contains(&pos.y)
+}
+
+/// What the game loop shares with the helpers it calls as pilots come
+/// and go.
+struct Game {
+    world: World,
+    /// The life each pilot is currently flying, for the high-score table.
+    runs: BTreeMap<PlayerId, Run>,
+    leaderboard: Leaderboard,
+    current_inputs: PlayerInputs,
+    /// Hands out bot ids; see `run_with_config`.
+    next_pid: Arc<AtomicU32>,
+    out_tx: broadcast::Sender<Arc<ServerMsg>>,
+    /// `ServerConfig::bot_fill`.
+    bot_fill: usize,
+}
+
+impl Game {
+    /// Take a pilot out for good: bank their run, drop them from the
+    /// world, rebalance the bots and tell everyone.
+    fn remove_pilot(&mut self, pid: PlayerId) {
+        // Quitting mid-flight still banks the run.
+        if let Some(entry) = self.runs.remove(&pid).and_then(|r| r.finish(&self.world, pid)) {
+            self.leaderboard.record(entry);
+        }
+        // Remove even a pilot who left while dead so their name and score
+        // don't linger in snapshots.
+        let was_flying = self.world.has_player(pid);
+        if let Some(name) = self.world.player_name(pid) {
+            notice(&self.out_tx, format!("{name} left"));
+        }
+        self.world.remove_player(pid);
+        self.current_inputs.remove(&pid);
+        let mut events = Vec::new();
+        if was_flying {
+            events.push(GameEvent::PlayerLeft(pid));
+        }
+        events.extend(balance_bots(&mut self.world, self.bot_fill, &self.next_pid));
+        broadcast_events(&self.out_tx, self.world.tick_index(), events);
+    }
+}
+
+fn broadcast_events(
+    out_tx: &broadcast::Sender<Arc<ServerMsg>>,
+    tick: Tick,
+    events: Vec<GameEvent>,
+) {
+    if !events.is_empty() {
+        let _ = out_tx.send(Arc::new(ServerMsg::Events { tick, events }));
+    }
+}
+
+/// Chat line from the server itself.
+fn notice(out_tx: &broadcast::Sender<Arc<ServerMsg>>, text: String) {
+    let _ = out_tx.send(Arc::new(ServerMsg::Chat { from: None, text }));
+}
+
+/// Fresh unguessable resume token. `RandomState` is seeded from the OS,
+/// which is all the randomness a reconnect secret needs.
+fn new_resume_token(pid: PlayerId) -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(pid.0);
+    hasher.finish()
+}
+
+/// `snapshot_from_world` plus the spectator count, which only the server
+/// knows.
+fn snapshot(world: &World, spectators: &BTreeSet<PlayerId>) -> Snapshot {
+    let mut snap = snapshot_from_world(world);
+    snap.spectators = spectators.len() as u32;
+    snap
+}
+
+/// Add or drop wingman bots so humans plus bots make `fill`. Bots are
+/// named by slot, and the newest goes first when a human takes its place,
+/// so the roster always reads `WINGMAN 1..n`. Returns the join/leave
+/// events to broadcast.
+fn balance_bots(world: &mut World, fill: usize, next_pid: &AtomicU32) -> Vec<GameEvent> {
+    let bots: Vec<PlayerId> = world.bots().collect();
+    let humans = world.player_names().len() - bots.len();
+    let want = fill.saturating_sub(humans);
+    let mut events = Vec::new();
+    for &pid in bots.iter().skip(want).rev() {
+        world.remove_player(pid);
+        events.push(GameEvent::PlayerLeft(pid));
+    }
+    for slot in bots.len()..want {
+        let pid = PlayerId(next_pid.fetch_add(1, Ordering::Relaxed));
+        world.add_bot(pid, format!("WINGMAN {}", slot + 1));
+        events.push(GameEvent::PlayerJoined(pid));
     }
+    events
 }
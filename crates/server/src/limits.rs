@@ -0,0 +1,234 @@
+//! Per-connection guards against hostile or broken clients: a cap on frame
+//! size, a token-bucket limit on message rate, and sanitising of the
+//! inputs and chat that reach the sim and other players. A connection
+//! that keeps tripping them is kicked with a [`Kick`] reason the client
+//! can show. The bounds on each connection's send queue live here too;
+//! `outbox` enforces them. [`AdminThrottle`] works across connections,
+//! per address, since a fresh socket per admin token guess is cheap.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use sim::PlayerInput;
+
+/// Limits applied to every connection. `Default` is tuned for the stock
+/// client, which sends one `Input` per 60 Hz tick plus the odd request.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Largest frame (and message) accepted, in bytes. The biggest honest
+    /// `ClientMsg` is a `Hello` or `Resume` with a long name.
+    pub max_frame_bytes: usize,
+    /// Sustained messages per second.
+    pub msgs_per_sec: f32,
+    /// Messages a connection can send in a burst after being quiet, e.g.
+    /// inputs that queued up behind a network stall.
+    pub burst: f32,
+    /// Malformed messages, rejected inputs and wrong admin tokens
+    /// tolerated before a kick. Honest clients never send any of them, so
+    /// these never wear off.
+    pub max_invalid: u32,
+    /// Messages waiting to be written to a slow client before it counts as
+    /// saturated. Snapshots coalesce, so this is mostly event batches:
+    /// about 4 s of a busy world. Nothing is dropped past it.
+    pub max_queued: usize,
+    /// How long the queue may stay saturated, from reaching `max_queued`
+    /// until it drains below half of that, before the client is dropped.
+    pub max_saturated: Duration,
+    /// Longest chat line, in characters, after sanitising.
+    pub max_chat_chars: usize,
+    /// Sustained chat lines per second, and how many may come at once.
+    /// Lines over the limit are refused with a notice, not counted against
+    /// the connection.
+    pub chat_per_sec: f32,
+    pub chat_burst: f32,
+    /// Wrong admin tokens one address may send before it's locked out,
+    /// and how long the first lockout lasts. Each further miss doubles
+    /// it, up to `admin_max_lockout`.
+    pub admin_tries: u32,
+    pub admin_lockout: Duration,
+    pub admin_max_lockout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_frame_bytes: 4 * 1024,
+            msgs_per_sec: 120.0,
+            burst: 120.0,
+            max_invalid: 16,
+            max_queued: 256,
+            max_saturated: Duration::from_secs(5),
+            max_chat_chars: protocol::MAX_CHAT_CHARS,
+            chat_per_sec: 0.5,
+            chat_burst: 4.0,
+            admin_tries: 5,
+            admin_lockout: Duration::from_secs(1),
+            admin_max_lockout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Why a connection was kicked. The `Display` text is what the client sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kick {
+    FrameTooLarge,
+    TooManyMessages,
+    TooManyInvalid,
+    /// An operator's `AdminCommand::Kick` or `Ban`.
+    Admin,
+    /// The pilot's name is banned.
+    Banned,
+    /// The pilot resumed on a newer connection.
+    Superseded,
+}
+
+impl std::fmt::Display for Kick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kick::FrameTooLarge => "message too large",
+            Kick::TooManyMessages => "sending too many messages",
+            Kick::TooManyInvalid => "too many invalid messages",
+            Kick::Admin => "kicked by an operator",
+            Kick::Banned => "banned from this server",
+            Kick::Superseded => "resumed from another connection",
+        })
+    }
+}
+
+/// Rate and validity bookkeeping for one connection.
+#[derive(Debug)]
+pub struct Guard {
+    limits: Limits,
+    /// Messages the connection may send right now. Goes negative while it
+    /// sends over the limit; those messages are dropped, and falling a
+    /// whole burst into debt gets it kicked.
+    tokens: f32,
+    refilled_at: Instant,
+    invalid: u32,
+    /// Separate, much slower bucket for chat lines.
+    chat_tokens: f32,
+    chat_refilled_at: Instant,
+}
+
+/// What to do with one inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accept,
+    /// Over the rate limit: drop the message but keep the connection.
+    Drop,
+    Kick(Kick),
+}
+
+impl Guard {
+    pub fn new(limits: Limits, now: Instant) -> Self {
+        Guard {
+            limits,
+            tokens: limits.burst,
+            refilled_at: now,
+            invalid: 0,
+            chat_tokens: limits.chat_burst,
+            chat_refilled_at: now,
+        }
+    }
+
+    /// Charge one message arriving at `now` against the bucket.
+    pub fn admit(&mut self, now: Instant) -> Verdict {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f32();
+        self.refilled_at = now;
+        self.tokens = (self.tokens + elapsed * self.limits.msgs_per_sec).min(self.limits.burst);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Verdict::Accept
+        } else if self.tokens < -self.limits.burst {
+            Verdict::Kick(Kick::TooManyMessages)
+        } else {
+            Verdict::Drop
+        }
+    }
+
+    /// Count a malformed message, rejected input or wrong admin token.
+    /// Returns the kick once the connection has sent too many.
+    pub fn invalid(&mut self) -> Option<Kick> {
+        self.invalid += 1;
+        (self.invalid >= self.limits.max_invalid).then_some(Kick::TooManyInvalid)
+    }
+
+    /// Charge one chat line arriving at `now`. False if the connection is
+    /// chatting too fast and the line should be refused.
+    pub fn chat(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.chat_refilled_at)
+            .as_secs_f32();
+        self.chat_refilled_at = now;
+        self.chat_tokens =
+            (self.chat_tokens + elapsed * self.limits.chat_per_sec).min(self.limits.chat_burst);
+        if self.chat_tokens < 1.0 {
+            return false;
+        }
+        self.chat_tokens -= 1.0;
+        true
+    }
+}
+
+/// Wrong admin tokens per address, kept by the game loop. An address that
+/// runs through `Limits::admin_tries` is locked out, right token or not,
+/// until its lockout ends; the right token clears its record.
+#[derive(Debug)]
+pub struct AdminThrottle {
+    limits: Limits,
+    misses: BTreeMap<IpAddr, Misses>,
+}
+
+#[derive(Debug, Default)]
+struct Misses {
+    count: u32,
+    locked_until: Option<Instant>,
+}
+
+impl AdminThrottle {
+    pub fn new(limits: Limits) -> Self {
+        AdminThrottle {
+            limits,
+            misses: BTreeMap::new(),
+        }
+    }
+
+    /// Whether `ip` is locked out at `now`.
+    pub fn locked(&self, ip: IpAddr, now: Instant) -> bool {
+        self.misses
+            .get(&ip)
+            .and_then(|m| m.locked_until)
+            .is_some_and(|until| now < until)
+    }
+
+    /// Count a wrong token from `ip` arriving at `now`.
+    pub fn missed(&mut self, ip: IpAddr, now: Instant) {
+        let misses = self.misses.entry(ip).or_default();
+        misses.count += 1;
+        if let Some(over) = misses.count.checked_sub(self.limits.admin_tries) {
+            let lockout = self.limits.admin_lockout.saturating_mul(1 << over.min(16));
+            misses.locked_until = Some(now + lockout.min(self.limits.admin_max_lockout));
+        }
+    }
+
+    /// The right token from `ip`: forget its misses.
+    pub fn cleared(&mut self, ip: IpAddr) {
+        self.misses.remove(&ip);
+    }
+}
+
+/// Clamp both axes to the `-1..=1` range the sim expects. Non-finite axes
+/// can't be repaired, so the input is rejected.
+pub fn sanitize_input(input: PlayerInput) -> Option<PlayerInput> {
+    if !input.xaxis.is_finite() || !input.yaxis.is_finite() {
+        return None;
+    }
+    Some(PlayerInput {
+        xaxis: input.xaxis.clamp(-1.0, 1.0),
+        yaxis: input.yaxis.clamp(-1.0, 1.0),
+        ..input
+    })
+}
@@ -1,6 +1,15 @@
+use std::time::Duration;
+
 use anyhow::Result;
+use server::shutdown::Shutdown;
+use server::ServerConfig;
+use sim::{FlightModel, ObjectiveKind, PvpRules};
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{info, warn};
+
+/// How long players get between Ctrl-C (or SIGTERM) and the server going
+/// down.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -13,7 +22,104 @@ async fn main() -> Result<()> {
 
     let addr =
         std::env::var("ICARUST_LISTEN").unwrap_or_else(|_| protocol::DEFAULT_ADDR.to_string());
+    let mut config = ServerConfig::default();
+    match std::env::var("ICARUST_FLIGHT_MODEL").as_deref() {
+        Ok("aero") | Ok("aerodynamic") => config.world.flight_model = FlightModel::Aerodynamic,
+        Ok("arcade") | Err(_) => {}
+        Ok(other) => warn!(%other, "unknown ICARUST_FLIGHT_MODEL, using arcade"),
+    }
+    config.leaderboard_path = Some(
+        std::env::var("ICARUST_LEADERBOARD")
+            .unwrap_or_else(|_| "leaderboard.json".to_string())
+            .into(),
+    );
+    match std::env::var("ICARUST_MODE").as_deref() {
+        Ok("ffa") => config.world.pvp = Some(PvpRules::default()),
+        Ok("tdm") => {
+            config.world.pvp = Some(PvpRules::default());
+            config.world.teams = 2;
+        }
+        Ok("coop") | Err(_) => {}
+        Ok(other) => warn!(%other, "unknown ICARUST_MODE, using coop"),
+    }
+    // AI waves stay on in PvP unless asked otherwise.
+    let hostiles = std::env::var("ICARUST_HOSTILES");
+    if let (Some(rules), Ok("0")) = (&mut config.world.pvp, hostiles.as_deref()) {
+        rules.hostile_waves = false;
+    }
+    if let Ok(teams) = std::env::var("ICARUST_TEAMS") {
+        match teams.parse() {
+            Ok(n) => config.world.teams = n,
+            Err(_) => warn!(%teams, "ICARUST_TEAMS is not a team count, ignoring"),
+        }
+    }
+    match std::env::var("ICARUST_OBJECTIVE").as_deref() {
+        Ok("convoy") => config.world.objective = Some(ObjectiveKind::DefendConvoy),
+        Ok("tanks") => config.world.objective = Some(ObjectiveKind::DestroyTanks),
+        Ok("bomber") => config.world.objective = Some(ObjectiveKind::EscortBomber),
+        Ok("none") | Err(_) => {}
+        Ok(other) => warn!(%other, "unknown ICARUST_OBJECTIVE, playing without one"),
+    }
+    if let Ok(fill) = std::env::var("ICARUST_BOTS") {
+        match fill.parse() {
+            Ok(n) => config.bot_fill = n,
+            Err(_) => warn!(%fill, "ICARUST_BOTS is not a pilot count, bots disabled"),
+        }
+    }
+    // Admin commands are refused unless the operator picks a token.
+    config.admin_token = std::env::var("ICARUST_ADMIN_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    // Prometheus metrics and a health check on a port of their own, off
+    // unless asked for.
+    if let Ok(metrics_addr) = std::env::var("ICARUST_METRICS") {
+        let metrics_listener = TcpListener::bind(&metrics_addr).await?;
+        info!(%metrics_addr, "serving /metrics and /healthz");
+        tokio::spawn(server::metrics::serve(
+            metrics_listener,
+            config.metrics.clone(),
+        ));
+    }
     let listener = TcpListener::bind(&addr).await?;
-    info!(%addr, "icarust server listening");
-    server::run_with_listener(listener).await
+    info!(
+        %addr,
+        flight_model = ?config.world.flight_model,
+        pvp = ?config.world.pvp,
+        teams = config.world.teams,
+        objective = ?config.world.objective,
+        bot_fill = config.bot_fill,
+        admin = config.admin_token.is_some(),
+        "icarust server listening"
+    );
+    tokio::spawn(shutdown_on_signal(config.shutdown.clone()));
+    server::run_with_config(listener, config).await
+}
+
+/// First Ctrl-C or SIGTERM starts a graceful shutdown; a second one exits on
+/// the spot.
+async fn shutdown_on_signal(shutdown: Shutdown) {
+    if let Err(e) = signal().await {
+        warn!("can't listen for shutdown signals: {e}");
+        return;
+    }
+    info!("shutting down in {SHUTDOWN_GRACE:?}; signal again to stop now");
+    shutdown.trigger("The server is shutting down.", SHUTDOWN_GRACE);
+    if signal().await.is_ok() {
+        std::process::exit(130);
+    }
+}
+
+#[cfg(unix)]
+async fn signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut term = signal(SignalKind::terminate())?;
+    tokio::select! {
+        ctrl_c = tokio::signal::ctrl_c() => ctrl_c,
+        _ = term.recv() => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+async fn signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
 }
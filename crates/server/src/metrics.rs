@@ -0,0 +1,282 @@
+//! Counters and gauges the server updates as it runs. One [`Metrics`] is
+//! shared by every task of a server instance; hand the same `Arc` in
+//! through `ServerConfig::metrics` to read them from outside.
+//!
+//! [`serve`] exposes them over plain HTTP on a port of their own:
+//! `/metrics` in the Prometheus text format, and `/healthz`, which answers
+//! 200 while the game loop is ticking and 503 once it has stopped.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use sim::{EntityKind, World};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::warn;
+
+/// Upper bounds (seconds) of the tick-duration histogram buckets. A tick
+/// has 16.7 ms to run before the loop falls behind.
+const TICK_BUCKETS: [f64; 8] = [0.0005, 0.001, 0.002, 0.004, 0.008, 0.016, 0.033, 0.1];
+/// Entity kinds in the order `Metrics::entities` counts them.
+const ENTITY_KINDS: [&str; 6] = ["player", "shot", "enemy", "tank", "convoy", "bomber"];
+/// `/healthz` fails once the game loop has gone this long without a tick.
+const HEALTHY_WITHIN: Duration = Duration::from_secs(1);
+/// Biggest request we bother reading. Scrapers send a few hundred bytes.
+const MAX_REQUEST: usize = 8 * 1024;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Snapshots a client never got because a newer one replaced them in
+    /// its queue.
+    pub snapshots_dropped: AtomicU64,
+    /// Broadcasts a connection's bridge missed because it fell more than
+    /// the broadcast channel's capacity behind.
+    pub broadcast_lagged: AtomicU64,
+    /// Connections closed because their queue stayed saturated too long.
+    pub slow_clients_dropped: AtomicU64,
+    /// Human pilots in the world, held ones included.
+    pub players: AtomicU64,
+    pub bots: AtomicU64,
+    pub spectators: AtomicU64,
+    pub level: AtomicI64,
+    /// Live entities per kind, indexed like `ENTITY_KINDS`.
+    pub entities: [AtomicU64; ENTITY_KINDS.len()],
+    /// Wall time spent on each game-loop tick.
+    pub tick_duration: Histogram,
+    /// Ticks that started a full tick or more late, i.e. the loop fell
+    /// behind and `MissedTickBehavior::Delay` pushed the schedule back.
+    pub tick_overruns: AtomicU64,
+    /// Snapshots written to clients, one per client per broadcast less
+    /// those the outbox coalesced away.
+    pub snapshots_sent: AtomicU64,
+    /// Encoded size of every snapshot written, counted per client.
+    pub snapshot_bytes: AtomicU64,
+    last_tick: Mutex<Option<Instant>>,
+}
+
+/// Prometheus-style cumulative histogram over `TICK_BUCKETS`.
+#[derive(Debug, Default)]
+pub struct Histogram {
+    buckets: [AtomicU64; TICK_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub fn observe(&self, d: Duration) {
+        let secs = d.as_secs_f64();
+        for (bound, bucket) in TICK_BUCKETS.iter().zip(&self.buckets) {
+            if secs <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(d.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Metrics {
+    /// Record one finished game-loop tick.
+    pub fn tick(&self, started: Instant, overran: bool) {
+        let now = Instant::now();
+        self.tick_duration.observe(now - started);
+        if overran {
+            self.tick_overruns.fetch_add(1, Ordering::Relaxed);
+        }
+        *self.last_tick.lock().unwrap() = Some(now);
+    }
+
+    /// Refresh the gauges that describe the world.
+    pub fn observe_world(&self, world: &World, spectators: usize) {
+        let bots = world.bots().count();
+        let players = world.player_names().len() - bots;
+        self.players.store(players as u64, Ordering::Relaxed);
+        self.bots.store(bots as u64, Ordering::Relaxed);
+        self.spectators.store(spectators as u64, Ordering::Relaxed);
+        self.level.store(world.level() as i64, Ordering::Relaxed);
+        let mut counts = [0u64; ENTITY_KINDS.len()];
+        for e in world.entities().filter(|e| e.alive) {
+            counts[kind_index(&e.kind)] += 1;
+        }
+        for (gauge, n) in self.entities.iter().zip(counts) {
+            gauge.store(n, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot_sent(&self, bytes: usize) {
+        self.snapshots_sent.fetch_add(1, Ordering::Relaxed);
+        self.snapshot_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// True while the game loop has ticked recently.
+    pub fn healthy(&self, now: Instant) -> bool {
+        self.last_tick
+            .lock()
+            .unwrap()
+            .is_some_and(|t| now.saturating_duration_since(t) < HEALTHY_WITHIN)
+    }
+
+    /// Everything in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let get = |c: &AtomicU64| c.load(Ordering::Relaxed);
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        };
+        metric(
+            "icarust_players",
+            "gauge",
+            "Human pilots in the world.",
+            get(&self.players).to_string(),
+        );
+        metric(
+            "icarust_bots",
+            "gauge",
+            "Wingman bots in the world.",
+            get(&self.bots).to_string(),
+        );
+        metric(
+            "icarust_spectators",
+            "gauge",
+            "Connections watching without a ship.",
+            get(&self.spectators).to_string(),
+        );
+        metric(
+            "icarust_level",
+            "gauge",
+            "Current world level.",
+            self.level.load(Ordering::Relaxed).to_string(),
+        );
+        metric(
+            "icarust_tick_overruns_total",
+            "counter",
+            "Ticks that started a full tick late.",
+            get(&self.tick_overruns).to_string(),
+        );
+        metric(
+            "icarust_snapshots_total",
+            "counter",
+            "Snapshots written to clients.",
+            get(&self.snapshots_sent).to_string(),
+        );
+        metric(
+            "icarust_snapshot_bytes_total",
+            "counter",
+            "Encoded bytes of snapshots written to clients.",
+            get(&self.snapshot_bytes).to_string(),
+        );
+        metric(
+            "icarust_broadcast_lagged_total",
+            "counter",
+            "Broadcasts connections missed by falling behind the channel.",
+            get(&self.broadcast_lagged).to_string(),
+        );
+        metric(
+            "icarust_snapshots_dropped_total",
+            "counter",
+            "Snapshots replaced in slow clients' queues.",
+            get(&self.snapshots_dropped).to_string(),
+        );
+        metric(
+            "icarust_slow_clients_dropped_total",
+            "counter",
+            "Connections closed because their queue stayed saturated.",
+            get(&self.slow_clients_dropped).to_string(),
+        );
+
+        let _ = writeln!(out, "# HELP icarust_entities Live entities by kind.");
+        let _ = writeln!(out, "# TYPE icarust_entities gauge");
+        for (kind, n) in ENTITY_KINDS.iter().zip(&self.entities) {
+            let _ = writeln!(out, "icarust_entities{{kind=\"{kind}\"}} {}", get(n));
+        }
+
+        let h = &self.tick_duration;
+        let name = "icarust_tick_duration_seconds";
+        let _ = writeln!(out, "# HELP {name} Wall time spent per game-loop tick.");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (bound, bucket) in TICK_BUCKETS.iter().zip(&h.buckets) {
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {}", get(bucket));
+        }
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", get(&h.count));
+        let sum = get(&h.sum_micros) as f64 / 1e6;
+        let _ = writeln!(out, "{name}_sum {sum}");
+        let _ = writeln!(out, "{name}_count {}", get(&h.count));
+        out
+    }
+}
+
+fn kind_index(kind: &EntityKind) -> usize {
+    match kind {
+        EntityKind::Player { .. } => 0,
+        EntityKind::Shot { .. } => 1,
+        EntityKind::Enemy => 2,
+        EntityKind::Tank => 3,
+        EntityKind::Convoy => 4,
+        EntityKind::Bomber => 5,
+    }
+}
+
+/// Serve `/metrics` and `/healthz` on `listener` forever.
+pub async fn serve(listener: TcpListener, metrics: Arc<Metrics>) -> Result<()> {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(p) => p,
+            Err(e) => {
+                warn!("metrics accept failed: {e}");
+                continue;
+            }
+        };
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, &metrics).await {
+                warn!(%peer, "metrics request failed: {e:#}");
+            }
+        });
+    }
+}
+
+/// Answer one request and close. Just enough HTTP/1.1 for scrapers and
+/// health probes: the request line is all we look at.
+async fn respond(mut stream: TcpStream, metrics: &Metrics) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let request = String::from_utf8_lossy(&buf);
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", "text/plain; version=0.0.4", metrics.render()),
+        ("GET", "/healthz") if metrics.healthy(Instant::now()) => {
+            ("200 OK", "text/plain", "ok\n".to_string())
+        }
+        ("GET", "/healthz") => (
+            "503 Service Unavailable",
+            "text/plain",
+            "game loop stalled\n".to_string(),
+        ),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
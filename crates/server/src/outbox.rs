@@ -0,0 +1,147 @@
+//! Bounded send queue for one connection, between the broadcast bridge
+//! and the socket writer. A client that reads slower than the server
+//! broadcasts can't grow it without limit:
+//!
+//! - Snapshots are full state, so a new one replaces any still waiting.
+//! - Everything else (events, replies) queues in order and is never
+//!   dropped: a missed kill or chat line can't be made up later.
+//! - Reaching `Limits::max_queued` starts the saturation clock, which only
+//!   stops once the queue drains below half of that. If it runs for
+//!   `Limits::max_saturated` the client is stalled and the connection ends.
+
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use protocol::ServerMsg;
+use tokio::sync::Notify;
+
+use crate::limits::Limits;
+use crate::metrics::Metrics;
+
+pub struct Outbox {
+    state: Mutex<State>,
+    max_queued: usize,
+    max_saturated: Duration,
+    metrics: Arc<Metrics>,
+    /// Wakes the writer when a message arrives or the outbox closes.
+    ready: Notify,
+    /// Fires once when the client is found to be stalled.
+    stall: Notify,
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Arc<ServerMsg>>,
+    /// When the queue last filled up, until it drains below the low-water
+    /// mark.
+    saturated_since: Option<Instant>,
+    stalled: bool,
+    closed: bool,
+}
+
+impl Outbox {
+    pub fn new(limits: &Limits, metrics: Arc<Metrics>) -> Self {
+        Outbox {
+            state: Mutex::new(State::default()),
+            max_queued: limits.max_queued,
+            max_saturated: limits.max_saturated,
+            metrics,
+            ready: Notify::new(),
+            stall: Notify::new(),
+        }
+    }
+
+    /// Queue `msg` for the writer. Once the client is stalled nothing more
+    /// is queued; the connection is on its way down.
+    pub fn push(&self, msg: Arc<ServerMsg>, now: Instant) {
+        let mut s = self.state.lock().unwrap();
+        if s.closed || s.stalled {
+            return;
+        }
+        if matches!(*msg, ServerMsg::Snapshot(_)) {
+            let stale = s
+                .queue
+                .iter()
+                .position(|m| matches!(**m, ServerMsg::Snapshot(_)));
+            if let Some(i) = stale {
+                s.queue.remove(i);
+                self.metrics
+                    .snapshots_dropped
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        s.queue.push_back(msg);
+        if s.queue.len() >= self.max_queued {
+            s.saturated_since.get_or_insert(now);
+        }
+        let stalled = s
+            .saturated_since
+            .is_some_and(|since| now.saturating_duration_since(since) >= self.max_saturated);
+        if stalled {
+            s.stalled = true;
+            self.metrics
+                .slow_clients_dropped
+                .fetch_add(1, Ordering::Relaxed);
+            self.stall.notify_one();
+        }
+        drop(s);
+        self.ready.notify_one();
+    }
+
+    /// The bridge missed `n` broadcasts. They're gone; just count them.
+    pub fn lagged(&self, n: u64) {
+        self.metrics
+            .broadcast_lagged
+            .fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Next message to write, oldest first. `None` once the outbox is
+    /// closed and everything queued before that has been handed out.
+    pub async fn next(&self) -> Option<Arc<ServerMsg>> {
+        loop {
+            {
+                let mut s = self.state.lock().unwrap();
+                if let Some(msg) = s.queue.pop_front() {
+                    // Reading a frame now and then isn't keeping up; only
+                    // a queue that has mostly drained stops the clock.
+                    if s.queue.len() < self.max_queued / 2 {
+                        s.saturated_since = None;
+                    }
+                    return Some(msg);
+                }
+                if s.closed {
+                    return None;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    /// The writer put `msg` on the wire as a frame of `bytes` bytes.
+    pub fn written(&self, msg: &ServerMsg, bytes: usize) {
+        if matches!(msg, ServerMsg::Snapshot(_)) {
+            self.metrics.snapshot_sent(bytes);
+        }
+    }
+
+    /// Stop taking messages. The writer still gets what's already queued.
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_one();
+    }
+
+    /// Resolves once the queue has stayed saturated for `max_saturated`.
+    pub async fn stalled(&self) {
+        self.stall.notified().await;
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
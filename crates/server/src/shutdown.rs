@@ -0,0 +1,59 @@
+//! Asking a running server to stop. Hand a [`Shutdown`] in through
+//! `ServerConfig::shutdown` and keep a clone; [`Shutdown::trigger`] makes
+//! the server stop accepting, warn every connection, play on for the grace
+//! period, bank the runs still in progress, close each socket with a close
+//! frame and return from `run_with_config`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    requested: Arc<watch::Sender<Option<Request>>>,
+}
+
+/// Why the server is stopping, and how long players get to wrap up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub reason: String,
+    pub grace: Duration,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Shutdown {
+            requested: Arc::new(watch::channel(None).0),
+        }
+    }
+}
+
+impl Shutdown {
+    /// Start shutting down. Later calls are ignored; the first reason and
+    /// grace period stand.
+    pub fn trigger(&self, reason: impl Into<String>, grace: Duration) {
+        self.requested.send_if_modified(|requested| {
+            if requested.is_some() {
+                return false;
+            }
+            *requested = Some(Request {
+                reason: reason.into(),
+                grace,
+            });
+            true
+        });
+    }
+
+    /// Resolves once `trigger` has been called, straight away if it already
+    /// has.
+    pub async fn requested(&self) -> Request {
+        let mut rx = self.requested.subscribe();
+        // The sender lives in `self`, so the channel can't close under us.
+        let requested = rx
+            .wait_for(Option::is_some)
+            .await
+            .expect("shutdown channel closed");
+        requested.clone().expect("waited for Some")
+    }
+}
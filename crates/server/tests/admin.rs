@@ -0,0 +1,302 @@
+//! Operator commands over `ClientMsg::Admin`: only with the right token,
+//! and each one reaching the world or the connections it names.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{
+    connect, join, kick_reason, next_matching, next_snapshot, operated, send, start, TOKEN,
+};
+use protocol::{AdminCommand, ClientMsg, ServerMsg};
+use server::limits::Limits;
+use server::ServerConfig;
+use sim::util::WireVec2;
+use sim::EntityKind;
+
+/// One-off admin connection, as `icarust-admin` makes. Returns the reply.
+async fn admin(url: &str, token: &str, line: &str) -> (bool, String) {
+    send_admin(url, token, server::admin::parse(line).unwrap()).await
+}
+
+/// `admin` for a command `parse` wouldn't let through.
+async fn send_admin(url: &str, token: &str, command: AdminCommand) -> (bool, String) {
+    let msg = ClientMsg::Admin {
+        token: token.into(),
+        command,
+    };
+    let mut ws = connect(url, &msg).await;
+    next_matching(&mut ws, |msg| match msg {
+        ServerMsg::AdminReply { ok, message } => Some((ok, message)),
+        _ => None,
+    })
+    .await
+}
+
+#[test]
+fn command_lines_parse() {
+    use server::admin::parse;
+    assert_eq!(
+        parse("god Red Baron off"),
+        Ok(AdminCommand::God {
+            name: "Red Baron".into(),
+            on: Some(false),
+        })
+    );
+    assert_eq!(
+        parse("god ace"),
+        Ok(AdminCommand::God {
+            name: "ace".into(),
+            on: None,
+        })
+    );
+    assert_eq!(
+        parse("spawn tanks 3 100 -20.5"),
+        Ok(AdminCommand::Spawn {
+            kind: EntityKind::Tank,
+            count: 3,
+            pos: WireVec2 { x: 100.0, y: -20.5 },
+        })
+    );
+    assert_eq!(
+        parse("  say  hold the line  "),
+        Ok(AdminCommand::Broadcast {
+            text: "hold the line".into(),
+        })
+    );
+    assert_eq!(parse("speed 0.5"), Ok(AdminCommand::TimeScale { scale: 0.5 }));
+    for bad in [
+        "",
+        "kick",
+        "level high",
+        "spawn convoy 1 0 0",
+        "spawn enemy 0 0 0",
+        "spawn enemy 1 nan inf",
+        "speed nan",
+        "speed -inf",
+        "fly",
+    ] {
+        assert!(parse(bad).is_err(), "{bad:?} parsed");
+    }
+}
+
+#[tokio::test]
+async fn commands_need_the_admin_token() {
+    let (url, server) = start(operated()).await;
+    let (ok, message) = admin(&url, "guess", "level 9").await;
+    assert!(!ok);
+    assert_eq!(message, "bad admin token");
+
+    // Without a configured token nothing gets in.
+    let (open_url, open) = start(ServerConfig::default()).await;
+    assert!(!admin(&open_url, "", "pause").await.0);
+
+    server.abort();
+    open.abort();
+}
+
+#[tokio::test]
+async fn kicked_pilots_leave_and_banned_names_stay_out() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "Ace").await;
+    let mut rook = join(&url, "rook").await;
+    next_snapshot(&mut ace).await;
+    next_snapshot(&mut rook).await;
+
+    assert_eq!(
+        admin(&url, TOKEN, "kick ace").await,
+        (true, "kicked ace".into())
+    );
+    assert_eq!(kick_reason(&mut ace).await, "kicked by an operator");
+    assert_eq!(
+        admin(&url, TOKEN, "kick nobody").await,
+        (false, "no pilot named nobody".into())
+    );
+
+    assert!(admin(&url, TOKEN, "ban Rook").await.0);
+    assert_eq!(kick_reason(&mut rook).await, "banned from this server");
+    let mut again = join(&url, "ROOK").await;
+    assert_eq!(kick_reason(&mut again).await, "banned from this server");
+
+    // A kick isn't a ban.
+    let mut ace = join(&url, "ace").await;
+    next_snapshot(&mut ace).await;
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn names_match_in_any_case_beyond_ascii() {
+    let (url, server) = start(operated()).await;
+    let mut elan = join(&url, "\u{c9}lan").await;
+    next_snapshot(&mut elan).await;
+
+    assert!(admin(&url, TOKEN, "god \u{e9}LAN on").await.0);
+    assert!(admin(&url, TOKEN, "ban \u{c9}LAN").await.0);
+    assert_eq!(kick_reason(&mut elan).await, "banned from this server");
+    let mut again = join(&url, "\u{e9}lan").await;
+    assert_eq!(kick_reason(&mut again).await, "banned from this server");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn shared_names_are_refused_rather_than_guessed() {
+    let (url, server) = start(operated()).await;
+    let mut first = join(&url, "pilot").await;
+    let mut second = join(&url, "Pilot").await;
+    next_snapshot(&mut first).await;
+    next_snapshot(&mut second).await;
+
+    for line in ["kick pilot", "ban pilot", "god pilot"] {
+        assert_eq!(
+            admin(&url, TOKEN, line).await,
+            (false, "2 pilots are named pilot".into()),
+            "{line}"
+        );
+    }
+    // The refused ban didn't stick either.
+    let mut third = join(&url, "pilot").await;
+    next_snapshot(&mut third).await;
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn world_commands_reach_the_world() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "ace").await;
+    next_snapshot(&mut ace).await;
+
+    assert!(admin(&url, TOKEN, "level 6").await.0);
+    let (ok, message) = admin(&url, TOKEN, "spawn tank 4 500 300").await;
+    assert!(ok, "{message}");
+    assert!(admin(&url, TOKEN, "god ace on").await.0);
+    assert!(admin(&url, TOKEN, "seed 1234").await.0);
+    // The first may have been queued before the commands landed.
+    next_snapshot(&mut ace).await;
+    let snap = next_snapshot(&mut ace).await;
+    assert_eq!(snap.level, 6);
+    let tanks = snap
+        .entities
+        .iter()
+        .filter(|e| e.kind == EntityKind::Tank)
+        .count();
+    assert!(tanks >= 4, "{tanks} tanks");
+
+    assert_eq!(
+        admin(&url, TOKEN, "say eyes up").await,
+        (true, "sent".into())
+    );
+    let text = next_matching(&mut ace, |msg| match msg {
+        ServerMsg::Announcement { text } => Some(text),
+        _ => None,
+    })
+    .await;
+    assert_eq!(text, "eyes up");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn pause_stops_the_clock_but_not_the_snapshots() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "ace").await;
+    next_snapshot(&mut ace).await;
+
+    assert!(admin(&url, TOKEN, "pause").await.0);
+    // Let anything from before the pause go by.
+    next_snapshot(&mut ace).await;
+    let snap = next_snapshot(&mut ace).await;
+    assert!(snap.paused);
+    let frozen = snap.tick;
+    for _ in 0..3 {
+        assert_eq!(next_snapshot(&mut ace).await.tick, frozen);
+    }
+
+    assert!(admin(&url, TOKEN, "resume").await.0);
+    next_snapshot(&mut ace).await;
+    assert!(next_snapshot(&mut ace).await.tick.0 > frozen.0);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn non_finite_speeds_leave_the_clock_alone() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "ace").await;
+    next_snapshot(&mut ace).await;
+
+    let nan = AdminCommand::TimeScale { scale: f32::NAN };
+    let (_, message) = send_admin(&url, TOKEN, nan).await;
+    assert_eq!(message, "time scale 1");
+    next_snapshot(&mut ace).await;
+    assert_eq!(next_snapshot(&mut ace).await.time_scale, 1.0);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn spawns_must_land_inside_the_world() {
+    let (url, server) = start(operated()).await;
+    for (x, y) in [(f32::NAN, 300.0), (500.0, f32::INFINITY), (-1.0e9, 300.0)] {
+        let spawn = AdminCommand::Spawn {
+            kind: EntityKind::Enemy,
+            count: 1,
+            pos: WireVec2 { x, y },
+        };
+        let (ok, message) = send_admin(&url, TOKEN, spawn).await;
+        assert!(!ok, "spawned at ({x}, {y})");
+        assert!(message.contains("outside the world"), "{message}");
+    }
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn guessing_the_token_in_game_gets_the_pilot_kicked() {
+    let (url, server) = start(operated()).await;
+    let mut mallory = join(&url, "mallory").await;
+    next_snapshot(&mut mallory).await;
+
+    for n in 0..server::limits::Limits::default().max_invalid {
+        let guess = ClientMsg::Admin {
+            token: format!("guess {n}"),
+            command: AdminCommand::Pause,
+        };
+        send(&mut mallory, &guess).await;
+    }
+    assert_eq!(kick_reason(&mut mallory).await, "too many invalid messages");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn guessing_addresses_are_locked_out_across_connections() {
+    let limits = Limits {
+        admin_tries: 2,
+        admin_lockout: Duration::from_millis(500),
+        ..Limits::default()
+    };
+    let (url, server) = start(ServerConfig { limits, ..operated() }).await;
+    let bad = (false, "bad admin token".to_string());
+    let locked = (false, "too many bad admin tokens, try again later".to_string());
+
+    // Each guess on a connection of its own.
+    for _ in 0..2 {
+        assert_eq!(admin(&url, "guess", "pause").await, bad);
+    }
+    assert_eq!(admin(&url, TOKEN, "pause").await, locked, "right token or not");
+
+    // Waiting doesn't wipe the slate: the next miss locks it out again,
+    // for twice as long.
+    tokio::time::sleep(Duration::from_millis(650)).await;
+    assert_eq!(admin(&url, "guess", "pause").await, bad);
+    assert_eq!(admin(&url, TOKEN, "pause").await, locked);
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(admin(&url, TOKEN, "pause").await, locked);
+    tokio::time::sleep(Duration::from_millis(550)).await;
+    assert_eq!(admin(&url, TOKEN, "pause").await, (true, "paused".into()));
+
+    server.abort();
+}
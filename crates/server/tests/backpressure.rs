@@ -0,0 +1,119 @@
+//! A client that stops reading can't grow the server's memory: its outbox
+//! keeps only the newest snapshot, and the connection is dropped once the
+//! queue has stayed saturated for too long. Events are never lost on the
+//! way: the client gets every one, or is disconnected.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::FutureExt;
+use protocol::{ServerMsg, Snapshot};
+use server::limits::Limits;
+use server::metrics::Metrics;
+use server::outbox::Outbox;
+use sim::{Tick, World};
+
+fn snapshot(tick: u64) -> Arc<ServerMsg> {
+    let mut snap: Snapshot = protocol::snapshot_from_world(&World::new(Default::default()));
+    snap.tick = Tick(tick);
+    Arc::new(ServerMsg::Snapshot(snap))
+}
+
+fn events(tick: u64) -> Arc<ServerMsg> {
+    Arc::new(ServerMsg::Events {
+        tick: Tick(tick),
+        events: Vec::new(),
+    })
+}
+
+fn tick_of(msg: &ServerMsg) -> u64 {
+    match msg {
+        ServerMsg::Snapshot(s) => s.tick.0,
+        ServerMsg::Events { tick, .. } => tick.0,
+        other => panic!("unexpected {other:?}"),
+    }
+}
+
+fn limits(max_queued: usize, max_saturated: Duration) -> Limits {
+    Limits {
+        max_queued,
+        max_saturated,
+        ..Limits::default()
+    }
+}
+
+#[tokio::test]
+async fn only_the_newest_snapshot_waits_but_every_event_does() {
+    let metrics = Arc::new(Metrics::default());
+    let outbox = Outbox::new(&limits(16, Duration::from_secs(5)), metrics.clone());
+    let now = Instant::now();
+    for tick in 1..=6 {
+        outbox.push(events(tick), now);
+        outbox.push(snapshot(tick), now);
+    }
+    outbox.close();
+
+    let mut ticks = Vec::new();
+    while let Some(msg) = outbox.next().await {
+        ticks.push((matches!(*msg, ServerMsg::Snapshot(_)), tick_of(&msg)));
+    }
+    let event_ticks: Vec<u64> = ticks.iter().filter(|(s, _)| !s).map(|(_, t)| *t).collect();
+    let snapshot_ticks: Vec<u64> = ticks.iter().filter(|(s, _)| *s).map(|(_, t)| *t).collect();
+    assert_eq!(event_ticks, vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(snapshot_ticks, vec![6]);
+    assert_eq!(metrics.snapshots_dropped.load(Ordering::Relaxed), 5);
+}
+
+#[tokio::test]
+async fn a_queue_that_stays_saturated_stalls_the_client() {
+    let metrics = Arc::new(Metrics::default());
+    let outbox = Outbox::new(&limits(4, Duration::from_secs(1)), metrics.clone());
+    let start = Instant::now();
+    for tick in 0..8 {
+        outbox.push(events(tick), start);
+    }
+    assert_eq!(outbox.len(), 8, "events are never dropped");
+    assert!(
+        outbox.stalled().now_or_never().is_none(),
+        "not saturated for long yet"
+    );
+
+    // Reading the odd frame doesn't help while the queue stays over half
+    // full.
+    let mut read = vec![tick_of(&outbox.next().await.unwrap())];
+    outbox.push(events(8), start + Duration::from_millis(900));
+    assert!(outbox.stalled().now_or_never().is_none());
+    read.push(tick_of(&outbox.next().await.unwrap()));
+    outbox.push(events(9), start + Duration::from_millis(1100));
+    assert!(outbox.stalled().now_or_never().is_some());
+    assert_eq!(metrics.slow_clients_dropped.load(Ordering::Relaxed), 1);
+
+    // Everything queued before the stall still goes out, in order.
+    outbox.close();
+    while let Some(msg) = outbox.next().await {
+        read.push(tick_of(&msg));
+    }
+    assert_eq!(read, (0..10).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn draining_below_half_stops_the_clock() {
+    let metrics = Arc::new(Metrics::default());
+    let outbox = Outbox::new(&limits(4, Duration::from_secs(1)), metrics.clone());
+    let start = Instant::now();
+    for tick in 0..4 {
+        outbox.push(events(tick), start);
+    }
+    for _ in 0..3 {
+        outbox.next().await.unwrap();
+    }
+
+    // Full again, but the clock restarted when it drained.
+    for tick in 4..8 {
+        outbox.push(events(tick), start + Duration::from_millis(900));
+    }
+    outbox.push(events(8), start + Duration::from_millis(1500));
+    assert!(outbox.stalled().now_or_never().is_none());
+    assert_eq!(metrics.slow_clients_dropped.load(Ordering::Relaxed), 0);
+}
@@ -0,0 +1,103 @@
+//! Chat: lines are cleaned up and sent to everyone under the sender's
+//! name, chatting too fast gets lines refused, and the server posts its own
+//! notices when pilots come and go.
+
+mod common;
+
+use common::{join, next_matching, send, start, Ws};
+use protocol::{ClientMsg, ServerMsg};
+use server::chat::sanitize;
+use server::ServerConfig;
+
+/// Next chat line, as `(from, text)`.
+async fn next_chat(ws: &mut Ws) -> (Option<String>, String) {
+    next_matching(ws, |msg| match msg {
+        ServerMsg::Chat { from, text } => Some((from, text)),
+        _ => None,
+    })
+    .await
+}
+
+fn say(text: &str) -> ClientMsg {
+    ClientMsg::Chat { text: text.into() }
+}
+
+#[test]
+fn sanitize_strips_spoofing_and_masks_swears() {
+    assert_eq!(
+        sanitize("  hi\u{202E}  there\n\tpilot ", 160).as_deref(),
+        Some("hi there pilot")
+    );
+    assert_eq!(
+        sanitize("z\u{0301}\u{0302}\u{0303}a\u{200B}lgo", 160).as_deref(),
+        Some("zalgo")
+    );
+    assert_eq!(
+        sanitize("well SHITTY flying", 160).as_deref(),
+        Some("well ****** flying")
+    );
+    assert_eq!(sanitize("scunthorpe", 160).as_deref(), Some("scunthorpe"));
+    assert_eq!(sanitize("héllo wörld", 5).as_deref(), Some("héllo"));
+    assert_eq!(sanitize(" \u{200B}\u{0007} ", 160), None);
+}
+
+#[tokio::test]
+async fn chat_reaches_everyone_under_the_senders_name() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let mut ace = join(&url, "ace").await;
+    assert_eq!(next_chat(&mut ace).await, (None, "ace joined".into()));
+    let mut rook = join(&url, "rook").await;
+    assert_eq!(next_chat(&mut ace).await, (None, "rook joined".into()));
+    assert_eq!(next_chat(&mut rook).await, (None, "rook joined".into()));
+
+    send(&mut rook, &say("  bandits\u{202E} at   six ")).await;
+    let line = (Some("rook".to_string()), "bandits at six".to_string());
+    assert_eq!(next_chat(&mut ace).await, line);
+    assert_eq!(next_chat(&mut rook).await, line);
+
+    send(&mut rook, &ClientMsg::Bye).await;
+    assert_eq!(next_chat(&mut ace).await, (None, "rook left".into()));
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn chatting_too_fast_gets_lines_refused() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let mut ace = join(&url, "ace").await;
+    next_chat(&mut ace).await;
+
+    // The default burst is four lines.
+    for n in 0..6 {
+        send(&mut ace, &say(&format!("spam {n}"))).await;
+    }
+    let mut lines = Vec::new();
+    for _ in 0..6 {
+        lines.push(next_chat(&mut ace).await);
+    }
+    let sent: Vec<&str> = lines
+        .iter()
+        .filter(|(from, _)| from.is_some())
+        .map(|(_, text)| text.as_str())
+        .collect();
+    assert_eq!(sent, ["spam 0", "spam 1", "spam 2", "spam 3"]);
+    let refused = lines.iter().filter(|(from, _)| from.is_none()).count();
+    assert_eq!(refused, 2);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn names_are_cleaned_up_and_cut_short_at_join() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let mut ace = join(&url, "  ace\u{202E} of\n spades, the long way round").await;
+    assert_eq!(next_chat(&mut ace).await, (None, "ace of spades, t joined".into()));
+    send(&mut ace, &say("hi")).await;
+    let (from, _) = next_chat(&mut ace).await;
+    assert_eq!(from.as_deref(), Some("ace of spades, t"));
+
+    let mut blank = join(&url, "\u{200B}").await;
+    assert_eq!(next_chat(&mut blank).await, (None, "pilot joined".into()));
+
+    server.abort();
+}
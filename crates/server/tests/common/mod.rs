@@ -0,0 +1,134 @@
+//! Helpers the server's integration tests share: run a server on a free
+//! port, connect to it as a pilot, and read what it sends back. Every read
+//! gives up after three seconds so a missing message fails the test rather
+//! than hanging it.
+
+// Each test binary uses its own subset.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, ServerMsg, Snapshot};
+use server::ServerConfig;
+use sim::{PlayerId, ShipClass};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+pub type Ws = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Admin token for `operated`.
+pub const TOKEN: &str = "hunter2";
+
+/// The stock server with `TOKEN` as its admin token.
+pub fn operated() -> ServerConfig {
+    ServerConfig {
+        admin_token: Some(TOKEN.into()),
+        ..ServerConfig::default()
+    }
+}
+
+/// Run a server with `config` on a free port. Returns its URL and the task
+/// to abort once the test is done with it.
+pub async fn start(config: ServerConfig) -> (String, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let _ = server::run_with_config(listener, config).await;
+    });
+    (url, server)
+}
+
+pub async fn send(ws: &mut Ws, msg: &ClientMsg) {
+    ws.send(Message::Binary(protocol::encode(msg)))
+        .await
+        .unwrap();
+}
+
+pub fn hello(name: &str) -> ClientMsg {
+    ClientMsg::Hello {
+        name: name.into(),
+        ship_class: ShipClass::Fighter,
+    }
+}
+
+/// Open a connection and send `first`, without waiting for an answer.
+pub async fn connect(url: &str, first: &ClientMsg) -> Ws {
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    send(&mut ws, first).await;
+    ws
+}
+
+/// Join as a pilot called `name`. The `Welcome` is still to be read.
+pub async fn join(url: &str, name: &str) -> Ws {
+    connect(url, &hello(name)).await
+}
+
+/// Read until `pick` accepts a message.
+pub async fn next_matching<T>(ws: &mut Ws, mut pick: impl FnMut(ServerMsg) -> Option<T>) -> T {
+    timeout(Duration::from_secs(3), async {
+        while let Some(Ok(frame)) = ws.next().await {
+            let Message::Binary(b) = frame else { continue };
+            if let Some(found) = pick(protocol::decode(&b).unwrap()) {
+                return found;
+            }
+        }
+        panic!("stream ended");
+    })
+    .await
+    .expect("timed out")
+}
+
+/// Wait for the `Welcome`: the id the connection speaks for, its resume
+/// token and the first snapshot.
+pub async fn welcome(ws: &mut Ws) -> (PlayerId, u64, Snapshot) {
+    next_matching(ws, |msg| match msg {
+        ServerMsg::Welcome {
+            player_id,
+            resume_token,
+            snapshot,
+            ..
+        } => Some((player_id, resume_token, snapshot)),
+        _ => None,
+    })
+    .await
+}
+
+/// Next snapshot, the one in the `Welcome` included.
+pub async fn next_snapshot(ws: &mut Ws) -> Snapshot {
+    next_snapshot_where(ws, |_| true).await
+}
+
+/// First snapshot (the `Welcome`'s included) that satisfies `pred`.
+pub async fn next_snapshot_where(ws: &mut Ws, pred: impl Fn(&Snapshot) -> bool) -> Snapshot {
+    next_matching(ws, |msg| match msg {
+        ServerMsg::Welcome { snapshot, .. } | ServerMsg::Snapshot(snapshot) => {
+            pred(&snapshot).then_some(snapshot)
+        }
+        _ => None,
+    })
+    .await
+}
+
+/// Read until the server kicks us, and return the reason. Sends that fail
+/// because the server already hung up are fine; the `Kicked` message is
+/// still waiting in our receive buffer.
+pub async fn kick_reason(ws: &mut Ws) -> String {
+    next_matching(ws, |msg| match msg {
+        ServerMsg::Kicked { reason } => Some(reason),
+        _ => None,
+    })
+    .await
+}
+
+/// Next server notice, skipping pilots' own chat.
+pub async fn next_notice(ws: &mut Ws) -> String {
+    next_matching(ws, |msg| match msg {
+        ServerMsg::Chat { from: None, text } => Some(text),
+        _ => None,
+    })
+    .await
+}
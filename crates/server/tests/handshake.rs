@@ -2,12 +2,16 @@
 //! drive a fake client through the WebSocket, and assert the protocol
 //! contract holds.
 
+mod common;
+
 use std::time::Duration;
 
+use common::{join, next_snapshot, start, welcome};
 use futures_util::{SinkExt, StreamExt};
-use protocol::{ClientMsg, ServerMsg};
+use protocol::{ClientMsg, ServerMsg, MAX_NAME_CHARS};
+use server::ServerConfig;
 use sim::entity::ShotOwner;
-use sim::{PlayerInput, Tick};
+use sim::{PlayerInput, ShipClass, Tick};
 use tokio::net::TcpListener;
 use tokio::time::timeout;
 use tokio_tungstenite::tungstenite::Message;
@@ -30,6 +34,7 @@ async fn hello_yields_welcome_and_snapshots() {
     // Hello.
     ws.send(Message::Binary(protocol::encode(&ClientMsg::Hello {
         name: "tester".into(),
+        ship_class: ShipClass::Gunship,
     })))
     .await
     .unwrap();
@@ -50,6 +55,12 @@ async fn hello_yields_welcome_and_snapshots() {
             player_id, snapshot, ..
         } => {
             assert!(!snapshot.entities.is_empty(), "welcome snapshot should include rocks");
+            let ship = snapshot
+                .entities
+                .iter()
+                .find(|e| matches!(e.kind, sim::EntityKind::Player { player_id: p } if p == player_id))
+                .expect("welcome snapshot should include the joining ship");
+            assert_eq!(ship.ship_class, ShipClass::Gunship, "Hello loadout should be applied");
             player_id
         }
         other => panic!("expected Welcome, got {other:?}"),
@@ -105,7 +116,13 @@ async fn hello_yields_welcome_and_snapshots() {
                     saw_snapshot_with_player = true;
                 }
             }
-            ServerMsg::Welcome { .. } => {}
+            ServerMsg::Welcome { .. }
+            | ServerMsg::Leaderboard(_)
+            | ServerMsg::AdminReply { .. }
+            | ServerMsg::Announcement { .. }
+            | ServerMsg::Chat { .. }
+            | ServerMsg::Shutdown { .. } => {}
+            ServerMsg::Kicked { reason } => panic!("kicked: {reason}"),
         }
     }
 
@@ -116,3 +133,18 @@ async fn hello_yields_welcome_and_snapshots() {
     let _ = ws.close(None).await;
     server.abort();
 }
+
+#[tokio::test]
+async fn snapshots_carry_names_cut_to_the_cap() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let mut ws = join(&url, &"long".repeat(MAX_NAME_CHARS)).await;
+    let (pid, _, _) = welcome(&mut ws).await;
+
+    // Every client's scoreboard lists it, so it mustn't run on forever.
+    let snap = next_snapshot(&mut ws).await;
+    let (_, name) = snap.player_names.iter().find(|(p, _)| *p == pid).unwrap();
+    assert_eq!(name.chars().count(), MAX_NAME_CHARS);
+    assert!(snap.pilot_stats.iter().any(|(p, _)| *p == pid));
+
+    server.abort();
+}
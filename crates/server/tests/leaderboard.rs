@@ -0,0 +1,108 @@
+//! High-score table: persistence across reopen, and serving the table to a
+//! client that asks for it.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, LeaderboardEntry, ServerMsg};
+use server::leaderboard::Leaderboard;
+use server::ServerConfig;
+use sim::ShipClass;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+
+fn entry(name: &str, score: i32) -> LeaderboardEntry {
+    LeaderboardEntry {
+        name: name.into(),
+        score,
+        level: 1,
+        enemy_kills: 1,
+        tank_kills: 0,
+        pilot_kills: 0,
+        duration_secs: 30.0,
+    }
+}
+
+fn temp_path(tag: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "icarust-leaderboard-{tag}-{}.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn table_survives_reopen_sorted_best_first() {
+    let path = temp_path("reopen");
+    let mut board = Leaderboard::open(path.clone()).unwrap();
+    board.record(entry("low", 100));
+    board.record(entry("high", 900));
+    board.record(entry("mid", 400));
+    board.close().await;
+
+    let board = Leaderboard::open(path.clone()).unwrap();
+    let names: Vec<_> = board.top(10).into_iter().map(|e| e.name).collect();
+    assert_eq!(names, vec!["high", "mid", "low"]);
+    assert_eq!(board.top(1).len(), 1);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn request_leaderboard_returns_the_saved_table() {
+    let path = temp_path("serve");
+    let mut seed = Leaderboard::open(path.clone()).unwrap();
+    seed.record(entry("ace", 1200));
+    seed.record(entry("rookie", 150));
+    seed.close().await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let config = ServerConfig {
+        leaderboard_path: Some(path.clone()),
+        ..ServerConfig::default()
+    };
+    let server = tokio::spawn(async move {
+        let _ = server::run_with_config(listener, config).await;
+    });
+
+    let (mut ws, _) = timeout(
+        Duration::from_secs(2),
+        tokio_tungstenite::connect_async(format!("ws://{addr}")),
+    )
+    .await
+    .expect("connect timed out")
+    .expect("connect failed");
+    ws.send(Message::Binary(protocol::encode(&ClientMsg::Hello {
+        name: "tester".into(),
+        ship_class: ShipClass::Fighter,
+    })))
+    .await
+    .unwrap();
+    ws.send(Message::Binary(protocol::encode(
+        &ClientMsg::RequestLeaderboard,
+    )))
+    .await
+    .unwrap();
+
+    // Skip the welcome and any snapshots that race ahead of the reply.
+    let entries = timeout(Duration::from_secs(2), async {
+        while let Some(frame) = ws.next().await {
+            if let Message::Binary(b) = frame.unwrap() {
+                if let ServerMsg::Leaderboard(entries) = protocol::decode(&b).unwrap() {
+                    return entries;
+                }
+            }
+        }
+        panic!("stream ended before the leaderboard arrived");
+    })
+    .await
+    .expect("leaderboard timed out");
+
+    assert_eq!(entries, vec![entry("ace", 1200), entry("rookie", 150)]);
+
+    server.abort();
+    let _ = std::fs::remove_file(&path);
+}
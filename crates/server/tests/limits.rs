@@ -0,0 +1,99 @@
+//! Per-connection limits: out-of-range axes are clamped, and clients that
+//! flood, send oversized frames or keep sending garbage are kicked with a
+//! reason before the socket closes.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{kick_reason, next_snapshot, start, welcome, Ws};
+use futures_util::SinkExt;
+use protocol::ClientMsg;
+use server::ServerConfig;
+use sim::{EntityKind, PlayerId, PlayerInput, Tick};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Connect as a pilot and wait for the `Welcome`.
+async fn join(url: &str) -> (Ws, PlayerId) {
+    let mut ws = common::join(url, "mallory").await;
+    let (pid, _, _) = welcome(&mut ws).await;
+    (ws, pid)
+}
+
+fn input(xaxis: f32, yaxis: f32) -> Message {
+    Message::Binary(protocol::encode(&ClientMsg::Input {
+        tick: Tick(0),
+        input: PlayerInput {
+            xaxis,
+            yaxis,
+            fire: false,
+        },
+    }))
+}
+
+#[tokio::test]
+async fn out_of_range_axes_are_clamped() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let (mut ws, pid) = join(&url).await;
+
+    // Unclamped, a single tick of this would spin the nose through ~10^5
+    // radians.
+    for _ in 0..5 {
+        ws.send(input(1.0e7, -1.0e7)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let snap = next_snapshot(&mut ws).await;
+    let ship = snap
+        .entities
+        .iter()
+        .find(|e| matches!(e.kind, EntityKind::Player { player_id } if player_id == pid))
+        .expect("ship still flying");
+    assert!(ship.facing.abs() < 10.0, "facing {}", ship.facing);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn non_finite_input_gets_the_client_kicked() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let (mut ws, _) = join(&url).await;
+
+    for axis in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY]
+        .into_iter()
+        .cycle()
+        .take(32)
+    {
+        if ws.send(input(axis, 0.0)).await.is_err() {
+            break;
+        }
+    }
+    assert_eq!(kick_reason(&mut ws).await, "too many invalid messages");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn flooding_gets_the_client_kicked() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let (mut ws, _) = join(&url).await;
+
+    for _ in 0..2_000 {
+        if ws.send(input(0.0, 1.0)).await.is_err() {
+            break;
+        }
+    }
+    assert_eq!(kick_reason(&mut ws).await, "sending too many messages");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn oversized_frames_get_the_client_kicked() {
+    let (url, server) = start(ServerConfig::default()).await;
+    let (mut ws, _) = join(&url).await;
+
+    ws.send(Message::Binary(vec![0; 64 * 1024])).await.unwrap();
+    assert_eq!(kick_reason(&mut ws).await, "message too large");
+
+    server.abort();
+}
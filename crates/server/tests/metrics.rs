@@ -0,0 +1,115 @@
+//! The metrics endpoint: Prometheus text on `/metrics`, and a `/healthz`
+//! that tracks whether the game loop is ticking.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, ServerMsg};
+use server::metrics::Metrics;
+use server::ServerConfig;
+use sim::ShipClass;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Serve `metrics` on a fresh port and return its address.
+async fn serve_metrics(metrics: Arc<Metrics>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(server::metrics::serve(listener, metrics));
+    addr
+}
+
+/// Minimal HTTP GET. Returns the status line and the body.
+async fn get(addr: &str, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n");
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    timeout(Duration::from_secs(3), stream.read_to_string(&mut response))
+        .await
+        .expect("response timed out")
+        .unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").expect("no header end");
+    let status = head.lines().next().unwrap().to_string();
+    (status, body.to_string())
+}
+
+/// Value of the sample written exactly `name`, labels included.
+fn sample(body: &str, name: &str) -> f64 {
+    body.lines()
+        .find_map(|l| l.strip_prefix(name)?.strip_prefix(' '))
+        .unwrap_or_else(|| panic!("no {name} in:\n{body}"))
+        .parse()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn metrics_describe_the_running_game() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let metrics = Arc::new(Metrics::default());
+    let config = ServerConfig {
+        bot_fill: 3,
+        metrics: metrics.clone(),
+        ..ServerConfig::default()
+    };
+    let server = tokio::spawn(async move {
+        let _ = server::run_with_config(listener, config).await;
+    });
+    let addr = serve_metrics(metrics).await;
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    ws.send(Message::Binary(protocol::encode(&ClientMsg::Hello {
+        name: "ops".into(),
+        ship_class: ShipClass::Fighter,
+    })))
+    .await
+    .unwrap();
+    // Let a few snapshots go out after the join.
+    let mut snapshots = 0;
+    while snapshots < 3 {
+        let frame = timeout(Duration::from_secs(3), ws.next())
+            .await
+            .expect("no traffic")
+            .unwrap()
+            .unwrap();
+        if let Message::Binary(b) = frame {
+            if let ServerMsg::Snapshot(_) = protocol::decode(&b).unwrap() {
+                snapshots += 1;
+            }
+        }
+    }
+
+    let (status, body) = get(&addr, "/metrics").await;
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert_eq!(sample(&body, "icarust_players"), 1.0);
+    assert_eq!(sample(&body, "icarust_bots"), 2.0);
+    assert_eq!(sample(&body, "icarust_level"), 1.0);
+    assert!(sample(&body, "icarust_snapshots_total") >= 3.0);
+    assert!(sample(&body, "icarust_snapshot_bytes_total") > 0.0);
+    assert!(sample(&body, "icarust_tick_duration_seconds_count") >= 9.0);
+    assert_eq!(sample(&body, "icarust_entities{kind=\"player\"}"), 3.0);
+    assert!(body.contains("icarust_tick_duration_seconds_bucket{le=\"+Inf\"}"));
+    assert!(body.contains("# TYPE icarust_tick_overruns_total counter"));
+    assert!(body.contains("# TYPE icarust_broadcast_lagged_total counter"));
+
+    let (status, body) = get(&addr, "/healthz").await;
+    assert_eq!(
+        (status.as_str(), body.as_str()),
+        ("HTTP/1.1 200 OK", "ok\n")
+    );
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn healthz_fails_when_the_game_loop_is_not_ticking() {
+    let addr = serve_metrics(Arc::new(Metrics::default())).await;
+    let (status, _) = get(&addr, "/healthz").await;
+    assert_eq!(status, "HTTP/1.1 503 Service Unavailable");
+    let (status, _) = get(&addr, "/nope").await;
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+}
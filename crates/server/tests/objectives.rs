@@ -0,0 +1,36 @@
+//! Co-op objectives and teams show up in every client's snapshot.
+
+mod common;
+
+use common::{join, next_snapshot, start};
+use server::ServerConfig;
+use sim::{EntityKind, ObjectiveKind, WorldConfig};
+
+#[tokio::test]
+async fn convoy_objective_and_team_scores_reach_clients() {
+    let config = ServerConfig {
+        world: WorldConfig {
+            objective: Some(ObjectiveKind::DefendConvoy),
+            teams: 2,
+            ..WorldConfig::default()
+        },
+        ..ServerConfig::default()
+    };
+    let (url, server) = start(config).await;
+
+    let mut alice = join(&url, "alice").await;
+    let snap = next_snapshot(&mut alice).await;
+    let status = snap.objective.expect("objective status");
+    assert_eq!(status.kind, ObjectiveKind::DefendConvoy);
+    assert_eq!((status.round, status.outcome), (1, None));
+    assert!(snap.entities.iter().any(|e| e.kind == EntityKind::Convoy));
+    let me = snap
+        .entities
+        .iter()
+        .find(|e| matches!(e.kind, EntityKind::Player { .. }))
+        .unwrap();
+    assert_eq!(me.team, Some(0));
+    assert_eq!(snap.team_scores, vec![(0, 0)]);
+
+    server.abort();
+}
@@ -0,0 +1,104 @@
+//! Pausing by vote: the game pauses once most pilots ask for it, resumes
+//! when the majority goes, and an operator pause outlasts any vote.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{join, next_notice, next_snapshot, operated, send, start, TOKEN};
+use protocol::ClientMsg;
+use server::ServerConfig;
+
+fn vote(pause: bool) -> ClientMsg {
+    ClientMsg::VotePause { pause }
+}
+
+#[tokio::test]
+async fn most_pilots_must_vote_to_pause() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "ace").await;
+    assert_eq!(next_notice(&mut ace).await, "ace joined");
+    let mut rook = join(&url, "rook").await;
+    assert_eq!(next_notice(&mut ace).await, "rook joined");
+
+    // Half isn't a majority.
+    send(&mut ace, &vote(true)).await;
+    assert_eq!(next_notice(&mut ace).await, "ace votes to pause (1/2)");
+    assert!(!next_snapshot(&mut ace).await.paused);
+
+    send(&mut rook, &vote(true)).await;
+    assert_eq!(next_notice(&mut ace).await, "rook votes to pause (2/2)");
+    assert_eq!(next_notice(&mut ace).await, "Game paused");
+    let frozen = next_snapshot(&mut ace).await;
+    assert!(frozen.paused);
+    assert_eq!(next_snapshot(&mut ace).await.tick, frozen.tick);
+
+    // Rook leaving takes their vote along, but ace alone still wants it.
+    send(&mut rook, &ClientMsg::Bye).await;
+    assert_eq!(next_notice(&mut ace).await, "rook left");
+    assert!(next_snapshot(&mut ace).await.paused);
+
+    send(&mut ace, &vote(false)).await;
+    assert_eq!(next_notice(&mut ace).await, "Game resumed");
+    let snap = next_snapshot(&mut ace).await;
+    assert!(!snap.paused);
+    assert!(next_snapshot(&mut ace).await.tick.0 > snap.tick.0);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn votes_cant_lift_an_operator_pause() {
+    let (url, server) = start(operated()).await;
+    let mut ace = join(&url, "ace").await;
+    assert_eq!(next_notice(&mut ace).await, "ace joined");
+
+    let pause = ClientMsg::Admin {
+        token: TOKEN.into(),
+        command: protocol::AdminCommand::Pause,
+    };
+    send(&mut ace, &pause).await;
+    assert_eq!(next_notice(&mut ace).await, "Game paused");
+
+    send(&mut ace, &vote(true)).await;
+    send(&mut ace, &vote(false)).await;
+    // Both votes are in by the time this snapshot goes out.
+    next_snapshot(&mut ace).await;
+    assert!(next_snapshot(&mut ace).await.paused);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn dropped_pilots_lose_their_vote_and_time_out_while_paused() {
+    let (url, server) = start(ServerConfig {
+        resume_grace: Duration::from_secs(1),
+        ..operated()
+    })
+    .await;
+    let mut ace = join(&url, "ace").await;
+    assert_eq!(next_notice(&mut ace).await, "ace joined");
+    let mut rook = join(&url, "rook").await;
+    assert_eq!(next_notice(&mut ace).await, "rook joined");
+    send(&mut ace, &vote(true)).await;
+    assert_eq!(next_notice(&mut ace).await, "ace votes to pause (1/2)");
+    send(&mut rook, &vote(true)).await;
+    assert_eq!(next_notice(&mut ace).await, "rook votes to pause (2/2)");
+    assert_eq!(next_notice(&mut ace).await, "Game paused");
+
+    // Rook's socket drops: they're held for a resume, but their vote goes.
+    drop(rook);
+    assert_eq!(next_notice(&mut ace).await, "Game resumed");
+
+    // The grace period runs out on the wall clock, paused or not.
+    let pause = ClientMsg::Admin {
+        token: TOKEN.into(),
+        command: protocol::AdminCommand::Pause,
+    };
+    send(&mut ace, &pause).await;
+    assert_eq!(next_notice(&mut ace).await, "Game paused");
+    assert_eq!(next_notice(&mut ace).await, "rook left");
+    assert!(next_snapshot(&mut ace).await.paused);
+
+    server.abort();
+}
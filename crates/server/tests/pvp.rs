@@ -0,0 +1,41 @@
+//! Team deathmatch deals joining pilots onto opposite teams and tells
+//! every client who is on which.
+
+mod common;
+
+use common::{join, next_snapshot, start};
+use server::ServerConfig;
+use sim::{EntityKind, PvpRules, WorldConfig};
+
+#[tokio::test]
+async fn team_deathmatch_splits_pilots_across_teams() {
+    let config = ServerConfig {
+        world: WorldConfig {
+            pvp: Some(PvpRules {
+                hostile_waves: false,
+            }),
+            teams: 2,
+            ..WorldConfig::default()
+        },
+        ..ServerConfig::default()
+    };
+    let (url, server) = start(config).await;
+
+    let mut alice = join(&url, "alice").await;
+    let snap = next_snapshot(&mut alice).await;
+    assert_eq!(snap.teams.len(), 1);
+    assert!(
+        !snap
+            .entities
+            .iter()
+            .any(|e| matches!(e.kind, EntityKind::Enemy | EntityKind::Tank)),
+        "hostile waves are off"
+    );
+    let mut bob = join(&url, "bob").await;
+    let snap = next_snapshot(&mut bob).await;
+    let mut teams: Vec<u8> = snap.teams.iter().map(|(_, t)| *t).collect();
+    teams.sort();
+    assert_eq!(teams, vec![0, 1]);
+
+    server.abort();
+}
@@ -0,0 +1,108 @@
+//! A pilot whose socket drops can reconnect with their resume token and
+//! pick up the same ship and score, as long as they're back before the
+//! grace period runs out.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{hello, kick_reason, next_snapshot_where, send, start, welcome, Ws};
+use protocol::{ClientMsg, Snapshot};
+use server::ServerConfig;
+use sim::{PlayerId, ShipClass};
+
+/// Connect with `first` and wait for the `Welcome`.
+async fn connect(url: &str, first: ClientMsg) -> (Ws, PlayerId, u64) {
+    let mut ws = common::connect(url, &first).await;
+    let (pid, token, _) = welcome(&mut ws).await;
+    (ws, pid, token)
+}
+
+fn resume(name: &str, token: u64) -> ClientMsg {
+    ClientMsg::Resume {
+        token,
+        name: name.into(),
+        ship_class: ShipClass::Fighter,
+    }
+}
+
+fn has_pilot(snap: &Snapshot, pid: PlayerId) -> bool {
+    snap.player_names.iter().any(|(p, _)| *p == pid)
+}
+
+#[tokio::test]
+async fn dropped_pilot_resumes_with_the_same_id() {
+    let (url, server) = start(ServerConfig::default()).await;
+
+    let (ws, alice, token) = connect(&url, hello("alice")).await;
+    let (mut bob, _, _) = connect(&url, hello("bob")).await;
+
+    // Pull the plug without a Bye or close frame.
+    drop(ws);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let snap = next_snapshot_where(&mut bob, |_| true).await;
+    assert!(has_pilot(&snap, alice), "a dropped pilot is held, not removed");
+
+    let (_ws, resumed, resumed_token) = connect(&url, resume("alice", token)).await;
+    assert_eq!(resumed, alice);
+    assert_ne!(resumed_token, token, "a resume hands out a fresh token");
+    let snap = next_snapshot_where(&mut bob, |_| true).await;
+    assert_eq!(snap.player_names.len(), 2, "{:?}", snap.player_names);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn resuming_shuts_out_the_old_connection_and_its_token() {
+    let (url, server) = start(ServerConfig::default()).await;
+
+    // The old socket is still open when the client comes back on a new one.
+    let (mut old, alice, token) = connect(&url, hello("alice")).await;
+    let (mut new, resumed, new_token) = connect(&url, resume("alice", token)).await;
+    assert_eq!(resumed, alice);
+    assert_eq!(kick_reason(&mut old).await, "resumed from another connection");
+    let snap = next_snapshot_where(&mut new, |_| true).await;
+    assert!(has_pilot(&snap, alice), "the kick didn't take the pilot along");
+
+    // The spent token no longer names anyone; the new one does.
+    let (_stolen, thief, _) = connect(&url, resume("mallory", token)).await;
+    assert_ne!(thief, alice);
+    let (_again, again, _) = connect(&url, resume("alice", new_token)).await;
+    assert_eq!(again, alice);
+    assert_eq!(kick_reason(&mut new).await, "resumed from another connection");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn held_pilot_leaves_when_the_grace_period_runs_out() {
+    let (url, server) = start(ServerConfig {
+        resume_grace: Duration::from_millis(100),
+        ..ServerConfig::default()
+    })
+    .await;
+
+    let (ws, alice, token) = connect(&url, hello("alice")).await;
+    let (mut bob, _, _) = connect(&url, hello("bob")).await;
+    drop(ws);
+    next_snapshot_where(&mut bob, |s| !has_pilot(s, alice)).await;
+
+    // Too late: the token no longer matches anyone, so this is a fresh join.
+    let (_ws, rejoined, new_token) = connect(&url, resume("alice", token)).await;
+    assert_ne!(rejoined, alice);
+    assert_ne!(new_token, token);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn saying_bye_leaves_at_once() {
+    let (url, server) = start(ServerConfig::default()).await;
+
+    let (mut ws, alice, _) = connect(&url, hello("alice")).await;
+    let (mut bob, _, _) = connect(&url, hello("bob")).await;
+    send(&mut ws, &ClientMsg::Bye).await;
+    next_snapshot_where(&mut bob, |s| !has_pilot(s, alice)).await;
+
+    server.abort();
+}
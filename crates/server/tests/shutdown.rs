@@ -0,0 +1,67 @@
+//! Graceful shutdown through `ServerConfig::shutdown`: connections are
+//! warned, then closed with a close frame, new ones are refused, and
+//! `run_with_config` returns.
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use protocol::{ClientMsg, ServerMsg};
+use server::shutdown::Shutdown;
+use server::ServerConfig;
+use sim::ShipClass;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test]
+async fn shutdown_warns_closes_and_returns() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let shutdown = Shutdown::default();
+    let config = ServerConfig {
+        shutdown: shutdown.clone(),
+        ..ServerConfig::default()
+    };
+    let server = tokio::spawn(server::run_with_config(listener, config));
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    let hello = ClientMsg::Hello {
+        name: "ace".into(),
+        ship_class: ShipClass::Fighter,
+    };
+    ws.send(Message::Binary(protocol::encode(&hello)))
+        .await
+        .unwrap();
+
+    shutdown.trigger("maintenance", Duration::from_millis(300));
+    // A second trigger doesn't change the plan.
+    shutdown.trigger("something else", Duration::from_secs(60));
+
+    let mut warned = None;
+    let mut closed = false;
+    timeout(Duration::from_secs(5), async {
+        while let Some(frame) = ws.next().await {
+            match frame {
+                Ok(Message::Binary(b)) => {
+                    if let ServerMsg::Shutdown { reason, seconds } = protocol::decode(&b).unwrap() {
+                        warned = Some((reason, seconds));
+                    }
+                }
+                Ok(Message::Close(_)) => closed = true,
+                Ok(_) => {}
+                Err(e) => panic!("socket error instead of a clean close: {e}"),
+            }
+        }
+    })
+    .await
+    .expect("connection never closed");
+    assert_eq!(warned, Some(("maintenance".to_string(), 1)));
+    assert!(closed, "no close frame");
+
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("run_with_config didn't return")
+        .unwrap()
+        .unwrap();
+    assert!(tokio_tungstenite::connect_async(&url).await.is_err());
+}
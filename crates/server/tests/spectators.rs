@@ -0,0 +1,60 @@
+//! Spectators get the same broadcasts as pilots but never a ship.
+
+mod common;
+
+use common::{connect, hello, next_snapshot, next_snapshot_where, send, start};
+use protocol::{ClientMsg, Snapshot};
+use server::ServerConfig;
+use sim::{EntityKind, PlayerInput, Tick};
+
+fn pilots(snap: &Snapshot) -> usize {
+    snap.entities
+        .iter()
+        .filter(|e| matches!(e.kind, EntityKind::Player { .. }))
+        .count()
+}
+
+#[tokio::test]
+async fn spectators_watch_without_a_ship() {
+    let (url, server) = start(ServerConfig::default()).await;
+
+    let spectate = ClientMsg::Spectate {
+        name: "watcher".into(),
+    };
+    let mut watcher = connect(&url, &spectate).await;
+    let snap = next_snapshot(&mut watcher).await;
+    assert_eq!(snap.spectators, 1);
+    assert_eq!(pilots(&snap), 0);
+
+    // Flying and respawning are ignored; the world stays empty of pilots.
+    send(
+        &mut watcher,
+        &ClientMsg::Input {
+            tick: Tick(1),
+            input: PlayerInput {
+                xaxis: 1.0,
+                yaxis: 1.0,
+                fire: true,
+            },
+        },
+    )
+    .await;
+    send(&mut watcher, &ClientMsg::Respawn).await;
+    let snap = next_snapshot_where(&mut watcher, |s| s.tick.0 > 30).await;
+    assert_eq!(pilots(&snap), 0);
+    assert!(snap.player_names.is_empty());
+    assert_eq!(snap.level, 1);
+
+    // A pilot who joins shows up for the spectator, and sees them counted.
+    let mut alice = connect(&url, &hello("alice")).await;
+    let snap = next_snapshot(&mut alice).await;
+    assert_eq!(snap.spectators, 1);
+    let snap = next_snapshot_where(&mut watcher, |s| pilots(s) == 1).await;
+    assert_eq!(snap.player_names.len(), 1);
+
+    send(&mut watcher, &ClientMsg::Bye).await;
+    let snap = next_snapshot_where(&mut alice, |s| s.spectators == 0).await;
+    assert_eq!(pilots(&snap), 1, "a leaving spectator takes nobody with it");
+
+    server.abort();
+}
@@ -0,0 +1,42 @@
+//! Wingman bots top the game up to `bot_fill` pilots and make way for
+//! humans as they join.
+
+mod common;
+
+use common::{join, next_snapshot, next_snapshot_where, send, start};
+use protocol::{ClientMsg, Snapshot};
+use server::ServerConfig;
+
+fn name_of(snap: &Snapshot, pid: sim::PlayerId) -> &str {
+    &snap.player_names.iter().find(|(p, _)| *p == pid).unwrap().1
+}
+
+#[tokio::test]
+async fn bots_fill_empty_slots_and_yield_to_humans() {
+    let (url, server) = start(ServerConfig {
+        bot_fill: 2,
+        ..ServerConfig::default()
+    })
+    .await;
+
+    // One human: one bot keeps them company, scored and named.
+    let mut alice = join(&url, "alice").await;
+    let snap = next_snapshot(&mut alice).await;
+    assert_eq!(snap.bots.len(), 1, "{:?}", snap.player_names);
+    assert_eq!(name_of(&snap, snap.bots[0]), "WINGMAN 1");
+    assert!(snap.score_by_player.iter().any(|(p, _)| *p == snap.bots[0]));
+    assert_eq!(snap.score_by_player.len(), 2);
+
+    // A second human takes the bot's slot.
+    let mut bob = join(&url, "bob").await;
+    let snap = next_snapshot(&mut bob).await;
+    assert!(snap.bots.is_empty(), "{:?}", snap.player_names);
+    assert_eq!(snap.player_names.len(), 2);
+
+    // And when they leave, the bot comes back.
+    send(&mut bob, &ClientMsg::Bye).await;
+    let snap = next_snapshot_where(&mut alice, |s| s.bots.len() == 1).await;
+    assert_eq!(name_of(&snap, snap.bots[0]), "WINGMAN 1");
+
+    server.abort();
+}
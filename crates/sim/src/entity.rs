@@ -1,3 +1,4 @@
+use crate::loadout::ShipClass;
 use crate::util::Vec2;
 use serde::{Deserialize, Serialize};
 
@@ -20,25 +21,35 @@ impl Tick {
 /// variants don't credit anyone but still chip away at player HP. `Tank`
 /// is split out from `Enemy` so the client can render the heavier shell
 /// (bigger sprite, recognizable silhouette) and the server can apply the
-/// higher damage value associated with artillery.
+/// higher damage value associated with artillery. `Bomb` is the same
+/// split on the player side: a bomber's payload credits its pilot like a
+/// bullet but hits as hard as a shell.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ShotOwner {
     Player(PlayerId),
+    Bomb(PlayerId),
     Enemy,
     Tank,
 }
 
 impl ShotOwner {
-    /// Hostile shots damage the player by this many HP per hit. Player
-    /// shots don't hit other players, so the value for `Player(_)` is
-    /// inert (we still return `1` so calling code stays branch-free).
+    /// HP removed per hit. Hostile shots use this against players; player
+    /// shots use it against hostiles, and in PvP against rival pilots.
     pub fn damage(self) -> i16 {
         match self {
-            ShotOwner::Tank => 2,
+            ShotOwner::Tank | ShotOwner::Bomb(_) => 2,
             ShotOwner::Enemy | ShotOwner::Player(_) => 1,
         }
     }
 
+    /// Pilot credited for this shot's kills, if a player fired it.
+    pub fn player_id(self) -> Option<PlayerId> {
+        match self {
+            ShotOwner::Player(pid) | ShotOwner::Bomb(pid) => Some(pid),
+            ShotOwner::Enemy | ShotOwner::Tank => None,
+        }
+    }
+
     /// True if this shot was fired by a hostile entity (anything that
     /// can damage a player). Lets shared collision code treat all
     /// non-player ownerships uniformly.
@@ -56,6 +67,11 @@ pub enum EntityKind {
     /// Ground vehicle with a tracking turret. Body rolls on the terrain
     /// surface; turret rotates independently (`Entity::turret_facing`).
     Tank,
+    /// Objective escort: an unarmed ground convoy the pilots defend. Rolls
+    /// on the terrain like a tank.
+    Convoy,
+    /// Objective escort: a slow, unarmed bomber flying a fixed route.
+    Bomber,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -116,11 +132,27 @@ pub struct Entity {
     /// `player::apply_forces` applies gravity normally. Sim-only — never
     /// surfaces on the wire.
     pub gravity_armed: bool,
+    /// True while a player's wing is stalled under
+    /// `FlightModel::Aerodynamic` (see `player::update_stall`). Always false
+    /// under the arcade model and on non-player entities.
+    pub stalled: bool,
+    /// Loadout this player ship was spawned with. Non-player entities keep
+    /// the default and ignore it.
+    pub ship_class: ShipClass,
+    /// Gun heat in `[0, player::GUN_HEAT_MAX]`. Each trigger pull adds the
+    /// class's `heat_per_shot`; it bleeds off over time. Players only.
+    pub gun_heat: f32,
+    /// Seconds left on an overheat lockout; `> 0` means the gun won't fire.
+    pub gun_lock: f32,
+    /// Team of a player ship when the world deals pilots onto teams.
+    /// `None` on everything else.
+    pub team: Option<u8>,
 }
 
 impl Entity {
-    pub fn player(id: EntityId, player_id: PlayerId, pos: Vec2) -> Self {
-        let max_hp = crate::player::PLAYER_MAX_HP;
+    pub fn player(id: EntityId, player_id: PlayerId, pos: Vec2, ship_class: ShipClass) -> Self {
+        let stats = ship_class.stats();
+        let max_hp = stats.max_hp;
         Entity {
             id,
             kind: EntityKind::Player { player_id },
@@ -128,7 +160,7 @@ impl Entity {
             vel: Vec2::ZERO,
             facing: 0.0,
             turret_facing: 0.0,
-            bbox: crate::player::PLAYER_BBOX,
+            bbox: stats.bbox,
             alive: true,
             ttl: None,
             shot_cooldown: 0.0,
@@ -144,6 +176,11 @@ impl Entity {
             detonates_on_terrain: false,
             // Newly-spawned pilot: gravity stays off until they thrust.
             gravity_armed: false,
+            stalled: false,
+            ship_class,
+            gun_heat: 0.0,
+            gun_lock: 0.0,
+            team: None,
         }
     }
 
@@ -153,7 +190,7 @@ impl Entity {
         // life-time lets the shot still cover a meaningful distance even
         // after the pilot peels off.
         let ttl = match owner {
-            ShotOwner::Player(_) => crate::player::PLAYER_SHOT_LIFE,
+            ShotOwner::Player(_) | ShotOwner::Bomb(_) => crate::player::PLAYER_SHOT_LIFE,
             _ => crate::world::SHOT_LIFE,
         };
         Entity {
@@ -176,6 +213,11 @@ impl Entity {
             source: None,
             detonates_on_terrain: false,
             gravity_armed: true,
+            stalled: false,
+            ship_class: ShipClass::default(),
+            gun_heat: 0.0,
+            gun_lock: 0.0,
+            team: None,
         }
     }
 
@@ -225,6 +267,11 @@ impl Entity {
             source: None,
             detonates_on_terrain: false,
             gravity_armed: true,
+            stalled: false,
+            ship_class: ShipClass::default(),
+            gun_heat: 0.0,
+            gun_lock: 0.0,
+            team: None,
         }
     }
 
@@ -252,6 +299,34 @@ impl Entity {
             source: None,
             detonates_on_terrain: false,
             gravity_armed: true,
+            stalled: false,
+            ship_class: ShipClass::default(),
+            gun_heat: 0.0,
+            gun_lock: 0.0,
+            team: None,
+        }
+    }
+
+    /// Convoy escort for `ObjectiveKind::DefendConvoy`. Pinned to the
+    /// terrain by the world tick, like a tank.
+    pub fn convoy(id: EntityId, pos: Vec2) -> Self {
+        Entity {
+            kind: EntityKind::Convoy,
+            bbox: crate::objective::CONVOY_BBOX,
+            hp: crate::objective::CONVOY_HP,
+            max_hp: crate::objective::CONVOY_HP,
+            ..Self::tank(id, pos)
+        }
+    }
+
+    /// Bomber escort for `ObjectiveKind::EscortBomber`, nose to the right.
+    pub fn bomber(id: EntityId, pos: Vec2) -> Self {
+        Entity {
+            kind: EntityKind::Bomber,
+            bbox: crate::objective::BOMBER_BBOX,
+            hp: crate::objective::BOMBER_HP,
+            max_hp: crate::objective::BOMBER_HP,
+            ..Self::tank(id, pos)
         }
     }
 
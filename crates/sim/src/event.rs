@@ -1,4 +1,6 @@
 use crate::entity::{PlayerId, ShotOwner};
+use crate::objective::{ObjectiveKind, ObjectiveOutcome};
+use crate::scoring::ScoreReason;
 use crate::terrain::TerrainKind;
 use crate::util::Vec2;
 use serde::{Deserialize, Serialize};
@@ -11,6 +13,9 @@ pub enum DeathCause {
     Enemy,
     EnemyShot,
     Terrain(TerrainKind),
+    /// Shot or bombed by another pilot in PvP. Carries the killer for the
+    /// kill feed.
+    Player(PlayerId),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -40,11 +45,34 @@ pub enum GameEvent {
         pos: Vec2,
         cause: DeathCause,
     },
-    /// Tank shell ended its life with a boom — terrain impact, hostile
+    /// Tank shell or bomb ended its life with a boom — terrain impact, hostile
     /// hit, or TTL-expired-near-something. The client renders an
     /// explosion at `pos`; any associated damage event (PlayerDamaged,
     /// EnemyKilled, …) is emitted alongside so the visual is independent
     /// of who got hit.
     ShellExploded { pos: Vec2 },
     LevelUp(i32),
+    /// Points credited to a pilot. A kill emits one for the base value and
+    /// one per style bonus; `points` already includes `multiplier`. `pos`
+    /// is where the victim died so the client can float a popup there.
+    ScoreAwarded {
+        player_id: PlayerId,
+        points: i32,
+        reason: ScoreReason,
+        multiplier: i32,
+        pos: Vec2,
+    },
+    /// A new objective round began (see `objective`).
+    ObjectiveStarted(ObjectiveKind),
+    /// The objective escort took a non-fatal hit.
+    EscortDamaged { pos: Vec2, hp: i16 },
+    /// The objective escort was destroyed. `ObjectiveEnded` with a
+    /// failure follows on the same tick.
+    EscortDestroyed { pos: Vec2 },
+    /// The current objective round was decided. The results hold for
+    /// `OBJECTIVE_RESULTS_TIME` before the next `ObjectiveStarted`.
+    ObjectiveEnded {
+        kind: ObjectiveKind,
+        outcome: ObjectiveOutcome,
+    },
 }
@@ -8,24 +8,39 @@
 //!
 //! `World::tick(&PlayerInputs, dt) -> Vec<GameEvent>` is the single
 //! authoritative step. `dt` is fixed at `1.0 / 60.0` in production. AI
-//! (`enemy::step`, `tank::step`) and wave scheduling (`wave::WaveDirector`)
-//! live in their own modules but are driven from `World::tick`.
+//! (`enemy::step`, `tank::step`, `wingman::steer` for bot pilots) and wave
+//! scheduling (`wave::WaveDirector`) live in their own modules but are
+//! driven from `World::tick`, as are co-op objectives (`objective`) and
+//! teams (`team`).
 
 pub mod enemy;
 pub mod entity;
 pub mod event;
 pub mod input;
+pub mod loadout;
+pub mod objective;
 pub mod physics;
 pub mod player;
+pub mod pvp;
+pub mod scoring;
+pub mod stats;
 pub mod tank;
+pub mod team;
 pub mod terrain;
 pub mod util;
 pub mod wave;
+pub mod wingman;
 pub mod world;
 
 pub use entity::{Entity, EntityId, EntityKind, PlayerId, ShotOwner, Tick};
 pub use event::{DeathCause, GameEvent};
 pub use input::{PlayerInput, PlayerInputs};
+pub use loadout::{ShipClass, Weapon};
+pub use objective::{ObjectiveKind, ObjectiveOutcome, ObjectiveStatus};
+pub use player::FlightModel;
+pub use pvp::PvpRules;
+pub use scoring::ScoreReason;
+pub use stats::{PilotStats, RunStats};
 pub use terrain::{TerrainBand, TerrainKind};
 pub use util::{Vec2, vec_from_angle};
 pub use world::{World, WorldConfig};
@@ -0,0 +1,179 @@
+//! Selectable ship classes. Each class is a fixed stat block — hull size,
+//! HP, engine, turn rate, and weapon — that `World::tick` reads off the
+//! player's `Entity::ship_class` every step. The pure flight functions in
+//! `player` take a `&ShipStats` so they stay independent of which class is
+//! flying.
+
+use serde::{Deserialize, Serialize};
+
+use crate::player::{
+    PLAYER_BBOX, PLAYER_MAX_HP, PLAYER_MAX_SPEED, PLAYER_SHOT_TIME, PLAYER_THRUST,
+    PLAYER_TURN_RATE,
+};
+
+/// Sideways distance (world units) between the gunship's two barrels and
+/// the fuselage centerline.
+pub const TWIN_GUN_SPACING: f32 = 6.0;
+/// Speed a bomb leaves the bay at, added to the ship's own velocity.
+pub const BOMB_DROP_SPEED: f32 = 60.0;
+/// Downward pull on a falling bomb (units/s²).
+pub const BOMB_GRAVITY: f32 = 320.0;
+/// Bomb hit radius — fatter than a bullet so a near-miss still connects.
+pub const BOMB_BBOX: f32 = 9.0;
+/// Seconds before an airborne bomb fizzles out.
+pub const BOMB_LIFE: f32 = 4.0;
+
+/// What a ship fires when the pilot holds the trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weapon {
+    /// One nose gun.
+    Gun,
+    /// Two guns either side of the nose, fired together.
+    TwinGun,
+    /// Gravity bombs dropped from the belly. Heavy, slow, and detonate on
+    /// the ground.
+    Bombs,
+}
+
+/// Ship class picked by the pilot at join time (`ClientMsg::Hello`) or
+/// from the menu (`ClientMsg::SelectShip`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ShipClass {
+    /// All-rounder. Uses the original `PLAYER_*` tuning.
+    #[default]
+    Fighter,
+    /// Fast and nimble, but goes down in a few hits.
+    Interceptor,
+    /// Slow, tanky, and carries twin guns.
+    Gunship,
+    /// Trades the gun for gravity bombs that crack tanks.
+    Bomber,
+}
+
+/// Per-class tuning. Everything `World::tick` needs to fly and fire a
+/// ship of that class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipStats {
+    pub bbox: f32,
+    pub max_hp: i16,
+    pub thrust: f32,
+    pub max_speed: f32,
+    /// Rotation in radians per second.
+    pub turn_rate: f32,
+    /// Seconds between trigger pulls.
+    pub shot_time: f32,
+    /// Gun heat added per trigger pull, as a fraction of
+    /// `player::GUN_HEAT_MAX`. Tuned so each class overheats after a few
+    /// seconds of held fire.
+    pub heat_per_shot: f32,
+    pub weapon: Weapon,
+}
+
+impl ShipClass {
+    pub const ALL: [ShipClass; 4] = [
+        ShipClass::Fighter,
+        ShipClass::Interceptor,
+        ShipClass::Gunship,
+        ShipClass::Bomber,
+    ];
+
+    pub fn stats(self) -> ShipStats {
+        match self {
+            ShipClass::Fighter => ShipStats {
+                bbox: PLAYER_BBOX,
+                max_hp: PLAYER_MAX_HP,
+                thrust: PLAYER_THRUST,
+                max_speed: PLAYER_MAX_SPEED,
+                turn_rate: PLAYER_TURN_RATE,
+                shot_time: PLAYER_SHOT_TIME,
+                heat_per_shot: 0.2,
+                weapon: Weapon::Gun,
+            },
+            ShipClass::Interceptor => ShipStats {
+                bbox: 10.0,
+                max_hp: 3,
+                thrust: 720.0,
+                max_speed: 330.0,
+                turn_rate: 3.6,
+                shot_time: 0.25,
+                heat_per_shot: 0.16,
+                weapon: Weapon::Gun,
+            },
+            ShipClass::Gunship => ShipStats {
+                bbox: 15.0,
+                max_hp: 8,
+                thrust: 480.0,
+                max_speed: 210.0,
+                turn_rate: 2.4,
+                shot_time: 0.35,
+                heat_per_shot: 0.22,
+                weapon: Weapon::TwinGun,
+            },
+            ShipClass::Bomber => ShipStats {
+                bbox: 14.0,
+                max_hp: 7,
+                thrust: 520.0,
+                max_speed: 230.0,
+                turn_rate: 2.6,
+                shot_time: 0.8,
+                heat_per_shot: 0.35,
+                weapon: Weapon::Bombs,
+            },
+        }
+    }
+
+    /// Display name for menus and HUD.
+    pub fn name(self) -> &'static str {
+        match self {
+            ShipClass::Fighter => "FIGHTER",
+            ShipClass::Interceptor => "INTERCEPTOR",
+            ShipClass::Gunship => "GUNSHIP",
+            ShipClass::Bomber => "BOMBER",
+        }
+    }
+
+    /// Next class in `ALL`, wrapping. Used by the menu's left/right picker.
+    pub fn next(self) -> ShipClass {
+        let i = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Previous class in `ALL`, wrapping.
+    pub fn prev(self) -> ShipClass {
+        let i = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fighter_matches_legacy_player_tuning() {
+        let s = ShipClass::Fighter.stats();
+        assert_eq!(s.bbox, PLAYER_BBOX);
+        assert_eq!(s.max_hp, PLAYER_MAX_HP);
+        assert_eq!(s.thrust, PLAYER_THRUST);
+        assert_eq!(s.weapon, Weapon::Gun);
+    }
+
+    #[test]
+    fn interceptor_is_faster_and_more_fragile_than_gunship() {
+        let i = ShipClass::Interceptor.stats();
+        let g = ShipClass::Gunship.stats();
+        assert!(i.max_speed > g.max_speed);
+        assert!(i.thrust > g.thrust);
+        assert!(i.max_hp < g.max_hp);
+    }
+
+    #[test]
+    fn next_and_prev_cycle_through_all_classes() {
+        let mut c = ShipClass::default();
+        for _ in 0..ShipClass::ALL.len() {
+            c = c.next();
+        }
+        assert_eq!(c, ShipClass::default());
+        assert_eq!(ShipClass::Fighter.prev(), ShipClass::Bomber);
+    }
+}
@@ -0,0 +1,222 @@
+//! Co-op objectives. With `WorldConfig::objective` set the pilots share a
+//! goal on top of surviving the waves: see a ground convoy across the
+//! map, hunt down a quota of tanks before the clock runs out, or escort a
+//! slow bomber on its run. Hostiles target the escort as well as the
+//! pilots.
+//!
+//! `Objective` is the bookkeeping for one round — progress, the clock and
+//! the verdict. `World::tick` moves the escort entity, feeds in tank kills
+//! and calls [`Objective::judge`]; once a round is decided it holds the
+//! result for `OBJECTIVE_RESULTS_TIME` (the client's results screen) and
+//! then starts the next round.
+
+use serde::{Deserialize, Serialize};
+
+use crate::entity::EntityId;
+
+/// Convoy rolling speed (world units/s). Slower than a tank so the pilots
+/// have time to clear the road ahead.
+pub const CONVOY_SPEED: f32 = 45.0;
+/// Distance the convoy has to cover. Three quarters of a lap of the
+/// default world, just under a minute at `CONVOY_SPEED`.
+pub const CONVOY_ROUTE: f32 = 2400.0;
+pub const CONVOY_HP: i16 = 12;
+pub const CONVOY_BBOX: f32 = 16.0;
+/// Terrain surface to `pos.y`, like `tank::TANK_GROUND_OFFSET`.
+pub const CONVOY_GROUND_OFFSET: f32 = 8.0;
+
+/// Bomber cruise speed (world units/s).
+pub const BOMBER_SPEED: f32 = 80.0;
+/// One full lap of the default world.
+pub const BOMBER_ROUTE: f32 = 3200.0;
+pub const BOMBER_HP: i16 = 8;
+pub const BOMBER_BBOX: f32 = 20.0;
+/// Height above the local ground the bomber holds.
+pub const BOMBER_ALTITUDE: f32 = 360.0;
+/// Max climb/sink rate (world units/s) while following the terrain.
+pub const BOMBER_CLIMB_RATE: f32 = 60.0;
+
+/// Tanks to destroy in a tank hunt.
+pub const TANK_HUNT_QUOTA: u32 = 6;
+/// Seconds the pilots get to fill the quota.
+pub const TANK_HUNT_TIME: f32 = 120.0;
+/// The hunt keeps at least this many tanks on the map, whatever the
+/// wave director thinks of the current level.
+pub const TANK_HUNT_MIN_ALIVE: i32 = 2;
+
+/// Escorts start this far left of the pilots' spawn point and head right.
+pub const ESCORT_START_OFFSET: f32 = 300.0;
+/// Seconds a decided round stays on the results screen before the next
+/// one starts.
+pub const OBJECTIVE_RESULTS_TIME: f32 = 8.0;
+/// Paid to every live pilot when a round succeeds.
+pub const OBJECTIVE_POINTS: i32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    /// Keep a ground convoy alive until it reaches the end of its route.
+    DefendConvoy,
+    /// Destroy `TANK_HUNT_QUOTA` tanks within `TANK_HUNT_TIME`.
+    DestroyTanks,
+    /// Keep a slow bomber alive for its whole run.
+    EscortBomber,
+}
+
+impl ObjectiveKind {
+    pub const ALL: [ObjectiveKind; 3] = [
+        ObjectiveKind::DefendConvoy,
+        ObjectiveKind::DestroyTanks,
+        ObjectiveKind::EscortBomber,
+    ];
+
+    /// Briefing line for the HUD and results screen.
+    pub fn name(self) -> &'static str {
+        match self {
+            ObjectiveKind::DefendConvoy => "DEFEND THE CONVOY",
+            ObjectiveKind::DestroyTanks => "DESTROY THE TANKS",
+            ObjectiveKind::EscortBomber => "ESCORT THE BOMBER",
+        }
+    }
+
+    /// Route length and speed of the escort, for missions that have one.
+    pub fn escort_route(self) -> Option<(f32, f32)> {
+        match self {
+            ObjectiveKind::DefendConvoy => Some((CONVOY_ROUTE, CONVOY_SPEED)),
+            ObjectiveKind::EscortBomber => Some((BOMBER_ROUTE, BOMBER_SPEED)),
+            ObjectiveKind::DestroyTanks => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveOutcome {
+    Success,
+    Failure,
+}
+
+/// What clients see of the current round.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveStatus {
+    pub kind: ObjectiveKind,
+    /// Rounds played, starting at 1.
+    pub round: u32,
+    /// `0..=1` toward success.
+    pub progress: f32,
+    /// Seconds left on a timed objective.
+    pub time_left: Option<f32>,
+    /// Set once the round is decided, for the results screen.
+    pub outcome: Option<ObjectiveOutcome>,
+}
+
+/// One round of an objective.
+#[derive(Debug, Clone)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    pub round: u32,
+    /// Escort entity for convoy and bomber missions.
+    pub escort: Option<EntityId>,
+    /// Distance the escort has covered.
+    pub travelled: f32,
+    pub tank_kills: u32,
+    /// Seconds since the round started. Stops once it's decided.
+    pub elapsed: f32,
+    pub outcome: Option<ObjectiveOutcome>,
+    /// Seconds of results screen left after the verdict.
+    pub results_timer: f32,
+}
+
+impl Objective {
+    pub fn new(kind: ObjectiveKind, round: u32) -> Self {
+        Objective {
+            kind,
+            round,
+            escort: None,
+            travelled: 0.0,
+            tank_kills: 0,
+            elapsed: 0.0,
+            outcome: None,
+            results_timer: OBJECTIVE_RESULTS_TIME,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        let p = match self.kind.escort_route() {
+            Some((route, _)) => self.travelled / route,
+            None => self.tank_kills as f32 / TANK_HUNT_QUOTA as f32,
+        };
+        p.clamp(0.0, 1.0)
+    }
+
+    pub fn status(&self) -> ObjectiveStatus {
+        let time_left = match self.kind {
+            ObjectiveKind::DestroyTanks => Some((TANK_HUNT_TIME - self.elapsed).max(0.0)),
+            _ => None,
+        };
+        ObjectiveStatus {
+            kind: self.kind,
+            round: self.round,
+            progress: self.progress(),
+            time_left,
+            outcome: self.outcome,
+        }
+    }
+
+    /// Decide the round if it can be decided. Returns the outcome on the
+    /// call that settles it and `None` before and after.
+    pub fn judge(&mut self, escort_alive: bool) -> Option<ObjectiveOutcome> {
+        if self.outcome.is_some() {
+            return None;
+        }
+        let outcome = match self.kind.escort_route() {
+            Some(_) if !escort_alive => Some(ObjectiveOutcome::Failure),
+            Some((route, _)) if self.travelled >= route => Some(ObjectiveOutcome::Success),
+            Some(_) => None,
+            None if self.tank_kills >= TANK_HUNT_QUOTA => Some(ObjectiveOutcome::Success),
+            None if self.elapsed >= TANK_HUNT_TIME => Some(ObjectiveOutcome::Failure),
+            None => None,
+        };
+        self.outcome = outcome;
+        outcome
+    }
+
+    /// True once the results screen has run its course.
+    pub fn finished(&self) -> bool {
+        self.outcome.is_some() && self.results_timer <= 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convoy_succeeds_at_the_end_of_its_route() {
+        let mut o = Objective::new(ObjectiveKind::DefendConvoy, 1);
+        o.travelled = CONVOY_ROUTE * 0.5;
+        assert_eq!(o.judge(true), None);
+        assert!((o.progress() - 0.5).abs() < 1e-6);
+        o.travelled = CONVOY_ROUTE;
+        assert_eq!(o.judge(true), Some(ObjectiveOutcome::Success));
+        assert_eq!(o.judge(false), None, "a decided round stays decided");
+    }
+
+    #[test]
+    fn losing_the_escort_fails_the_round() {
+        let mut o = Objective::new(ObjectiveKind::EscortBomber, 1);
+        assert_eq!(o.judge(false), Some(ObjectiveOutcome::Failure));
+        assert_eq!(o.status().outcome, Some(ObjectiveOutcome::Failure));
+    }
+
+    #[test]
+    fn tank_hunt_races_the_clock() {
+        let mut o = Objective::new(ObjectiveKind::DestroyTanks, 1);
+        o.tank_kills = TANK_HUNT_QUOTA;
+        assert_eq!(o.judge(true), Some(ObjectiveOutcome::Success));
+
+        let mut o = Objective::new(ObjectiveKind::DestroyTanks, 1);
+        o.tank_kills = TANK_HUNT_QUOTA - 1;
+        o.elapsed = TANK_HUNT_TIME;
+        assert_eq!(o.status().time_left, Some(0.0));
+        assert_eq!(o.judge(true), Some(ObjectiveOutcome::Failure));
+    }
+}
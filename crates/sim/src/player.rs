@@ -1,6 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::input::PlayerInput;
+use crate::loadout::ShipStats;
 use crate::util::{self, Vec2};
 
+// Baseline tuning. These are the `ShipClass::Fighter` numbers; other
+// classes carry their own in `loadout::ShipClass::stats`.
 pub const PLAYER_BBOX: f32 = 12.0;
 pub const PLAYER_THRUST: f32 = 594.0;
 pub const PLAYER_MAX_SPEED: f32 = 264.0;
@@ -36,21 +41,79 @@ pub const RAM_DEATH_SECONDS: f32 = 1.5;
 /// hostiles take proportionally longer to chew through.
 pub const RAM_DAMAGE_PER_SECOND: f32 = PLAYER_MAX_HP as f32 / RAM_DEATH_SECONDS;
 
-/// Pure rotation + thrust step. Returns `(new_velocity, new_facing)`.
-pub fn apply_input(velocity: Vec2, facing: f32, input: &PlayerInput, dt: f32) -> (Vec2, f32) {
-    let new_facing = facing + dt * PLAYER_TURN_RATE * input.xaxis;
+/// Heat level at which the gun overheats. Heat is normalised so the HUD can
+/// draw it as a fraction without knowing any per-class numbers.
+pub const GUN_HEAT_MAX: f32 = 1.0;
+/// Heat shed per second, whether or not the gun is locked.
+pub const GUN_HEAT_DISSIPATION: f32 = 0.35;
+/// Seconds the gun stays locked after overheating. Long enough that
+/// holding the trigger is a real mistake, short enough not to feel broken.
+pub const GUN_OVERHEAT_LOCK: f32 = 1.5;
+
+/// Which flight model drives player ships. Picked per world through
+/// `WorldConfig::flight_model`; every pilot in a world flies the same way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlightModel {
+    /// Pure thrust-vectoring: the ship goes wherever the engine points,
+    /// with drag and gravity on top. `apply_input` / `apply_forces`.
+    #[default]
+    Arcade,
+    /// Sopwith/Luftrausers-style: the wing turns airspeed into lift, a
+    /// slow ship stalls and drops its nose, and hard turns bleed energy.
+    /// `apply_aero_input` / `apply_aero_forces`.
+    Aerodynamic,
+}
+
+/// Airspeed along the nose (units/s) below which the wing stops flying.
+/// Only used by `FlightModel::Aerodynamic`.
+pub const AERO_STALL_SPEED: f32 = 70.0;
+/// Airspeed a stalled ship must climb back above before the wing bites
+/// again. Higher than `AERO_STALL_SPEED` so a pilot hovering right at the
+/// edge doesn't flicker in and out of the stall every tick.
+pub const AERO_RECOVERY_SPEED: f32 = 95.0;
+/// Airspeed at which the wing produces full lift. Below this the grip
+/// scales linearly with airspeed, so slow flight feels floaty well before
+/// the outright stall.
+pub const AERO_FULL_LIFT_SPEED: f32 = 200.0;
+/// How fast lift cancels sideways slip at full grip, per second. This is
+/// what makes velocity follow the nose and what holds a fast, level ship
+/// up against gravity.
+pub const AERO_LIFT_RATE: f32 = 6.0;
+/// Fraction of the ship's turn rate left while stalled — the controls go
+/// mushy until the pilot regains airspeed.
+pub const AERO_STALL_TURN_FACTOR: f32 = 0.35;
+/// Radians per second a stalled ship's nose falls toward straight down.
+pub const AERO_STALL_NOSE_DROP_RATE: f32 = 1.8;
+/// Fraction of speed lost per second at full stick deflection. Tight
+/// turns cost energy, so looping forever at top speed isn't free.
+pub const AERO_TURN_BLEED: f32 = 0.5;
+
+/// Pure rotation + thrust step. Both axes are proportional: a half-pushed
+/// stick turns at half rate and a half-pulled trigger gives half thrust.
+/// Returns `(new_velocity, new_facing)`.
+pub fn apply_input(
+    velocity: Vec2,
+    facing: f32,
+    input: &PlayerInput,
+    ship: &ShipStats,
+    dt: f32,
+) -> (Vec2, f32) {
+    let new_facing = facing + dt * ship.turn_rate * input.xaxis;
     let mut vel = velocity;
-    if input.yaxis > 0.0 {
-        vel += util::vec_from_angle(new_facing) * PLAYER_THRUST * dt;
-    }
+    vel += thrust(new_facing, input, ship) * dt;
     (vel, new_facing)
 }
 
+/// Acceleration from the throttle. Reverse (`yaxis < 0`) does nothing.
+fn thrust(facing: f32, input: &PlayerInput, ship: &ShipStats) -> Vec2 {
+    util::vec_from_angle(facing) * ship.thrust * input.yaxis.clamp(0.0, 1.0)
+}
+
 /// Pure drag + gravity + clamp step. `gravity_armed` gates the gravity
 /// term so a freshly-spawned pilot can sit still until they choose to
 /// thrust — see `Entity::gravity_armed`. Drag still applies either way,
 /// but at rest drag is zero so the ship stays put.
-pub fn apply_forces(velocity: Vec2, dt: f32, gravity_armed: bool) -> Vec2 {
+pub fn apply_forces(velocity: Vec2, ship: &ShipStats, dt: f32, gravity_armed: bool) -> Vec2 {
     let drag = velocity * -PLAYER_DRAG;
     let gravity = if gravity_armed {
         Vec2::new(0.0, -PLAYER_GRAVITY)
@@ -58,25 +121,115 @@ pub fn apply_forces(velocity: Vec2, dt: f32, gravity_armed: bool) -> Vec2 {
         Vec2::ZERO
     };
     let mut vel = velocity + (gravity + drag) * dt;
-    if let Some(clamped) = util::clamp_velocity(vel, PLAYER_MAX_SPEED) {
+    if let Some(clamped) = util::clamp_velocity(vel, ship.max_speed) {
         vel = clamped;
     }
     vel
 }
 
+/// Dissipate gun heat and count down any overheat lock. Returns
+/// `(heat, lock)`.
+pub fn cool_gun(heat: f32, lock: f32, dt: f32) -> (f32, f32) {
+    (
+        (heat - GUN_HEAT_DISSIPATION * dt).max(0.0),
+        (lock - dt).max(0.0),
+    )
+}
+
+/// Add one trigger pull's worth of heat. Crossing `GUN_HEAT_MAX` pins the
+/// heat at max and starts a `GUN_OVERHEAT_LOCK` lockout. Returns
+/// `(heat, lock)`.
+pub fn heat_gun(heat: f32, ship: &ShipStats) -> (f32, f32) {
+    let heat = heat + ship.heat_per_shot;
+    if heat >= GUN_HEAT_MAX {
+        (GUN_HEAT_MAX, GUN_OVERHEAT_LOCK)
+    } else {
+        (heat, 0.0)
+    }
+}
+
+/// Airspeed along the nose — the only velocity component the wing can
+/// turn into lift.
+pub fn airspeed(velocity: Vec2, facing: f32) -> f32 {
+    velocity.dot(util::vec_from_angle(facing))
+}
+
+/// Next stall state for an aerodynamic ship. Enters the stall below
+/// `AERO_STALL_SPEED` and only leaves it above `AERO_RECOVERY_SPEED`.
+pub fn update_stall(velocity: Vec2, facing: f32, stalled: bool) -> bool {
+    let speed = airspeed(velocity, facing);
+    if stalled {
+        speed < AERO_RECOVERY_SPEED
+    } else {
+        speed < AERO_STALL_SPEED
+    }
+}
+
+/// Aerodynamic counterpart to `apply_input`. Thrust is unchanged; turning
+/// is damped while stalled and the nose falls toward the ground, and
+/// turning with a flying wing bleeds speed. Returns `(new_velocity,
+/// new_facing)`.
+pub fn apply_aero_input(
+    velocity: Vec2,
+    facing: f32,
+    stalled: bool,
+    input: &PlayerInput,
+    ship: &ShipStats,
+    dt: f32,
+) -> (Vec2, f32) {
+    let mut vel = velocity;
+    let new_facing = if stalled {
+        let turned = facing + dt * ship.turn_rate * AERO_STALL_TURN_FACTOR * input.xaxis;
+        util::steer_toward_angle(turned, std::f32::consts::PI, AERO_STALL_NOSE_DROP_RATE * dt)
+    } else {
+        vel *= (1.0 - AERO_TURN_BLEED * input.xaxis.abs() * dt).max(0.0);
+        facing + dt * ship.turn_rate * input.xaxis
+    };
+    vel += thrust(new_facing, input, ship) * dt;
+    (vel, new_facing)
+}
+
+/// Aerodynamic counterpart to `apply_forces`: lift first, then the same
+/// drag + gravity + clamp. Lift removes sideways slip in proportion to
+/// airspeed, so a fast ship flies where it points and holds altitude
+/// while a stalled one just falls.
+pub fn apply_aero_forces(
+    velocity: Vec2,
+    facing: f32,
+    stalled: bool,
+    ship: &ShipStats,
+    dt: f32,
+    gravity_armed: bool,
+) -> Vec2 {
+    let mut vel = velocity;
+    if !stalled {
+        let nose = util::vec_from_angle(facing);
+        let along = vel.dot(nose);
+        let slip = vel - nose * along;
+        let grip = (along / AERO_FULL_LIFT_SPEED).clamp(0.0, 1.0);
+        vel -= slip * (grip * AERO_LIFT_RATE * dt).min(1.0);
+    }
+    apply_forces(vel, ship, dt, gravity_armed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::loadout::ShipClass;
 
     fn dt() -> f32 {
         1.0 / 60.0
     }
 
+    fn fighter() -> ShipStats {
+        ShipClass::Fighter.stats()
+    }
+
     #[test]
     fn thrust_at_facing_zero_pushes_y_positive() {
         let mut input = PlayerInput::default();
         input.yaxis = 1.0;
-        let (vel, _) = apply_input(Vec2::ZERO, 0.0, &input, dt());
+        let (vel, _) = apply_input(Vec2::ZERO, 0.0, &input, &fighter(), dt());
         assert!(vel.y > 0.0, "expected +y thrust, got {:?}", vel);
         assert!(vel.x.abs() < 1e-5);
     }
@@ -84,7 +237,29 @@ mod tests {
     #[test]
     fn no_thrust_when_yaxis_zero() {
         let input = PlayerInput::default();
-        let (vel, _) = apply_input(Vec2::ZERO, 0.0, &input, dt());
+        let (vel, _) = apply_input(Vec2::ZERO, 0.0, &input, &fighter(), dt());
+        assert_eq!(vel, Vec2::ZERO);
+    }
+
+    #[test]
+    fn half_throttle_gives_half_thrust() {
+        let full = PlayerInput {
+            yaxis: 1.0,
+            ..PlayerInput::default()
+        };
+        let half = PlayerInput {
+            yaxis: 0.5,
+            ..PlayerInput::default()
+        };
+        let (full_vel, _) = apply_input(Vec2::ZERO, 0.0, &full, &fighter(), dt());
+        let (half_vel, _) = apply_input(Vec2::ZERO, 0.0, &half, &fighter(), dt());
+        assert!((half_vel.y * 2.0 - full_vel.y).abs() < 1e-5);
+
+        let reverse = PlayerInput {
+            yaxis: -1.0,
+            ..PlayerInput::default()
+        };
+        let (vel, _) = apply_input(Vec2::ZERO, 0.0, &reverse, &fighter(), dt());
         assert_eq!(vel, Vec2::ZERO);
     }
 
@@ -95,10 +270,10 @@ mod tests {
         let mut input = PlayerInput::default();
         input.yaxis = 1.0;
         for _ in 0..60 {
-            let (v, _) = apply_input(vel, 0.0, &input, dt());
+            let (v, _) = apply_input(vel, 0.0, &input, &fighter(), dt());
             vel = v;
             pos += vel * dt();
-            vel = apply_forces(vel, dt(), true);
+            vel = apply_forces(vel, &fighter(), dt(), true);
         }
         assert!(
             pos.y > 50.0,
@@ -112,7 +287,7 @@ mod tests {
     #[test]
     fn velocity_clamps_to_max_speed() {
         let mut vel = Vec2::new(0.0, 1000.0);
-        vel = apply_forces(vel, dt(), true);
+        vel = apply_forces(vel, &fighter(), dt(), true);
         assert!(vel.length() <= PLAYER_MAX_SPEED + 1e-3);
     }
 
@@ -125,7 +300,7 @@ mod tests {
         let mut pos = Vec2::ZERO;
         for _ in 0..30 {
             pos += vel * dt();
-            vel = apply_forces(vel, dt(), false);
+            vel = apply_forces(vel, &fighter(), dt(), false);
         }
         assert_eq!(vel, Vec2::ZERO);
         assert_eq!(pos, Vec2::ZERO);
@@ -135,8 +310,160 @@ mod tests {
     fn gravity_when_armed_pulls_player_down() {
         let mut vel = Vec2::ZERO;
         for _ in 0..30 {
-            vel = apply_forces(vel, dt(), true);
+            vel = apply_forces(vel, &fighter(), dt(), true);
         }
         assert!(vel.y < 0.0, "armed pilot should accelerate downward, got {:?}", vel);
     }
+
+    /// One aerodynamic step the way `World::tick` runs it: stall check,
+    /// input, move, forces.
+    fn aero_step(vel: &mut Vec2, facing: &mut f32, stalled: &mut bool, input: &PlayerInput) {
+        *stalled = update_stall(*vel, *facing, *stalled);
+        let (v, f) = apply_aero_input(*vel, *facing, *stalled, input, &fighter(), dt());
+        *facing = f;
+        *vel = apply_aero_forces(v, f, *stalled, &fighter(), dt(), true);
+    }
+
+    #[test]
+    fn aero_climb_without_thrust_stalls_and_drops_nose() {
+        // Pointed straight up at a healthy speed with the engine off,
+        // gravity and drag eat the airspeed until the wing stalls. Once
+        // stalled the nose must start falling toward the ground.
+        let mut vel = Vec2::new(0.0, 180.0);
+        let mut facing = 0.0_f32;
+        let mut stalled = false;
+        let input = PlayerInput::default();
+        let mut ticks = 0;
+        while !stalled && ticks < 180 {
+            aero_step(&mut vel, &mut facing, &mut stalled, &input);
+            ticks += 1;
+        }
+        assert!(stalled, "unpowered climb should stall within 3s, vel={vel:?}");
+        let facing_at_onset = facing;
+        for _ in 0..15 {
+            aero_step(&mut vel, &mut facing, &mut stalled, &input);
+        }
+        assert!(
+            facing.abs() > facing_at_onset.abs() + 0.2,
+            "stalled nose should drop toward PI, went {facing_at_onset} -> {facing}"
+        );
+    }
+
+    #[test]
+    fn aero_stall_recovers_once_the_dive_builds_airspeed() {
+        // Start already stalled, nose up, barely moving. The nose drops,
+        // gravity builds speed along it, and the wing bites again.
+        let mut vel = Vec2::new(0.0, 10.0);
+        let mut facing = 0.3_f32;
+        let mut stalled = true;
+        let input = PlayerInput::default();
+        let mut recovered = false;
+        for _ in 0..(60 * 5) {
+            aero_step(&mut vel, &mut facing, &mut stalled, &input);
+            if !stalled {
+                recovered = true;
+                break;
+            }
+        }
+        assert!(recovered, "a stalled ship should recover in a dive, vel={vel:?}");
+        assert!(airspeed(vel, facing) >= AERO_RECOVERY_SPEED);
+    }
+
+    #[test]
+    fn aero_stall_has_hysteresis() {
+        // Between the stall and recovery speeds the state is sticky.
+        let mid = (AERO_STALL_SPEED + AERO_RECOVERY_SPEED) * 0.5;
+        let vel = Vec2::new(0.0, mid);
+        assert!(!update_stall(vel, 0.0, false), "flying ship stays flying");
+        assert!(update_stall(vel, 0.0, true), "stalled ship stays stalled");
+    }
+
+    #[test]
+    fn aero_stall_dampens_turning() {
+        let input = PlayerInput {
+            xaxis: 1.0,
+            ..PlayerInput::default()
+        };
+        let vel = Vec2::new(AERO_FULL_LIFT_SPEED, 0.0);
+        let facing = std::f32::consts::FRAC_PI_2;
+        let (_, flying) = apply_aero_input(vel, facing, false, &input, &fighter(), dt());
+        // Compare against a stalled ship pointing straight down, where the
+        // nose-drop term is already satisfied and only the turn remains.
+        let (_, stalled) = apply_aero_input(vel, std::f32::consts::PI, true, &input, &fighter(), dt());
+        let flying_rate = flying - facing;
+        let stalled_rate = (stalled - std::f32::consts::PI).abs();
+        assert!(
+            stalled_rate < flying_rate * 0.5,
+            "stalled turn {stalled_rate} should be well below flying turn {flying_rate}"
+        );
+    }
+
+    #[test]
+    fn aero_fast_level_flight_holds_altitude() {
+        // At full lift, a level ship loses well under half the height of the
+        // same ship under the arcade model.
+        let mut vel = Vec2::new(AERO_FULL_LIFT_SPEED + 40.0, 0.0);
+        let mut facing = std::f32::consts::FRAC_PI_2;
+        let mut stalled = false;
+        let input = PlayerInput {
+            yaxis: 1.0,
+            ..PlayerInput::default()
+        };
+        let mut aero_y = 0.0;
+        for _ in 0..60 {
+            aero_step(&mut vel, &mut facing, &mut stalled, &input);
+            aero_y += vel.y * dt();
+        }
+        let mut arcade_vel = Vec2::new(AERO_FULL_LIFT_SPEED + 40.0, 0.0);
+        let mut arcade_y = 0.0;
+        for _ in 0..60 {
+            let (v, _) = apply_input(arcade_vel, std::f32::consts::FRAC_PI_2, &input, &fighter(), dt());
+            arcade_vel = apply_forces(v, &fighter(), dt(), true);
+            arcade_y += arcade_vel.y * dt();
+        }
+        assert!(!stalled);
+        assert!(
+            aero_y > arcade_y * 0.5,
+            "lift should hold altitude: aero dropped {aero_y}, arcade {arcade_y}"
+        );
+    }
+
+    #[test]
+    fn sustained_fire_overheats_then_cools_off() {
+        let ship = fighter();
+        let mut heat = 0.0;
+        let mut lock = 0.0;
+        let mut pulls = 0;
+        // Hold the trigger: one pull every `shot_time`, cooling in between.
+        while pulls < 100 {
+            (heat, lock) = heat_gun(heat, &ship);
+            pulls += 1;
+            if lock > 0.0 {
+                break;
+            }
+            (heat, lock) = cool_gun(heat, lock, ship.shot_time);
+        }
+        assert!(lock > 0.0, "holding fire should overheat the gun");
+        assert!(pulls > 5, "gun shouldn't overheat almost immediately, took {pulls}");
+        assert_eq!(heat, GUN_HEAT_MAX);
+
+        (heat, lock) = cool_gun(heat, lock, GUN_OVERHEAT_LOCK);
+        assert_eq!(lock, 0.0, "lock should expire after GUN_OVERHEAT_LOCK");
+        assert!(heat < GUN_HEAT_MAX);
+    }
+
+    #[test]
+    fn gun_heat_never_goes_negative() {
+        assert_eq!(cool_gun(0.1, 0.0, 10.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn aero_hard_turns_bleed_speed() {
+        let vel = Vec2::new(0.0, AERO_FULL_LIFT_SPEED);
+        let mut input = PlayerInput::default();
+        let (straight, _) = apply_aero_input(vel, 0.0, false, &input, &fighter(), dt());
+        input.xaxis = 1.0;
+        let (turning, _) = apply_aero_input(vel, 0.0, false, &input, &fighter(), dt());
+        assert!(turning.length() < straight.length());
+    }
 }
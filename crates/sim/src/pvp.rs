@@ -0,0 +1,67 @@
+//! Player-versus-player rules. With `WorldConfig::pvp` set, player shots
+//! and bombs hit rival pilots as well as hostiles, kills are credited via
+//! `DeathCause::Player`, and downed pilots come back on their own after
+//! `PVP_RESPAWN_DELAY` without resetting anyone else's wave.
+//!
+//! With `WorldConfig::teams > 0` teammates can't hurt each other
+//! (team deathmatch); with no teams it's free-for-all.
+
+use crate::util::{self, Vec2};
+
+/// Seconds between a PvP death and the automatic respawn.
+pub const PVP_RESPAWN_DELAY: f32 = 3.0;
+/// Evenly spaced spawn columns across the world. A respawning pilot takes
+/// the one farthest from any live rival so nobody spawns into a gun.
+pub const PVP_SPAWN_SLOTS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PvpRules {
+    /// Keep spawning AI enemy waves alongside the dogfight.
+    pub hostile_waves: bool,
+}
+
+impl Default for PvpRules {
+    fn default() -> Self {
+        PvpRules {
+            hostile_waves: true,
+        }
+    }
+}
+
+/// Spawn column farthest (wrap-aware) from every position in `rivals`.
+/// With no rivals, the world center — same as co-op.
+pub fn spawn_x(world_width: f32, rivals: &[Vec2]) -> f32 {
+    if rivals.is_empty() {
+        return world_width * 0.5;
+    }
+    let slot_x = |i: usize| (i as f32 + 0.5) * world_width / PVP_SPAWN_SLOTS as f32;
+    let clearance = |x: f32| {
+        rivals
+            .iter()
+            .map(|r| {
+                util::toroidal_offset(Vec2::new(x, r.y), *r, world_width)
+                    .x
+                    .abs()
+            })
+            .fold(f32::MAX, f32::min)
+    };
+    (0..PVP_SPAWN_SLOTS)
+        .map(slot_x)
+        .max_by(|a, b| clearance(*a).total_cmp(&clearance(*b)))
+        .unwrap_or(world_width * 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_avoids_rivals_across_the_seam() {
+        let width = 3200.0;
+        let x = spawn_x(width, &[Vec2::new(100.0, 500.0)]);
+        // Opposite side of the torus from the rival.
+        let gap = util::toroidal_offset(Vec2::new(x, 0.0), Vec2::new(100.0, 0.0), width);
+        assert!(gap.x.abs() > width * 0.4, "spawned {gap:?} from the rival");
+        assert_eq!(spawn_x(width, &[]), width * 0.5);
+    }
+}
@@ -0,0 +1,201 @@
+//! Kill scoring. Hostiles are worth different base points, quick
+//! successive kills build a per-player combo multiplier that decays when
+//! the pilot stops killing, and style bonuses reward bombing tanks from
+//! altitude or fighting right down on the deck.
+//!
+//! `World::handle_collisions` queues each credited kill and hands them to
+//! [`ScoreKeeper::award`] once the collision pass is done; every award
+//! surfaces as a `GameEvent::ScoreAwarded` so the client can float a
+//! popup.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{EntityKind, PlayerId};
+
+/// Base points for a flying enemy ship.
+pub const ENEMY_POINTS: i32 = 100;
+/// Base points for a tank. Tougher and rarer, so worth more.
+pub const TANK_POINTS: i32 = 250;
+/// Base points for downing a rival pilot in PvP.
+pub const PLAYER_POINTS: i32 = 200;
+/// Seconds a combo holds after a kill before it starts to decay. Each
+/// further window without a kill drops the multiplier by one step.
+pub const COMBO_WINDOW: f32 = 3.0;
+/// Combo multiplier ceiling.
+pub const COMBO_MAX_MULTIPLIER: i32 = 5;
+/// Killer altitude above the local terrain surface at or above which a
+/// tank kill counts as an air strike.
+pub const AIR_STRIKE_ALTITUDE: f32 = 300.0;
+/// Bonus points for an air strike, before the multiplier.
+pub const AIR_STRIKE_BONUS: i32 = 150;
+/// Killer altitude above the local terrain surface at or below which any
+/// kill counts as a low-flying kill.
+pub const LOW_FLYING_ALTITUDE: f32 = 60.0;
+/// Bonus points for a low-flying kill, before the multiplier.
+pub const LOW_FLYING_BONUS: i32 = 100;
+
+/// Why points were awarded. One kill can produce several awards — the base
+/// kill plus any bonuses — each reported separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoreReason {
+    EnemyKill,
+    TankKill,
+    /// Rival pilot downed in PvP.
+    PlayerKill,
+    /// Tank destroyed from high above the ground.
+    AirStrike,
+    /// Kill made while skimming the terrain.
+    LowFlying,
+    /// Share of a completed co-op objective (`objective::OBJECTIVE_POINTS`).
+    Objective,
+}
+
+/// One line of a kill's payout. `points` already includes `multiplier`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Award {
+    pub points: i32,
+    pub reason: ScoreReason,
+    pub multiplier: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Combo {
+    multiplier: i32,
+    /// Seconds until the multiplier drops a step.
+    timer: f32,
+}
+
+/// Per-player combo state.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreKeeper {
+    combos: BTreeMap<PlayerId, Combo>,
+}
+
+impl ScoreKeeper {
+    /// Current combo multiplier for `player_id` (1 when no combo is live).
+    pub fn multiplier(&self, player_id: PlayerId) -> i32 {
+        self.combos.get(&player_id).map_or(1, |c| c.multiplier)
+    }
+
+    /// Decay every live combo. A multiplier steps down once per
+    /// `COMBO_WINDOW` without a kill and is forgotten when it hits 1.
+    pub fn tick(&mut self, dt: f32) {
+        self.combos.retain(|_, c| {
+            c.timer -= dt;
+            while c.timer <= 0.0 && c.multiplier > 1 {
+                c.multiplier -= 1;
+                c.timer += COMBO_WINDOW;
+            }
+            c.multiplier > 1
+        });
+    }
+
+    /// Drop a pilot's combo — on death, respawn, or leaving.
+    pub fn reset(&mut self, player_id: PlayerId) {
+        self.combos.remove(&player_id);
+    }
+
+    /// Price a kill of `victim` by `player_id`, whose altitude above the
+    /// local terrain surface is `killer_altitude`. Pays out at the current
+    /// multiplier, then bumps the combo for the next kill.
+    pub fn award(
+        &mut self,
+        player_id: PlayerId,
+        victim: EntityKind,
+        killer_altitude: f32,
+    ) -> Vec<Award> {
+        let multiplier = self.multiplier(player_id);
+        let mut awards = Vec::new();
+        let mut pay = |points: i32, reason: ScoreReason| {
+            awards.push(Award {
+                points: points * multiplier,
+                reason,
+                multiplier,
+            });
+        };
+        match victim {
+            EntityKind::Tank => {
+                pay(TANK_POINTS, ScoreReason::TankKill);
+                if killer_altitude >= AIR_STRIKE_ALTITUDE {
+                    pay(AIR_STRIKE_BONUS, ScoreReason::AirStrike);
+                }
+            }
+            EntityKind::Player { .. } => pay(PLAYER_POINTS, ScoreReason::PlayerKill),
+            _ => pay(ENEMY_POINTS, ScoreReason::EnemyKill),
+        }
+        if killer_altitude <= LOW_FLYING_ALTITUDE {
+            pay(LOW_FLYING_BONUS, ScoreReason::LowFlying);
+        }
+        self.combos.insert(
+            player_id,
+            Combo {
+                multiplier: (multiplier + 1).min(COMBO_MAX_MULTIPLIER),
+                timer: COMBO_WINDOW,
+            },
+        );
+        awards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CRUISE: f32 = 150.0;
+
+    fn total(awards: &[Award]) -> i32 {
+        awards.iter().map(|a| a.points).sum()
+    }
+
+    #[test]
+    fn tank_is_worth_more_than_a_ship() {
+        let mut keeper = ScoreKeeper::default();
+        let ship = total(&keeper.award(PlayerId(1), EntityKind::Enemy, CRUISE));
+        let tank = total(&keeper.award(PlayerId(2), EntityKind::Tank, CRUISE));
+        assert_eq!(ship, ENEMY_POINTS);
+        assert_eq!(tank, TANK_POINTS);
+        let pilot = EntityKind::Player { player_id: PlayerId(3) };
+        assert_eq!(total(&keeper.award(PlayerId(4), pilot, CRUISE)), PLAYER_POINTS);
+    }
+
+    #[test]
+    fn quick_kills_build_a_capped_combo() {
+        let mut keeper = ScoreKeeper::default();
+        let pid = PlayerId(1);
+        let mut seen = Vec::new();
+        for _ in 0..7 {
+            let a = keeper.award(pid, EntityKind::Enemy, CRUISE);
+            seen.push(a[0].multiplier);
+            keeper.tick(0.5);
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4, 5, 5, 5]);
+    }
+
+    #[test]
+    fn combo_decays_one_step_per_quiet_window() {
+        let mut keeper = ScoreKeeper::default();
+        let pid = PlayerId(1);
+        for _ in 0..3 {
+            keeper.award(pid, EntityKind::Enemy, CRUISE);
+        }
+        assert_eq!(keeper.multiplier(pid), 4);
+        keeper.tick(COMBO_WINDOW + 0.01);
+        assert_eq!(keeper.multiplier(pid), 3);
+        keeper.tick(COMBO_WINDOW * 3.0);
+        assert_eq!(keeper.multiplier(pid), 1);
+    }
+
+    #[test]
+    fn style_bonuses_pay_out_separately() {
+        let mut keeper = ScoreKeeper::default();
+        let strike = keeper.award(PlayerId(1), EntityKind::Tank, AIR_STRIKE_ALTITUDE + 1.0);
+        assert!(strike.iter().any(|a| a.reason == ScoreReason::AirStrike));
+        let low = keeper.award(PlayerId(2), EntityKind::Enemy, LOW_FLYING_ALTITUDE - 1.0);
+        assert!(low.iter().any(|a| a.reason == ScoreReason::LowFlying));
+        // Air strikes are only for ground targets.
+        let ship = keeper.award(PlayerId(3), EntityKind::Enemy, AIR_STRIKE_ALTITUDE + 1.0);
+        assert_eq!(ship.len(), 1);
+    }
+}
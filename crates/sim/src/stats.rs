@@ -0,0 +1,131 @@
+//! Per-pilot statistics for the scoreboard and the end-of-run summary.
+//!
+//! `World` keeps a [`PilotStats`] for every pilot from `add_player` to
+//! `remove_player`: session totals that survive respawns, plus a
+//! [`RunStats`] for the current life that starts over at each spawn and
+//! is frozen at death so the summary can be read after the ship is gone.
+//! Shots and hits are counted where `World::tick` fires and resolves them,
+//! kills where they're priced (`World::award_kills`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{EntityKind, Tick};
+use crate::TICK_DT;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PilotStats {
+    /// Kills across every life this session.
+    pub kills: u32,
+    pub deaths: u32,
+    /// The life in progress, or the last one while the pilot is down.
+    pub run: RunStats,
+}
+
+/// One life, spawn to death.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RunStats {
+    pub started: Tick,
+    /// Tick the pilot went down on; `None` while still flying.
+    pub ended: Option<Tick>,
+    /// Highest world level seen during the run.
+    pub level: i32,
+    /// Bullets and bombs released.
+    pub shots_fired: u32,
+    /// Shots that landed on a hostile or a rival pilot.
+    pub shots_hit: u32,
+    pub enemy_kills: u32,
+    pub tank_kills: u32,
+    /// Rival pilots downed in PvP.
+    pub pilot_kills: u32,
+}
+
+impl PilotStats {
+    pub fn new(now: Tick, level: i32) -> Self {
+        PilotStats {
+            kills: 0,
+            deaths: 0,
+            run: RunStats::new(now, level),
+        }
+    }
+
+    /// Start a fresh run; the session totals carry on.
+    pub fn spawned(&mut self, now: Tick, level: i32) {
+        self.run = RunStats::new(now, level);
+    }
+
+    /// Freeze the run at `now`.
+    pub fn died(&mut self, now: Tick) {
+        self.deaths += 1;
+        self.run.ended.get_or_insert(now);
+    }
+
+    /// Credit a kill of `victim`.
+    pub fn killed(&mut self, victim: EntityKind) {
+        let count = match victim {
+            EntityKind::Enemy => &mut self.run.enemy_kills,
+            EntityKind::Tank => &mut self.run.tank_kills,
+            EntityKind::Player { .. } => &mut self.run.pilot_kills,
+            _ => return,
+        };
+        *count += 1;
+        self.kills += 1;
+    }
+}
+
+impl RunStats {
+    pub fn new(now: Tick, level: i32) -> Self {
+        RunStats {
+            started: now,
+            ended: None,
+            level,
+            shots_fired: 0,
+            shots_hit: 0,
+            enemy_kills: 0,
+            tank_kills: 0,
+            pilot_kills: 0,
+        }
+    }
+
+    /// Seconds flown, up to `now` for a run still going.
+    pub fn duration_secs(&self, now: Tick) -> f32 {
+        let end = self.ended.unwrap_or(now);
+        end.0.saturating_sub(self.started.0) as f32 * TICK_DT
+    }
+
+    /// Fraction of shots that hit; `None` before the first shot.
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.shots_fired > 0).then(|| self.shots_hit as f32 / self.shots_fired as f32)
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.enemy_kills + self.tank_kills + self.pilot_kills
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::PlayerId;
+
+    #[test]
+    fn respawn_starts_a_new_run_but_keeps_session_totals() {
+        let mut stats = PilotStats::new(Tick(60), 2);
+        stats.killed(EntityKind::Tank);
+        stats.killed(EntityKind::Player {
+            player_id: PlayerId(3),
+        });
+        stats.killed(EntityKind::Convoy);
+        stats.run.shots_fired = 4;
+        stats.run.shots_hit = 1;
+        stats.died(Tick(180));
+        // The run stops counting once the pilot is down.
+        assert_eq!(stats.run.duration_secs(Tick(600)), 2.0);
+        assert_eq!(stats.run.accuracy(), Some(0.25));
+        assert_eq!((stats.run.tank_kills, stats.run.kills()), (1, 2));
+
+        stats.spawned(Tick(600), 1);
+        assert_eq!((stats.kills, stats.deaths), (2, 1));
+        assert_eq!(stats.run, RunStats::new(Tick(600), 1));
+        assert_eq!(stats.run.accuracy(), None);
+    }
+}
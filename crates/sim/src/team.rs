@@ -0,0 +1,50 @@
+//! Teams. With `WorldConfig::teams > 0` every pilot is dealt onto a team
+//! when they first join and keeps it until they leave. Co-op uses teams
+//! only for the aggregated `World::team_scores`; PvP also uses them to
+//! decide who can shoot whom (`World::are_rivals`).
+
+use std::collections::BTreeMap;
+
+use crate::entity::PlayerId;
+
+/// Team for a new pilot given how many pilots each team already has
+/// (`counts[t]`). Fills the smallest team, lowest index on ties.
+pub fn pick_team(counts: &[usize]) -> u8 {
+    counts
+        .iter()
+        .enumerate()
+        .min_by_key(|(t, n)| (**n, *t))
+        .map_or(0, |(t, _)| t as u8)
+}
+
+/// Sum pilot scores per team. Pilots without a team don't count; a team
+/// whose pilots have all left drops out.
+pub fn team_totals(
+    scores: &BTreeMap<PlayerId, i32>,
+    teams: &BTreeMap<PlayerId, u8>,
+) -> BTreeMap<u8, i32> {
+    let mut totals = BTreeMap::new();
+    for (pid, team) in teams {
+        *totals.entry(*team).or_insert(0) += scores.get(pid).copied().unwrap_or(0);
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teams_fill_smallest_first() {
+        assert_eq!(pick_team(&[0, 0]), 0);
+        assert_eq!(pick_team(&[1, 0]), 1);
+        assert_eq!(pick_team(&[2, 2, 1]), 2);
+    }
+
+    #[test]
+    fn totals_sum_each_team() {
+        let scores = BTreeMap::from([(PlayerId(1), 100), (PlayerId(2), 250), (PlayerId(3), 50)]);
+        let teams = BTreeMap::from([(PlayerId(1), 0), (PlayerId(2), 1), (PlayerId(3), 0)]);
+        assert_eq!(team_totals(&scores, &teams), BTreeMap::from([(0, 150), (1, 250)]));
+    }
+}
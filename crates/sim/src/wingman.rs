@@ -0,0 +1,118 @@
+//! Co-op wingman AI. Server-spawned bot pilots fly a real player ship, so
+//! instead of moving the entity directly like `enemy::step` this produces
+//! a `PlayerInput` that `World::tick` feeds through the same flight and
+//! weapon code as a human's. The policy only reads world state, which
+//! keeps `(seed, input_history)` replays reproducible with bots aboard.
+//!
+//! Priorities, highest first: don't hit the ground, attack the nearest
+//! hostile in range, stay near the nearest human, patrol.
+
+use crate::input::PlayerInput;
+use crate::util::{self, Vec2};
+
+/// Below this height above the local ground the wingman stops whatever
+/// it's doing and climbs nose-up on full thrust.
+pub const WINGMAN_PULL_UP_ALTITUDE: f32 = 90.0;
+/// Hostiles farther than this are ignored in favour of escorting.
+pub const WINGMAN_SEEK_RANGE: f32 = 700.0;
+/// Half-angle of the cone (radians) inside which the wingman pulls the
+/// trigger.
+pub const WINGMAN_FIRE_CONE: f32 = 0.2;
+/// Max distance at which the wingman fires.
+pub const WINGMAN_FIRE_RANGE: f32 = 420.0;
+/// Turn stick per radian of heading error.
+pub const WINGMAN_TURN_GAIN: f32 = 4.0;
+/// With nothing to shoot, the wingman closes on its human until inside
+/// this radius, then loiters.
+pub const WINGMAN_ESCORT_DISTANCE: f32 = 160.0;
+/// Height above ground a wingman with no one to escort patrols around.
+pub const WINGMAN_CRUISE_ALTITUDE: f32 = 300.0;
+/// Patrol heading off vertical (radians): climbing right when below
+/// cruise altitude, mirrored into a shallow dive when above, so an
+/// unescorted wingman sweeps the world in a gentle sawtooth.
+const PATROL_ANGLE: f32 = std::f32::consts::FRAC_PI_3;
+/// Seconds a downed wingman waits before `World::tick` respawns it.
+pub const WINGMAN_RESPAWN_DELAY: f32 = 3.0;
+
+/// Pick this tick's input. `altitude` is height above the ground under
+/// the ship; `hostile` and `escort` are wrap-aware offsets from the ship
+/// to the nearest live hostile and the nearest live human, if any.
+pub fn steer(
+    facing: f32,
+    altitude: f32,
+    hostile: Option<Vec2>,
+    escort: Option<Vec2>,
+) -> PlayerInput {
+    if altitude < WINGMAN_PULL_UP_ALTITUDE {
+        return fly_toward(facing, 0.0, true, false);
+    }
+    if let Some(to_hostile) = hostile.filter(|o| o.length() <= WINGMAN_SEEK_RANGE) {
+        let aim = to_hostile.x.atan2(to_hostile.y);
+        let aim_error = util::signed_angular_delta(facing, aim).abs();
+        let fire = aim_error < WINGMAN_FIRE_CONE && to_hostile.length() < WINGMAN_FIRE_RANGE;
+        return fly_toward(facing, aim, true, fire);
+    }
+    match escort {
+        Some(to_escort) if to_escort.length() > WINGMAN_ESCORT_DISTANCE => {
+            fly_toward(facing, to_escort.x.atan2(to_escort.y), true, false)
+        }
+        // Close enough: hover nose-up, thrusting only when below the human.
+        Some(to_escort) => fly_toward(facing, 0.0, to_escort.y > 0.0, false),
+        None if altitude < WINGMAN_CRUISE_ALTITUDE => {
+            fly_toward(facing, PATROL_ANGLE, true, false)
+        }
+        None => fly_toward(facing, std::f32::consts::PI - PATROL_ANGLE, true, false),
+    }
+}
+
+fn fly_toward(facing: f32, aim: f32, thrust: bool, fire: bool) -> PlayerInput {
+    PlayerInput {
+        xaxis: (util::signed_angular_delta(facing, aim) * WINGMAN_TURN_GAIN).clamp(-1.0, 1.0),
+        yaxis: if thrust { 1.0 } else { 0.0 },
+        fire,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const CRUISE: f32 = 250.0;
+
+    #[test]
+    fn pulls_up_near_the_ground_even_with_a_target() {
+        let input = steer(FRAC_PI_2, 40.0, Some(Vec2::new(100.0, 0.0)), None);
+        assert!(input.xaxis < 0.0, "should swing nose-up");
+        assert_eq!(input.yaxis, 1.0);
+        assert!(!input.fire);
+    }
+
+    #[test]
+    fn attacks_a_hostile_in_range() {
+        // Target due right, nose already pointing right: fire.
+        let input = steer(FRAC_PI_2, CRUISE, Some(Vec2::new(200.0, 0.0)), None);
+        assert!(input.fire);
+        // Same target, nose up: turn right first.
+        let input = steer(0.0, CRUISE, Some(Vec2::new(200.0, 0.0)), None);
+        assert!(input.xaxis > 0.0);
+        assert!(!input.fire);
+    }
+
+    #[test]
+    fn escorts_when_hostiles_are_out_of_range() {
+        let far_hostile = Some(Vec2::new(WINGMAN_SEEK_RANGE + 1.0, 0.0));
+        let input = steer(0.0, CRUISE, far_hostile, Some(Vec2::new(-400.0, 0.0)));
+        assert!(input.xaxis < 0.0, "should turn toward the human on the left");
+        assert!(!input.fire);
+    }
+
+    #[test]
+    fn patrols_alone() {
+        let low = steer(0.0, WINGMAN_CRUISE_ALTITUDE - 50.0, None, None);
+        let high = steer(FRAC_PI_2, WINGMAN_CRUISE_ALTITUDE + 50.0, None, None);
+        assert_eq!(low.yaxis, 1.0);
+        assert!(low.xaxis > 0.0, "climb out to the right");
+        assert!(high.xaxis > 0.0, "ease the nose down past level");
+    }
+}